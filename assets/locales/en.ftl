@@ -0,0 +1,330 @@
+# SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+# SPDX-License-Identifier: GPL-3.0-only
+
+# feedback
+feedback-title = Feedback
+feedback-description = Found a bug or have an idea? This opens a prefilled issue on GitHub, nothing is sent until you submit it there.
+feedback-issue-title = Title
+feedback-what-happened = What happened?
+feedback-attach-summary = Attach system information
+feedback-open-issue = Open issue
+
+# mod-suggestions
+mod-suggestions-title = Suggested mods
+mod-suggestions-skip = Skip
+mod-suggestions-install-all = Install all
+
+# download
+download-title = Downloads
+download-empty = Nothing to download
+download-queued = Queued
+download-paused = Paused at { $progress }%
+download-downloading = Downloading... { $progress }%
+download-finished = Download finished!
+download-errored = Something went wrong :(
+download-resume = Resume
+download-pause = Pause
+download-cancel = Cancel
+download-bytes-of = { $downloaded } of { $total }
+download-bytes = { $downloaded } downloaded
+download-details = { $files } of { $total } files, { $downloaded } at { $speed }/s
+download-eta = , { $eta } left
+download-clear-finished = Clear finished
+download-resume-all = Resume all
+download-pause-all = Pause all
+
+# new-instance
+new-instance-title = New instance
+new-instance-vanilla = Vanilla
+new-instance-modrinth = Modrinth
+
+# offline-account
+offline-account-title = Adding offline account
+offline-account-username = Username
+offline-account-add = Add
+
+# authlib-account
+authlib-account-title = authlib-injector account
+authlib-account-server = Authentication server
+authlib-account-username = Username or email
+authlib-account-password = Password
+authlib-account-add = Log in
+authlib-account-ely-by-browser = Log in to ely.by in the browser
+authlib-account-waiting = Waiting for the browser...
+
+# pack-import
+pack-import-empty = Nothing to import
+pack-import-mrpack-title = Import Modrinth modpack
+pack-import-multimc-title = Import MultiMC instance
+pack-import-technic-title = Import Technic modpack
+pack-import-atlauncher-title = Import ATLauncher instance
+pack-import-minecraft = Minecraft { $version }
+pack-import-fabric = Fabric { $version }
+pack-import-name = Instance name
+pack-import-import = Import
+
+# json-viewer
+json-viewer-title = JSON viewer
+json-viewer-select = Select a document
+json-viewer-search = Search
+
+# mod-browser
+mod-browser-title = Mods for { $instance }
+mod-browser-search = Search mods
+mod-browser-downloads = { $downloads } downloads
+mod-browser-results = Showing { $shown } of { $total }
+mod-browser-load-more = Load more
+
+# storage
+storage-title = Storage
+storage-calculating = Calculating...
+storage-shared = Shared files
+storage-instances = Instances
+storage-total = { $size } in total
+
+# about
+about-license = { $license } Licensed
+about-copyright = Copyright © 2023 { $authors }
+about-repository = Repository
+about-send-feedback = Send feedback
+
+# instances
+no-instances = You don't have any instances yet. Create one!
+instances-title = Instances
+instances-account = Account
+instances-running = Running
+instances-update-available = Update available
+instances-trash = Recently deleted
+instances-played-today = Played today
+instances-played-days-ago = Played { $days } days ago
+
+# trash
+trash-title = Recently deleted
+trash-empty = No deleted instances
+trash-days-left = Deleted forever in { $days } days
+trash-restore = Restore
+trash-delete = Delete forever
+
+# login
+login-instructions = Please open up { $url } in a browser and put in the code { $code } to proceed with login
+login-open-page = Open page and copy code
+
+# navbar
+navbar-instances = Instances
+navbar-new-instance = New Instance
+navbar-downloads = Downloads
+navbar-tasks = Tasks
+navbar-offline = Offline, using the saved accounts
+navbar-accounts = Accounts
+navbar-settings = Settings
+navbar-about = About { $name }
+
+# accounts
+accounts-title = Accounts
+accounts-active = Active account
+accounts-others = Other accounts
+accounts-add = Add account
+accounts-add-offline = Add offline account
+accounts-add-authlib = Add authlib-injector account
+
+# instance
+instance-not-found = Instance not found
+instance-minecraft = Minecraft { $version }
+instance-memory = Memory
+instance-window-width = Window width
+instance-window-height = Window height
+instance-pre-launch-hook = Pre-launch command
+instance-post-exit-hook = Post-exit command
+instance-inherited = Inherited: { $value }
+instance-inherited-not-set = Inherited: not set
+instance-playtime = Played for { $playtime }
+instance-jvm-preset = JVM preset
+instance-default-from-settings = Default from settings
+instance-use-default = Use default
+instance-profiling = Collect GC and JFR logs into the diagnostics folder
+instance-gc-summary = Last session: { $pauses } GC pauses, { $average } ms on average, { $max } ms at most
+instance-gc-allocation = , allocating { $rate } MiB/s
+instance-java = Java
+instance-java-managed = Managed
+instance-gpu = GPU
+instance-gpu-default = System default
+instance-tags = Tags
+instance-add-tag = Add tag
+instance-env = Environment variables
+instance-env-name = NAME
+instance-env-value = value
+instance-env-add = Add
+instance-worlds = Worlds
+instance-play-world = Play this world
+instance-world-details = Details
+instance-servers = Servers
+instance-experiment-active = Experiment in progress, you'll be asked to keep or roll back the changes when the game exits
+instance-running = Running (pid { $pid })
+instance-stop = Stop
+instance-play = Play
+instance-start = Start
+instance-server-command = Command
+instance-server-send = Send
+instance-server-properties = Server properties
+instance-optimize = Optimize
+instance-browse-mods = Browse mods
+instance-keep-changes = Keep changes
+instance-roll-back = Roll back
+instance-start-experiment = Start experiment
+instance-launch-demo = Launch demo
+instance-verify = Verify files
+instance-update = Update to { $version }
+instance-classpath-graph = Classpath graph
+instance-export-credits = Export credits
+instance-export-zip = Export as zip
+instance-backups = Backups
+instance-backups-keep = Keep
+instance-back-up-now = Back up now
+instance-backup-restore = Restore
+instance-backup-delete = Delete
+
+# instance export
+instance-export-title = Export { $name }
+instance-export-include = Include in the zip
+instance-export-back = Back
+instance-export-save = Export
+
+# modrinth-modpacks
+modrinth-modpacks-title = Modrinth Modpacks
+modrinth-modpacks-downloads = { $downloads } Downloads
+modrinth-modpacks-search = Search modpacks
+modrinth-modpacks-results = Showing { $shown } of { $total }
+modrinth-modpacks-load-more = Load more
+
+# modrinth-project
+modrinth-project-empty = No project selected
+modrinth-project-loading = Loading the description…
+modrinth-project-authors = By { $authors }
+modrinth-project-downloads = { $downloads } downloads
+modrinth-project-license = License: { $license }
+modrinth-project-loaders = Loaders: { $loaders }
+modrinth-project-versions = Minecraft versions: { $versions }
+modrinth-project-back = Back
+modrinth-project-install = Install
+modrinth-project-previous = Previous
+modrinth-project-next = Next
+modrinth-project-close = Close
+
+# search-filters
+search-filters-version = Any version
+search-filters-loader = Any loader
+search-filters-category = Any category
+search-filters-license = Any license
+search-filters-clear = Clear filters
+search-filters-sort-relevance = Relevance
+search-filters-sort-downloads = Downloads
+search-filters-sort-updated = Recently updated
+search-filters-category-adventure = Adventure
+search-filters-category-decoration = Decoration
+search-filters-category-equipment = Equipment
+search-filters-category-food = Food
+search-filters-category-library = Library
+search-filters-category-magic = Magic
+search-filters-category-mobs = Mobs
+search-filters-category-optimization = Optimization
+search-filters-category-storage = Storage
+search-filters-category-technology = Technology
+search-filters-category-utility = Utility
+search-filters-category-worldgen = World generation
+search-filters-category-challenging = Challenging
+search-filters-category-combat = Combat
+search-filters-category-kitchen-sink = Kitchen sink
+search-filters-category-lightweight = Lightweight
+search-filters-category-multiplayer = Multiplayer
+search-filters-category-quests = Quests
+
+# vanilla-installer
+vanilla-installer-title = Vanilla Installer
+vanilla-installer-name = Instance name
+vanilla-installer-memory = Memory
+vanilla-installer-jvm-preset = JVM preset
+vanilla-installer-default-from-settings = Default from settings
+vanilla-installer-select-version = Select version
+vanilla-installer-search-versions = Search versions
+vanilla-installer-no-patch-notes = No patch notes for this version
+vanilla-installer-select-for-patch-notes = Select a version to see its patch notes
+vanilla-installer-create = Create
+vanilla-installer-server = Dedicated server
+vanilla-installer-fabric = Install Fabric
+
+# settings
+settings-check-for-updates = Automatically check for updates
+settings-minimize-on-launch = Minimize the launcher while a game runs
+settings-crash-reports = Offer to report crashes
+settings-theme = Theme
+settings-java-vendor = Java runtime vendor
+settings-jvm-preset = Default JVM preset
+settings-gc = Garbage collector
+settings-log-level = Log level (after a restart)
+settings-memory = Default memory
+settings-window-width = Default window width
+settings-window-height = Default window height
+settings-pre-launch-hook = Pre-launch command
+settings-post-exit-hook = Post-exit command
+settings-not-set = Not set
+settings-download-threads = Parallel downloads
+settings-download-retries = Download retries
+settings-trash-retention = Days to keep deleted instances
+settings-mirrors = Fall back to mirrors for libraries and assets
+settings-speed-limit = Download speed limit (KiB/s, 0 for unlimited)
+settings-instances-dir = Instances folder
+settings-predownload = Pre-download versions
+settings-json-viewer = JSON viewer
+settings-storage = Storage
+settings-clean = Clean unused files
+settings-export-config = Export configuration
+settings-import-config = Import configuration
+settings-save = Save
+settings-title = Settings
+settings-language = Language
+
+# server properties
+server-properties-title = { $name } properties
+server-properties-back = Back
+server-properties-save = Save
+
+# world
+world-title = World { $world }
+world-empty = No world selected
+world-name = Name
+world-game-mode = Game mode
+world-difficulty = Difficulty
+world-cheats = Allow cheats
+world-version = Last played in
+world-seed = Seed
+world-hardcore = Hardcore world
+world-back = Back
+world-save = Save
+
+# server list
+server-list-title = { $name } servers
+server-list-empty = No servers yet
+server-list-offline = Can't reach the server
+server-list-offline-srv = Can't reach the server, SRV records aren't supported: add the port to the address
+server-list-online = { $online }/{ $max } players, { $latency } ms, { $version }
+server-list-name = Name
+server-list-address = Address
+server-list-add = Add
+server-list-refresh = Refresh
+server-list-back = Back
+
+# command palette
+palette-placeholder = Go to an instance or a page
+palette-no-results = Nothing matches
+
+# notifications
+notifications-more = { $count } more notifications
+notifications-details = Details
+notifications-hide-details = Hide details
+notifications-copy-details = Copy details
+
+# tasks
+tasks-title = Tasks
+tasks-empty = Nothing is running in the background
+tasks-working = Working...
+tasks-cancel = Cancel
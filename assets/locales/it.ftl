@@ -0,0 +1,330 @@
+# SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+# SPDX-License-Identifier: GPL-3.0-only
+
+# feedback
+feedback-title = Feedback
+feedback-description = Hai trovato un bug o hai un'idea? Questo apre una issue precompilata su GitHub, non viene inviato nulla finché non la invii da lì.
+feedback-issue-title = Titolo
+feedback-what-happened = Cosa è successo?
+feedback-attach-summary = Allega le informazioni di sistema
+feedback-open-issue = Apri issue
+
+# mod-suggestions
+mod-suggestions-title = Mod consigliate
+mod-suggestions-skip = Salta
+mod-suggestions-install-all = Installa tutte
+
+# download
+download-title = Download
+download-empty = Niente da scaricare
+download-queued = In coda
+download-paused = In pausa al { $progress }%
+download-downloading = Download in corso... { $progress }%
+download-finished = Download completato!
+download-errored = Qualcosa è andato storto :(
+download-resume = Riprendi
+download-pause = Pausa
+download-cancel = Annulla
+download-bytes-of = { $downloaded } di { $total }
+download-bytes = { $downloaded } scaricati
+download-details = { $files } di { $total } file, { $downloaded } a { $speed }/s
+download-eta = , { $eta } rimanenti
+download-clear-finished = Rimuovi completati
+download-resume-all = Riprendi tutti
+download-pause-all = Metti tutti in pausa
+
+# new-instance
+new-instance-title = Nuova istanza
+new-instance-vanilla = Vanilla
+new-instance-modrinth = Modrinth
+
+# offline-account
+offline-account-title = Aggiunta di un account offline
+offline-account-username = Nome utente
+offline-account-add = Aggiungi
+
+# authlib-account
+authlib-account-title = Account authlib-injector
+authlib-account-server = Server di autenticazione
+authlib-account-username = Nome utente o email
+authlib-account-password = Password
+authlib-account-add = Accedi
+authlib-account-ely-by-browser = Accedi a ely.by nel browser
+authlib-account-waiting = In attesa del browser...
+
+# pack-import
+pack-import-empty = Niente da importare
+pack-import-mrpack-title = Importa modpack Modrinth
+pack-import-multimc-title = Importa istanza MultiMC
+pack-import-technic-title = Importa modpack Technic
+pack-import-atlauncher-title = Importa istanza ATLauncher
+pack-import-minecraft = Minecraft { $version }
+pack-import-fabric = Fabric { $version }
+pack-import-name = Nome dell'istanza
+pack-import-import = Importa
+
+# json-viewer
+json-viewer-title = Visualizzatore JSON
+json-viewer-select = Seleziona un documento
+json-viewer-search = Cerca
+
+# mod-browser
+mod-browser-title = Mod per { $instance }
+mod-browser-search = Cerca mod
+mod-browser-downloads = { $downloads } download
+mod-browser-results = { $shown } di { $total } risultati
+mod-browser-load-more = Carica altri
+
+# storage
+storage-title = Spazio
+storage-calculating = Calcolo in corso...
+storage-shared = File condivisi
+storage-instances = Istanze
+storage-total = { $size } in totale
+
+# about
+about-license = Rilasciato con licenza { $license }
+about-copyright = Copyright © 2023 { $authors }
+about-repository = Repository
+about-send-feedback = Invia feedback
+
+# instances
+no-instances = Non hai ancora nessuna istanza. Creane una!
+instances-title = Istanze
+instances-account = Account
+instances-running = In esecuzione
+instances-update-available = Aggiornamento disponibile
+instances-trash = Eliminate di recente
+instances-played-today = Giocata oggi
+instances-played-days-ago = Giocata { $days } giorni fa
+
+# trash
+trash-title = Eliminate di recente
+trash-empty = Nessuna istanza eliminata
+trash-days-left = Eliminata definitivamente tra { $days } giorni
+trash-restore = Ripristina
+trash-delete = Elimina definitivamente
+
+# login
+login-instructions = Apri { $url } in un browser e inserisci il codice { $code } per procedere con l'accesso
+login-open-page = Apri la pagina e copia il codice
+
+# navbar
+navbar-instances = Istanze
+navbar-new-instance = Nuova istanza
+navbar-downloads = Download
+navbar-tasks = Attività
+navbar-offline = Offline, vengono usati gli account salvati
+navbar-accounts = Account
+navbar-settings = Impostazioni
+navbar-about = Informazioni su { $name }
+
+# accounts
+accounts-title = Account
+accounts-active = Account attivo
+accounts-others = Altri account
+accounts-add = Aggiungi account
+accounts-add-offline = Aggiungi account offline
+accounts-add-authlib = Aggiungi account authlib-injector
+
+# instance
+instance-not-found = Istanza non trovata
+instance-minecraft = Minecraft { $version }
+instance-memory = Memoria
+instance-window-width = Larghezza finestra
+instance-window-height = Altezza finestra
+instance-pre-launch-hook = Comando prima dell'avvio
+instance-post-exit-hook = Comando dopo l'uscita
+instance-inherited = Ereditato: { $value }
+instance-inherited-not-set = Ereditato: non impostato
+instance-playtime = Tempo di gioco: { $playtime }
+instance-jvm-preset = Preset JVM
+instance-default-from-settings = Predefinito dalle impostazioni
+instance-use-default = Usa predefinito
+instance-profiling = Raccogli i log GC e JFR nella cartella di diagnostica
+instance-gc-summary = Ultima sessione: { $pauses } pause del GC, { $average } ms in media, { $max } ms al massimo
+instance-gc-allocation = , allocando { $rate } MiB/s
+instance-java = Java
+instance-java-managed = Gestito
+instance-gpu = GPU
+instance-gpu-default = Predefinita di sistema
+instance-tags = Tag
+instance-add-tag = Aggiungi tag
+instance-env = Variabili d'ambiente
+instance-env-name = NOME
+instance-env-value = valore
+instance-env-add = Aggiungi
+instance-worlds = Mondi
+instance-play-world = Gioca questo mondo
+instance-world-details = Dettagli
+instance-servers = Server
+instance-experiment-active = Esperimento in corso, alla chiusura del gioco ti verrà chiesto se mantenere o annullare le modifiche
+instance-running = In esecuzione (pid { $pid })
+instance-stop = Ferma
+instance-play = Gioca
+instance-start = Avvia
+instance-server-command = Comando
+instance-server-send = Invia
+instance-server-properties = Proprietà del server
+instance-optimize = Ottimizza
+instance-browse-mods = Sfoglia mod
+instance-keep-changes = Mantieni modifiche
+instance-roll-back = Annulla modifiche
+instance-start-experiment = Avvia esperimento
+instance-launch-demo = Avvia demo
+instance-verify = Verifica file
+instance-update = Aggiorna a { $version }
+instance-classpath-graph = Grafo del classpath
+instance-export-credits = Esporta riconoscimenti
+instance-export-zip = Esporta come zip
+instance-backups = Backup
+instance-backups-keep = Conserva
+instance-back-up-now = Esegui backup ora
+instance-backup-restore = Ripristina
+instance-backup-delete = Elimina
+
+# instance export
+instance-export-title = Esporta { $name }
+instance-export-include = Includi nello zip
+instance-export-back = Indietro
+instance-export-save = Esporta
+
+# modrinth-modpacks
+modrinth-modpacks-title = Modpack di Modrinth
+modrinth-modpacks-downloads = { $downloads } download
+modrinth-modpacks-search = Cerca modpack
+modrinth-modpacks-results = { $shown } di { $total } risultati
+modrinth-modpacks-load-more = Carica altri
+
+# modrinth-project
+modrinth-project-empty = Nessun progetto selezionato
+modrinth-project-loading = Caricamento della descrizione…
+modrinth-project-authors = Di { $authors }
+modrinth-project-downloads = { $downloads } download
+modrinth-project-license = Licenza: { $license }
+modrinth-project-loaders = Loader: { $loaders }
+modrinth-project-versions = Versioni di Minecraft: { $versions }
+modrinth-project-back = Indietro
+modrinth-project-install = Installa
+modrinth-project-previous = Precedente
+modrinth-project-next = Successiva
+modrinth-project-close = Chiudi
+
+# search-filters
+search-filters-version = Qualsiasi versione
+search-filters-loader = Qualsiasi loader
+search-filters-category = Qualsiasi categoria
+search-filters-license = Qualsiasi licenza
+search-filters-clear = Rimuovi filtri
+search-filters-sort-relevance = Pertinenza
+search-filters-sort-downloads = Download
+search-filters-sort-updated = Aggiornati di recente
+search-filters-category-adventure = Avventura
+search-filters-category-decoration = Decorazione
+search-filters-category-equipment = Equipaggiamento
+search-filters-category-food = Cibo
+search-filters-category-library = Librerie
+search-filters-category-magic = Magia
+search-filters-category-mobs = Creature
+search-filters-category-optimization = Ottimizzazione
+search-filters-category-storage = Contenitori
+search-filters-category-technology = Tecnologia
+search-filters-category-utility = Utilità
+search-filters-category-worldgen = Generazione del mondo
+search-filters-category-challenging = Impegnativo
+search-filters-category-combat = Combattimento
+search-filters-category-kitchen-sink = Tutto incluso
+search-filters-category-lightweight = Leggero
+search-filters-category-multiplayer = Multigiocatore
+search-filters-category-quests = Missioni
+
+# vanilla-installer
+vanilla-installer-title = Installazione vanilla
+vanilla-installer-name = Nome dell'istanza
+vanilla-installer-memory = Memoria
+vanilla-installer-jvm-preset = Preset JVM
+vanilla-installer-default-from-settings = Predefinito dalle impostazioni
+vanilla-installer-select-version = Seleziona versione
+vanilla-installer-search-versions = Cerca versioni
+vanilla-installer-no-patch-notes = Nessuna nota di rilascio per questa versione
+vanilla-installer-select-for-patch-notes = Seleziona una versione per vederne le note di rilascio
+vanilla-installer-create = Crea
+vanilla-installer-server = Server dedicato
+vanilla-installer-fabric = Installa Fabric
+
+# settings
+settings-check-for-updates = Controlla automaticamente gli aggiornamenti
+settings-minimize-on-launch = Riduci a icona il launcher mentre un gioco è in esecuzione
+settings-crash-reports = Proponi di segnalare i crash
+settings-theme = Tema
+settings-java-vendor = Fornitore del runtime Java
+settings-jvm-preset = Preset JVM predefinito
+settings-gc = Garbage collector
+settings-log-level = Livello di log (dopo un riavvio)
+settings-memory = Memoria predefinita
+settings-window-width = Larghezza finestra predefinita
+settings-window-height = Altezza finestra predefinita
+settings-pre-launch-hook = Comando prima dell'avvio
+settings-post-exit-hook = Comando dopo l'uscita
+settings-not-set = Non impostato
+settings-download-threads = Download paralleli
+settings-download-retries = Tentativi di download
+settings-trash-retention = Giorni di conservazione delle istanze eliminate
+settings-mirrors = Usa i mirror per librerie e risorse in caso di errore
+settings-speed-limit = Limite di velocità di download (KiB/s, 0 per illimitato)
+settings-instances-dir = Cartella delle istanze
+settings-predownload = Versioni da pre-scaricare
+settings-json-viewer = Visualizzatore JSON
+settings-storage = Spazio su disco
+settings-clean = Elimina i file inutilizzati
+settings-export-config = Esporta configurazione
+settings-import-config = Importa configurazione
+settings-save = Salva
+settings-title = Impostazioni
+settings-language = Lingua
+
+# server properties
+server-properties-title = Proprietà di { $name }
+server-properties-back = Indietro
+server-properties-save = Salva
+
+# world
+world-title = Mondo { $world }
+world-empty = Nessun mondo selezionato
+world-name = Nome
+world-game-mode = Modalità di gioco
+world-difficulty = Difficoltà
+world-cheats = Consenti trucchi
+world-version = Ultima versione usata
+world-seed = Seed
+world-hardcore = Mondo hardcore
+world-back = Indietro
+world-save = Salva
+
+# server list
+server-list-title = Server di { $name }
+server-list-empty = Ancora nessun server
+server-list-offline = Server non raggiungibile
+server-list-offline-srv = Server non raggiungibile, i record SRV non sono supportati: aggiungi la porta all'indirizzo
+server-list-online = { $online }/{ $max } giocatori, { $latency } ms, { $version }
+server-list-name = Nome
+server-list-address = Indirizzo
+server-list-add = Aggiungi
+server-list-refresh = Aggiorna
+server-list-back = Indietro
+
+# command palette
+palette-placeholder = Vai a un'istanza o a una pagina
+palette-no-results = Nessun risultato
+
+# notifications
+notifications-more = Altre { $count } notifiche
+notifications-details = Dettagli
+notifications-hide-details = Nascondi dettagli
+notifications-copy-details = Copia dettagli
+
+# tasks
+tasks-title = Attività
+tasks-empty = Niente è in esecuzione in background
+tasks-working = In corso...
+tasks-cancel = Annulla
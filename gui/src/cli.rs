@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use lib::deep_link::DeepLink;
+
+use crate::pages::Page;
+
+pub const USAGE: &str = "Usage:
+  crab-launcher [--instance <name>] [--view <page>]
+  crab-launcher icy-launcher://<link>
+  crab-launcher list
+  crab-launcher launch <instance>
+  crab-launcher create --version <version> <name>
+  crab-launcher verify <instance>";
+
+// run without the gui
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subcommand {
+    List,
+    Launch(String),
+    Create { name: String, version: String },
+    Verify(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Args {
+    pub instance: Option<String>,
+    pub view: Option<Page>,
+    pub subcommand: Option<Subcommand>,
+    // e.g. icy-launcher://install/modrinth/<project>
+    pub deep_link: Option<String>,
+}
+
+impl Args {
+    // what a launcher that's already open is asked to show instead
+    pub fn to_link(&self) -> String {
+        if let Some(link) = &self.deep_link {
+            return link.clone();
+        }
+
+        let link = if let Some(name) = &self.instance {
+            DeepLink::OpenInstance(name.clone())
+        } else if let Some(name) = self.view.as_ref().and_then(|page| {
+            Page::NAMES
+                .into_iter()
+                .find(|name| Page::from_name(name).as_ref() == Some(page))
+        }) {
+            DeepLink::OpenView(name.to_string())
+        } else {
+            DeepLink::Open
+        };
+
+        link.to_url()
+    }
+}
+
+fn parse_subcommand(
+    name: &str,
+    mut iter: impl Iterator<Item = String>,
+) -> Result<Subcommand, String> {
+    let subcommand = match name {
+        "list" => Subcommand::List,
+        "launch" => Subcommand::Launch(iter.next().ok_or("launch requires an instance")?),
+        "verify" => Subcommand::Verify(iter.next().ok_or("verify requires an instance")?),
+        "create" => {
+            let mut version = None;
+            let mut name = None;
+
+            while let Some(arg) = iter.next() {
+                if arg == "--version" {
+                    version = Some(iter.next().ok_or("--version requires a version")?);
+                } else if name.is_none() {
+                    name = Some(arg);
+                } else {
+                    return Err(format!("Unknown argument: {arg}"));
+                }
+            }
+
+            Subcommand::Create {
+                name: name.ok_or("create requires a name")?,
+                version: version.ok_or("create requires --version")?,
+            }
+        }
+        _ => return Err(format!("Unknown argument: {name}")),
+    };
+
+    if let Some(arg) = iter.next() {
+        return Err(format!("Unknown argument: {arg}"));
+    }
+
+    Ok(subcommand)
+}
+
+pub fn parse() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--instance" => {
+                let name = iter.next().ok_or("--instance requires a name")?;
+                args.instance = Some(name);
+            }
+            "--view" => {
+                let name = iter.next().ok_or("--view requires a page")?;
+                let page = Page::from_name(&name).ok_or(format!("Unknown page: {name}"))?;
+                args.view = Some(page);
+            }
+            _ if arg.starts_with(&format!("{}://", lib::deep_link::SCHEME)) => {
+                args.deep_link = Some(arg);
+            }
+            // subcommands take the rest of the arguments
+            _ if args.instance.is_none() && args.view.is_none() && args.deep_link.is_none() => {
+                args.subcommand = Some(parse_subcommand(&arg, iter)?);
+                break;
+            }
+            _ => return Err(format!("Unknown argument: {arg}")),
+        }
+    }
+
+    Ok(args)
+}
@@ -3,10 +3,11 @@
 
 use crate::types::messages::Message;
 use iced::widget::svg;
-use iced::{color, theme, Element};
+use iced::{theme, Element};
 
 #[derive(Debug, Clone)]
 pub enum Icon {
+    AccountOutline,
     AccountPlusOutline,
     AccountAlertOutline,
     AccountCheckOutline,
@@ -22,7 +23,9 @@ pub enum Icon {
     DownloadOutline,
     AlertCircleOutline,
     PlayOutline,
+    Stop,
     FolderOpenOutline,
+    FormatListChecks,
     Github,
     Minecraft,
     Modrinth,
@@ -31,6 +34,7 @@ pub enum Icon {
 impl Icon {
     pub fn view(&self, dimensions: u16) -> Element<Message> {
         let bytes: &[u8] = match self {
+            Icon::AccountOutline => include_bytes!("../../../assets/mdi/account-outline.svg"),
             Icon::AccountPlusOutline => {
                 include_bytes!("../../../assets/mdi/account-plus-outline.svg")
             }
@@ -62,9 +66,13 @@ impl Icon {
                 include_bytes!("../../../assets/mdi/alert-circle-outline.svg")
             }
             Icon::PlayOutline => include_bytes!("../../../assets/mdi/play-outline.svg"),
+            Icon::Stop => include_bytes!("../../../assets/mdi/stop.svg"),
             Icon::FolderOpenOutline => {
                 include_bytes!("../../../assets/mdi/folder-open-outline.svg")
             }
+            Icon::FormatListChecks => {
+                include_bytes!("../../../assets/mdi/format-list-checks.svg")
+            }
             Icon::Github => include_bytes!("../../../assets/simple-icons/github.svg"),
             Icon::Minecraft => include_bytes!("../../../assets/simple-icons/minecraft.svg"),
             Icon::Modrinth => include_bytes!("../../../assets/simple-icons/modrinth.svg"),
@@ -73,8 +81,8 @@ impl Icon {
         let handle = svg::Handle::from_memory(bytes);
 
         svg(handle)
-            .style(theme::Svg::custom_fn(|_theme| svg::Appearance {
-                color: Some(color!(0xe2e8f0)),
+            .style(theme::Svg::custom_fn(|theme| svg::Appearance {
+                color: Some(theme.palette().text),
             }))
             .width(dimensions)
             .height(dimensions)
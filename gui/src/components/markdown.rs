@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_rule, text, Column, Row},
+    Element, Font, Length,
+};
+use lib::markdown::Block;
+
+use crate::style;
+use crate::types::messages::Message;
+
+fn heading_size(level: usize) -> u16 {
+    match level {
+        1 => 28,
+        2 => 24,
+        3 => 20,
+        _ => 18,
+    }
+}
+
+pub fn view<'a>(blocks: &[Block]) -> Element<'a, Message> {
+    let mut col = Column::new().spacing(10);
+
+    for block in blocks {
+        let element: Element<'a, Message> = match block {
+            Block::Heading(level, title) => text(title).size(heading_size(*level)).into(),
+            Block::Paragraph(paragraph) => text(paragraph).into(),
+            Block::ListItem(item) => Row::new()
+                .push(text("•"))
+                .push(text(item))
+                .spacing(5)
+                .into(),
+            Block::Quote(quote) => container(text(quote).size(14))
+                .padding([0, 0, 0, 15])
+                .into(),
+            Block::Code(code) => container(text(code).size(14).font(Font::MONOSPACE))
+                .width(Length::Fill)
+                .padding(10)
+                .style(style::card())
+                .into(),
+            Block::Link(label, url) => button(text(label))
+                .style(theme::Button::Text)
+                .padding(0)
+                .on_press(Message::OpenURL(url.clone()))
+                .into(),
+            Block::Rule => horizontal_rule(1).into(),
+        };
+
+        col = col.push(element);
+    }
+
+    col.into()
+}
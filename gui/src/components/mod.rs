@@ -2,4 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub mod icon;
+pub mod markdown;
 pub mod navbar;
+pub mod notifications;
+pub mod palette;
+pub mod search_filters;
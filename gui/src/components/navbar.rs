@@ -13,6 +13,7 @@ use crate::components::icon::Icon;
 use crate::pages::Page;
 use crate::{style, Message};
 use lib::accounts::Accounts;
+use lib::i18n::{t, t_args};
 
 fn change_view_button<'a>(
     page: Page,
@@ -43,6 +44,7 @@ pub fn view<'a>(
     launcher_name: &'a str,
     current_page: &'a Page,
     accounts: &'a Accounts,
+    offline: bool,
 ) -> Element<'a, Message> {
     let account_icon = {
         if let Some(account) = &accounts.active {
@@ -58,39 +60,63 @@ pub fn view<'a>(
         }
     };
 
-    let col = Column::new()
+    let mut col = Column::new()
         .push(change_view_button(
             Page::Instances,
             current_page,
             Icon::ViewGridOutline.view(32),
-            "Instances",
+            &t("navbar-instances"),
         ))
         .push(change_view_button(
             Page::NewInstance,
             current_page,
             Icon::ViewGridPlusOutline.view(32),
-            "New Instance",
+            &t("navbar-new-instance"),
         ))
         .push(vertical_space(Length::Fill))
+        .push(change_view_button(
+            Page::Tasks,
+            current_page,
+            Icon::FormatListChecks.view(32),
+            &t("navbar-tasks"),
+        ))
+        .push(change_view_button(
+            Page::Download,
+            current_page,
+            Icon::DownloadOutline.view(32),
+            &t("navbar-downloads"),
+        ))
         .push(change_view_button(
             Page::Accounts,
             current_page,
             account_icon,
-            "Accounts",
+            &t("navbar-accounts"),
         ))
         .push(change_view_button(
             Page::Settings,
             current_page,
             Icon::CogOutline.view(32),
-            "Settings",
+            &t("navbar-settings"),
         ))
         .push(change_view_button(
             Page::About,
             current_page,
             Icon::InformationOutline.view(32),
-            &format!("About {}", launcher_name),
+            &t_args("navbar-about", &[("name", &launcher_name)]),
         ))
         .align_items(Alignment::Center);
 
+    if offline {
+        let offline_badge = tooltip(
+            container(Icon::AlertCircleOutline.view(32)).padding(10),
+            t("navbar-offline"),
+            tooltip::Position::Right,
+        )
+        .gap(10)
+        .style(theme::Container::Box);
+
+        col = col.push(offline_badge);
+    }
+
     container(col).style(style::dark()).into()
 }
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, text, Column, Row},
+    Alignment, Element, Font, Length,
+};
+use lib::i18n::{t, t_args};
+
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::notifications::{Notifications, VISIBLE};
+
+pub fn view(notifications: &Notifications) -> Element<Message> {
+    if notifications.queue.is_empty() {
+        return Column::new().into();
+    }
+
+    let mut col = Column::new().spacing(5).padding([10, 10, 0, 10]);
+
+    for notification in notifications.visible() {
+        let mut content = Column::new()
+            .push(text(&notification.title).size(18))
+            .push(text(&notification.text))
+            .spacing(2);
+
+        if let (Some(details), true) = (&notification.details, notification.expanded) {
+            content = content.push(text(details).size(14).font(Font::MONOSPACE));
+        }
+
+        let mut row = Row::new()
+            .push(content)
+            .push(horizontal_space(Length::Fill))
+            .align_items(Alignment::Center)
+            .spacing(5);
+
+        if notification.details.is_some() {
+            row = row
+                .push(
+                    button(text(t(if notification.expanded {
+                        "notifications-hide-details"
+                    } else {
+                        "notifications-details"
+                    })))
+                    .on_press(Message::ToggleNotificationDetails(notification.id))
+                    .style(theme::Button::Text),
+                )
+                .push(
+                    button(text(t("notifications-copy-details")))
+                        .on_press(Message::CopyToClipboard(notification.full_text()))
+                        .style(theme::Button::Text),
+                );
+        }
+
+        for (label, message) in &notification.actions {
+            row = row.push(
+                button(text(label))
+                    .on_press(Message::NotificationAction(
+                        notification.id,
+                        Box::new(message.clone()),
+                    ))
+                    .style(theme::Button::Secondary),
+            );
+        }
+
+        if !notification.required {
+            row = row.push(
+                button(text("×"))
+                    .on_press(Message::DismissNotification(notification.id))
+                    .style(theme::Button::Text),
+            );
+        }
+
+        col = col.push(
+            container(row)
+                .width(Length::Fill)
+                .padding(10)
+                .style(style::notification(notification.severity)),
+        );
+    }
+
+    if notifications.queue.len() > VISIBLE {
+        col = col.push(text(t_args(
+            "notifications-more",
+            &[("count", &(notifications.queue.len() - VISIBLE))],
+        )));
+    }
+
+    col.into()
+}
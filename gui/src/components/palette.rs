@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, text, text_input, Column},
+    Element, Length,
+};
+use lib::i18n::t;
+use lib::instances::Instances;
+
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::palette::{self, Palette};
+
+pub fn view<'a>(palette: &'a Palette, instances: &'a Instances) -> Element<'a, Message> {
+    let input = text_input(&t("palette-placeholder"), &palette.query)
+        .id(palette::input_id())
+        .on_input(Message::PaletteQueryChanged)
+        .on_submit(Message::PaletteSubmit);
+
+    let mut col = Column::new().push(input).spacing(5).padding(10);
+
+    let entries = palette.entries(instances);
+    if entries.is_empty() {
+        col = col.push(text(t("palette-no-results")));
+    }
+
+    for (label, message) in entries {
+        col = col.push(
+            button(text(label))
+                .style(theme::Button::Text)
+                .width(Length::Fill)
+                .on_press(Message::PaletteSelect(Box::new(message))),
+        );
+    }
+
+    container(col)
+        .width(Length::Fill)
+        .padding(10)
+        .style(style::card())
+        .into()
+}
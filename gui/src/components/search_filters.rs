@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+
+use iced::{
+    theme,
+    widget::{button, pick_list, text, text_input},
+    Element,
+};
+use iced_aw::Wrap;
+use lib::i18n::t;
+use lib::instances::Loader;
+use lib::modrinth::{SearchFilters, SortIndex};
+
+use crate::types::messages::Message;
+
+// a category slug, shown translated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Category(&'static str);
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", t(&format!("search-filters-category-{}", self.0)))
+    }
+}
+
+// text fields only search on submit, the pick lists search right away;
+// version and loader are left out when they come from an instance
+pub fn view<'a>(
+    filters: &SearchFilters,
+    categories: &'static [&'static str],
+    with_version: bool,
+    on_input: fn(SearchFilters) -> Message,
+    on_select: fn(SearchFilters) -> Message,
+    on_submit: Message,
+    on_clear: Message,
+) -> Element<'a, Message> {
+    let mut elements: Vec<Element<'a, Message>> = Vec::new();
+
+    if with_version {
+        let current = filters.clone();
+        elements.push(
+            text_input(&t("search-filters-version"), &filters.minecraft_version)
+                .on_input(move |minecraft_version| {
+                    on_input(SearchFilters {
+                        minecraft_version,
+                        ..current.clone()
+                    })
+                })
+                .on_submit(on_submit.clone())
+                .width(150)
+                .into(),
+        );
+
+        let current = filters.clone();
+        elements.push(
+            pick_list(
+                // quilt packs can't be imported
+                vec![Loader::Fabric],
+                filters.loader,
+                move |loader| {
+                    on_select(SearchFilters {
+                        loader: Some(loader),
+                        ..current.clone()
+                    })
+                },
+            )
+            .placeholder(t("search-filters-loader"))
+            .into(),
+        );
+    }
+
+    let current = filters.clone();
+    let selected = categories
+        .iter()
+        .find(|category| **category == filters.category)
+        .map(|category| Category(category));
+    elements.push(
+        pick_list(
+            categories
+                .iter()
+                .map(|category| Category(category))
+                .collect::<Vec<_>>(),
+            selected,
+            move |category| {
+                on_select(SearchFilters {
+                    category: category.0.to_string(),
+                    ..current.clone()
+                })
+            },
+        )
+        .placeholder(t("search-filters-category"))
+        .into(),
+    );
+
+    let current = filters.clone();
+    elements.push(
+        text_input(&t("search-filters-license"), &filters.license)
+            .on_input(move |license| {
+                on_input(SearchFilters {
+                    license,
+                    ..current.clone()
+                })
+            })
+            .on_submit(on_submit)
+            .width(150)
+            .into(),
+    );
+
+    let current = filters.clone();
+    elements.push(
+        pick_list(&SortIndex::ALL[..], Some(filters.sort), move |sort| {
+            on_select(SearchFilters {
+                sort,
+                ..current.clone()
+            })
+        })
+        .into(),
+    );
+
+    elements.push(
+        button(text(t("search-filters-clear")))
+            .style(theme::Button::Text)
+            .on_press(on_clear)
+            .into(),
+    );
+
+    Wrap::with_elements(elements)
+        .spacing(5.)
+        .line_spacing(5.)
+        .into()
+}
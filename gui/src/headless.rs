@@ -0,0 +1,144 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anyhow::{anyhow, bail, Result};
+use iced::futures::executor::block_on;
+use lib::accounts::Accounts;
+use lib::instances::{Instance, Instances, LaunchOptions, Loader};
+use lib::running::RunningGames;
+use lib::settings::Settings;
+use lib::{DownloadControl, DownloadEvent, DownloadQueue};
+
+use crate::cli::Subcommand;
+
+pub fn run(subcommand: Subcommand) -> Result<()> {
+    let settings = Settings::load()?;
+    let mut instances = Instances::load(settings.instances_dir())?;
+
+    match subcommand {
+        Subcommand::List => {
+            for (name, info) in &instances.list {
+                let loader = match info.loader {
+                    Loader::Vanilla => String::new(),
+                    loader => format!(" ({loader:?})"),
+                };
+
+                println!("{name}\t{}{loader}", info.minecraft);
+            }
+        }
+        Subcommand::Launch(name) => {
+            let mut accounts = Accounts::load()?;
+            let mut running = RunningGames::load()?;
+
+            // same choice as the gui
+            let account = instances
+                .list
+                .get(&name)
+                .ok_or_else(|| anyhow!("Instance not found: {name}"))?
+                .account
+                .as_ref()
+                .and_then(|id| accounts.find(id))
+                .or(accounts.active.as_ref())
+                .cloned()
+                .ok_or_else(|| anyhow!("No account selected"))?;
+
+            let offline = !lib::network::is_online();
+
+            let account = if offline {
+                println!("No network connection, launching offline");
+                account
+            } else {
+                accounts.refresh_account(account)?
+            };
+
+            let options = LaunchOptions {
+                offline,
+                ..Default::default()
+            };
+
+            // a failed backup shouldn't keep the game from starting
+            if let Some(info) = instances.list.get(&name) {
+                if let Err(error) = block_on(lib::backups::run_scheduled(
+                    name.clone(),
+                    instances.get_dir(&name),
+                    info.clone(),
+                )) {
+                    println!("Failed to back up {name}: {error:#}");
+                }
+            }
+
+            if let Some(hook) = instances.get_pre_launch_hook(&name, &settings) {
+                block_on(lib::hooks::run_pre_launch(
+                    hook,
+                    instances.get_dir(&name),
+                    name.clone(),
+                ))?;
+            }
+
+            let mut child = instances.launch(&name, &account, &settings, &options, &mut running)?;
+
+            println!("Launched {name} (pid {})", child.id());
+
+            let status = child.wait()?;
+            running.remove(child.id())?;
+            instances.run_post_exit_hook(&name, &settings)?;
+
+            if !status.success() {
+                bail!("{name} exited with {status}");
+            }
+        }
+        Subcommand::Create { name, version } => {
+            let versions = block_on(lib::vanilla_installer::get_versions())?;
+            if !versions.iter().any(|v| v.id == version) {
+                bail!("Version {version} not found");
+            }
+
+            let info = Instance::new(version.clone(), Loader::Vanilla, None, None, None);
+            instances.create(name.clone(), info)?;
+
+            let queue = lib::vanilla_installer::download_version(&version, settings.java_vendor)?;
+            download(queue, &settings)?;
+
+            println!("Created {name}");
+        }
+        Subcommand::Verify(name) => {
+            let info = instances
+                .list
+                .get(&name)
+                .ok_or_else(|| anyhow!("Instance not found: {name}"))?;
+
+            let queue = block_on(lib::vanilla_installer::verify_instance(
+                info.clone(),
+                settings.java_vendor,
+            ))?;
+
+            if queue.is_empty() {
+                println!("All files of {name} are fine");
+            } else {
+                println!("Repairing {} files", queue.len());
+                download(queue, &settings)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn download(queue: DownloadQueue, settings: &Settings) -> Result<()> {
+    let total = queue.len();
+    let receiver = queue.start(settings.download_options(), DownloadControl::default());
+    let mut downloaded = 0;
+
+    for event in receiver {
+        match event {
+            DownloadEvent::Finished(_, item) => {
+                downloaded += 1;
+                println!("[{downloaded}/{total}] {}", item.path.display());
+            }
+            DownloadEvent::Failed(error) => return Err(error),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
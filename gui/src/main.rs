@@ -8,16 +8,88 @@ use iced::{executor, theme, Application, Color, Command, Element, Settings, Subs
 use crate::types::launcher::Launcher;
 use crate::types::messages::Message;
 
+mod cli;
 mod components;
+mod format;
+mod headless;
 mod pages;
 mod style;
 mod subscriptions;
 mod types;
 
 pub const LOGO_PNG: &[u8] = include_bytes!("../../assets/logo-128x128.png");
+pub const LOGO_LIGHT_PNG: &[u8] = include_bytes!("../../assets/logo-128x128-light.png");
+
+pub fn logo(theme: lib::settings::Theme) -> &'static [u8] {
+    match theme {
+        lib::settings::Theme::Dark => LOGO_PNG,
+        lib::settings::Theme::Light => LOGO_LIGHT_PNG,
+    }
+}
+
+// release builds have no console of their own, output from the command line would
+// be lost without the one of the terminal
+#[cfg(windows)]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // fails when not started from a terminal, there's nothing to print to then
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
 
 pub fn main() -> iced::Result {
-    let mut settings = Settings::default();
+    #[cfg(windows)]
+    if std::env::args_os().len() > 1 {
+        attach_console();
+    }
+
+    // the launcher reads them again, these are only needed for the log level
+    let log_level = lib::settings::Settings::load()
+        .map(|settings| settings.log_level)
+        .unwrap_or_default();
+
+    // dropping it would lose the last lines
+    let _log_guard = match lib::logging::init(log_level) {
+        Ok(guard) => Some(guard),
+        Err(error) => {
+            eprintln!("logging to file is disabled: {error}");
+            None
+        }
+    };
+
+    lib::crash_reporter::install_hook(env!("CARGO_PKG_VERSION"));
+
+    let args = match cli::parse() {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}");
+            eprintln!("{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(subcommand) = args.subcommand {
+        if let Err(error) = headless::run(subcommand) {
+            eprintln!("{error:#}");
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+
+    // a launcher is already open, it takes over instead of opening a second one
+    if lib::ipc::send(&args.to_link()).is_ok() {
+        return Ok(());
+    }
+
+    let mut settings = Settings::with_flags(args);
     let icon = iced::window::icon::from_file_data(LOGO_PNG, None).unwrap();
     settings.window.icon = Some(icon);
 
@@ -28,10 +100,10 @@ impl Application for Launcher {
     type Executor = executor::Default;
     type Message = Message;
     type Theme = Theme;
-    type Flags = ();
+    type Flags = cli::Args;
 
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        Launcher::new()
+    fn new(flags: cli::Args) -> (Self, Command<Message>) {
+        Launcher::new(flags)
     }
 
     fn title(&self) -> String {
@@ -47,9 +119,14 @@ impl Application for Launcher {
     }
 
     fn theme(&self) -> Self::Theme {
+        let base = match self.settings.theme {
+            lib::settings::Theme::Dark => Theme::Dark,
+            lib::settings::Theme::Light => Theme::Light,
+        };
+
         Theme::custom(theme::Palette {
             primary: Color::from_rgb8(192, 101, 33),
-            ..Theme::Dark.palette()
+            ..base.palette()
         })
     }
 
@@ -4,25 +4,26 @@
 use iced::widget::image;
 use iced::{
     theme,
-    widget::{button, horizontal_space, text, vertical_space, Column, Row},
+    widget::{button, container, horizontal_space, text, vertical_space, Column, Row},
     Alignment, Element, Length,
 };
 
 use crate::components::icon::Icon;
-use crate::{style, Message, LOGO_PNG};
+use crate::pages::Page;
+use crate::{logo, style, Message};
+use lib::i18n::{t, t_args};
+use lib::settings::Theme;
 
 const APP_VERSION: &str = concat!("v", env!("CARGO_PKG_VERSION"));
-const LICENSE: &str = concat!(env!("CARGO_PKG_LICENSE"), " Licensed");
-const COPYRIGHT: &str = concat!("Copyright © 2023 ", env!("CARGO_PKG_AUTHORS"));
 const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
 
-pub fn view(launcher_name: &'static str) -> Element<'static, Message> {
-    let logo = image::Handle::from_memory(LOGO_PNG);
+pub fn view(launcher_name: &'static str, theme: Theme) -> Element<'static, Message> {
+    let logo = image::Handle::from_memory(logo(theme));
     let logo = image(logo).width(100).height(100);
 
     let repo_button = button(
         Row::new()
-            .push(text(" Repository "))
+            .push(text(format!(" {} ", t("about-repository"))))
             .push(Icon::Github.view(24))
             .align_items(Alignment::Center)
             .padding(5),
@@ -30,17 +31,29 @@ pub fn view(launcher_name: &'static str) -> Element<'static, Message> {
     .style(style::circle_button(theme::Button::Primary))
     .on_press(Message::OpenURL(REPOSITORY.to_string()));
 
+    let feedback_button = button(container(text(t("about-send-feedback"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Feedback));
+
     let footer = Row::new()
         .push(horizontal_space(Length::Fill))
-        .push(repo_button);
+        .push(feedback_button)
+        .push(repo_button)
+        .spacing(10);
 
     Column::new()
         .push(vertical_space(Length::Fill))
         .push(logo)
         .push(text(launcher_name).size(50))
         .push(text(APP_VERSION))
-        .push(text(LICENSE))
-        .push(text(COPYRIGHT))
+        .push(text(t_args(
+            "about-license",
+            &[("license", &env!("CARGO_PKG_LICENSE"))],
+        )))
+        .push(text(t_args(
+            "about-copyright",
+            &[("authors", &env!("CARGO_PKG_AUTHORS"))],
+        )))
         .push(vertical_space(Length::Fill))
         .push(footer)
         .spacing(10)
@@ -4,17 +4,50 @@
 use crate::components::icon::Icon;
 use iced::{
     theme,
-    widget::{button, container, horizontal_space, scrollable, text, vertical_space, Column, Row},
+    widget::{
+        button, container, horizontal_space, image, scrollable, text, vertical_space, Column, Row,
+    },
     Alignment, Element, Length,
 };
 use iced_aw::floating_element;
-use lib::accounts::Accounts;
+use lib::accounts::{Account, Accounts};
+use lib::i18n::t;
+use lib::skin::SkinPreview;
 
 use crate::pages::Page;
 use crate::style;
 use crate::types::messages::Message;
 
-pub fn view(accounts: &Accounts) -> Element<Message> {
+fn head(account: &Account) -> Element<Message> {
+    match &account.cached_head {
+        Some(cached_head) => image(image::Handle::from_memory(cached_head.clone()))
+            .width(32)
+            .height(32)
+            .into(),
+        None => Icon::AccountOutline.view(32),
+    }
+}
+
+// front and back side by side, at half the rendered size
+fn skin(preview: &SkinPreview) -> Element<'_, Message> {
+    let side = |png: &Vec<u8>| {
+        image(image::Handle::from_memory(png.clone()))
+            .width(64)
+            .height(128)
+    };
+
+    Row::new()
+        .push(side(&preview.front))
+        .push(side(&preview.back))
+        .spacing(20)
+        .padding(10)
+        .into()
+}
+
+pub fn view<'a>(
+    accounts: &'a Accounts,
+    skin_preview: Option<&'a SkinPreview>,
+) -> Element<'a, Message> {
     let mut content = Column::new()
         .width(Length::Fill)
         .height(Length::Fill)
@@ -22,6 +55,7 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
 
     if let Some(active_account) = &accounts.active {
         let row = Row::new()
+            .push(head(active_account))
             .push(text(&active_account.mc_username))
             .push(horizontal_space(Length::Fill))
             .push(
@@ -30,11 +64,19 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
                     .style(style::circle_button(theme::Button::Destructive)),
             )
             .align_items(Alignment::Center)
-            .padding(10);
+            .padding(10)
+            .spacing(10);
 
-        let active = container(row).style(style::card());
+        let mut active = Column::new().push(row);
+        if let Some(preview) =
+            skin_preview.filter(|preview| preview.account_id == active_account.mc_id)
+        {
+            active = active.push(skin(preview));
+        }
+
+        let active = container(active).style(style::card());
         content = content
-            .push(text("Active account"))
+            .push(text(t("accounts-active")))
             .push(active)
             .push(vertical_space(10));
     }
@@ -44,6 +86,7 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
 
         for account in &accounts.others {
             let row = Row::new()
+                .push(head(account))
                 .push(text(&account.mc_username))
                 .push(horizontal_space(Length::Fill))
                 .push(
@@ -58,13 +101,13 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
                 )
                 .align_items(Alignment::Center)
                 .padding(10)
-                .spacing(5);
+                .spacing(10);
 
             others = others.push(container(row).style(style::card()));
         }
 
         content = content
-            .push(text("Other accounts"))
+            .push(text(t("accounts-others")))
             .push(scrollable(others));
     }
 
@@ -78,7 +121,7 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
         {
             let add_offline_account_button = button(
                 Row::new()
-                    .push(text(" Add offline account "))
+                    .push(text(format!(" {} ", t("accounts-add-offline"))))
                     .push(Icon::AccountPlusOutline.view(24))
                     .align_items(Alignment::Center)
                     .padding(5),
@@ -89,9 +132,20 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
             row = row.push(add_offline_account_button);
         }
 
+        let add_authlib_account_button = button(
+            Row::new()
+                .push(text(format!(" {} ", t("accounts-add-authlib"))))
+                .push(Icon::AccountPlusOutline.view(24))
+                .align_items(Alignment::Center)
+                .padding(5),
+        )
+        .on_press(Message::ChangePage(Page::AddingAuthlibAccount))
+        .style(style::circle_button(theme::Button::Secondary));
+        row = row.push(add_authlib_account_button);
+
         let add_account_button = button(
             Row::new()
-                .push(text(" Add account "))
+                .push(text(format!(" {} ", t("accounts-add"))))
                 .push(Icon::AccountPlusOutline.view(24))
                 .align_items(Alignment::Center)
                 .padding(5),
@@ -103,7 +157,7 @@ pub fn view(accounts: &Accounts) -> Element<Message> {
     });
 
     Column::new()
-        .push(text("Accounts").size(30))
+        .push(text(t("accounts-title")).size(30))
         .push(content)
         .spacing(10)
         .padding(10)
@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::widget::{button, container, text, text_input, vertical_space, Column, Row};
+use iced::{theme, Alignment, Element, Length};
+use lib::accounts::ELY_BY_CLIENT_SECRET;
+use lib::authlib_injector::ELY_BY_SERVER;
+use lib::i18n::t;
+
+use crate::style;
+use crate::types::login::AuthlibLogin;
+use crate::types::messages::Message;
+
+pub fn view(login: &AuthlibLogin) -> Element<Message> {
+    let title = text(t("authlib-account-title")).size(30);
+
+    let server = text_input("https://example.com/api/yggdrasil", &login.server)
+        .width(300)
+        .on_input(Message::AuthlibServerChanged);
+
+    let presets = Row::new().push(
+        button(text("ely.by"))
+            .on_press(Message::AuthlibServerChanged(ELY_BY_SERVER.to_string()))
+            .style(theme::Button::Secondary),
+    );
+
+    let username = text_input(&t("authlib-account-username"), &login.username)
+        .width(300)
+        .on_input(Message::AuthlibUsernameChanged);
+
+    let password = text_input(&t("authlib-account-password"), &login.password)
+        .width(300)
+        .password()
+        .on_input(Message::AuthlibPasswordChanged)
+        .on_submit(Message::AddAuthlibAccount);
+
+    let add_button = button(container(text(t("authlib-account-add"))).padding(5))
+        .on_press(Message::AddAuthlibAccount)
+        .style(style::circle_button(theme::Button::Primary));
+
+    let mut col = Column::new()
+        .push(vertical_space(Length::Fill))
+        .push(title)
+        .push(text(t("authlib-account-server")))
+        .push(server)
+        .push(presets)
+        .push(username)
+        .push(password)
+        .push(add_button);
+
+    // ely.by accounts can also log in without handing over the password
+    if ELY_BY_CLIENT_SECRET.is_some() {
+        col = col.push(
+            button(container(text(t("authlib-account-ely-by-browser"))).padding(5))
+                .on_press(Message::AddElyByAccount)
+                .style(style::circle_button(theme::Button::Secondary)),
+        );
+    }
+
+    col.push(vertical_space(Length::Fill))
+        .width(Length::Fill)
+        .spacing(20)
+        .align_items(Alignment::Center)
+        .into()
+}
@@ -3,18 +3,19 @@
 
 use iced::{Alignment, Element, Length, theme};
 use iced::widget::{button, Column, container, text, text_input, vertical_space};
+use lib::i18n::t;
 use crate::style;
 
 use crate::types::messages::Message;
 
 pub fn view(username: &str) -> Element<Message> {
-    let title = text("Adding offline account").size(30);
+    let title = text(t("offline-account-title")).size(30);
 
-    let username_input = text_input("Username", username)
+    let username_input = text_input(&t("offline-account-username"), username)
         .width(200)
         .on_input(Message::OfflineAccountUsernameChanged);
 
-    let add_button = button(container(text("Add")).padding(5))
+    let add_button = button(container(text(t("offline-account-add"))).padding(5))
         .on_press(Message::AddOfflineAccount)
         .style(style::circle_button(theme::Button::Primary));
 
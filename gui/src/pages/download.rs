@@ -2,38 +2,155 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use iced::{
-    Alignment,
-    Element, Length, widget::{Column, progress_bar, text, vertical_space},
+    theme,
+    widget::{button, container, horizontal_space, progress_bar, scrollable, text, Column, Row},
+    Alignment, Element, Length,
 };
 
-use crate::types::download::{Download, State};
+use lib::i18n::{t, t_args};
+
+use crate::format::{format_bytes, format_duration};
+use crate::style;
+use crate::types::download::{Download, Job, State};
 use crate::types::messages::Message;
 
-pub fn view(download: &Download) -> Element<Message> {
-    let current_progress = match &download.state {
-        State::Idle { .. } => 0.0,
-        State::Downloading { progress, queue: _ } => *progress,
-        State::Finished { .. } => 100.0,
-        State::Errored { .. } => 0.0,
+fn job_view(job: &Job) -> Element<Message> {
+    let current_progress = match &job.state {
+        State::Queued => 0.0,
+        State::Downloading { stats, .. } => stats.percentage(),
+        State::Finished => 100.0,
+        State::Errored => 0.0,
+    };
+
+    let status = match &job.state {
+        State::Queued => t("download-queued"),
+        State::Downloading { .. } if job.is_paused() => t_args(
+            "download-paused",
+            &[("progress", &format!("{current_progress:.2}"))],
+        ),
+        State::Downloading { .. } => t_args(
+            "download-downloading",
+            &[("progress", &format!("{current_progress:.2}"))],
+        ),
+        State::Finished => t("download-finished"),
+        State::Errored => t("download-errored"),
     };
 
-    let progress_bar = progress_bar(0.0..=100.0, current_progress);
+    let mut buttons = Row::new().spacing(10);
+
+    if let State::Downloading { .. } = &job.state {
+        let pause_button = if job.is_paused() {
+            button(container(text(t("download-resume"))).padding(5))
+                .on_press(Message::ResumeDownload(job.id))
+        } else {
+            button(container(text(t("download-pause"))).padding(5))
+                .on_press(Message::PauseDownload(job.id))
+        };
+
+        buttons = buttons.push(pause_button.style(style::circle_button(theme::Button::Secondary)));
+    }
+
+    if !job.is_done() {
+        buttons = buttons.push(
+            button(container(text(t("download-cancel"))).padding(5))
+                .style(style::circle_button(theme::Button::Destructive))
+                .on_press(Message::CancelDownload(job.id)),
+        );
+    }
+
+    let header = Row::new()
+        .push(text(&job.name))
+        .push(horizontal_space(Length::Fill))
+        .push(buttons)
+        .align_items(Alignment::Center);
+
+    let mut col = Column::new()
+        .push(header)
+        .push(progress_bar(0.0..=100.0, current_progress).height(10))
+        .push(text(status))
+        .spacing(10)
+        .padding(10);
+
+    if let State::Downloading { stats, .. } = &job.state {
+        let downloaded = if stats.total_bytes > 0 {
+            t_args(
+                "download-bytes-of",
+                &[
+                    ("downloaded", &format_bytes(stats.downloaded_bytes as f64)),
+                    ("total", &format_bytes(stats.total_bytes as f64)),
+                ],
+            )
+        } else {
+            t_args(
+                "download-bytes",
+                &[("downloaded", &format_bytes(stats.bytes as f64))],
+            )
+        };
+
+        let mut details = t_args(
+            "download-details",
+            &[
+                ("files", &stats.downloaded),
+                ("total", &stats.total),
+                ("downloaded", &downloaded),
+                ("speed", &format_bytes(stats.speed)),
+            ],
+        );
+
+        if let Some(eta) = stats.eta {
+            details.push_str(&t_args("download-eta", &[("eta", &format_duration(eta))]));
+        }
+
+        col = col.push(text(details));
+
+        for name in &stats.current {
+            col = col.push(text(name).size(14));
+        }
+    }
+
+    container(col).style(style::card()).into()
+}
+
+pub fn view(download: &Download) -> Element<Message> {
+    let title = text(t("download-title")).size(30);
+
+    if download.jobs.is_empty() {
+        return Column::new()
+            .push(title)
+            .push(text(t("download-empty")))
+            .spacing(10)
+            .padding(10)
+            .into();
+    }
+
+    let mut list = Column::new().spacing(10);
+    for job in &download.jobs {
+        list = list.push(job_view(job));
+    }
+
+    let clear_button = button(container(text(t("download-clear-finished"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ClearFinishedDownloads);
+
+    let pause_button = if download.paused {
+        button(container(text(t("download-resume-all"))).padding(5))
+            .on_press(Message::ResumeAllDownloads)
+    } else {
+        button(container(text(t("download-pause-all"))).padding(5))
+            .on_press(Message::PauseAllDownloads)
+    };
 
-    let current_progress = format!("Downloading... {current_progress:.2}%");
-    let text = text(match &download.state {
-        State::Idle => "Starting download",
-        State::Finished => "Download finished!",
-        State::Downloading { .. } => &current_progress,
-        State::Errored => "Something went wrong :(",
-    });
+    let footer = Row::new()
+        .push(horizontal_space(Length::Fill))
+        .push(pause_button.style(style::circle_button(theme::Button::Secondary)))
+        .push(clear_button)
+        .spacing(10);
 
     Column::new()
-        .push(vertical_space(Length::Fill))
-        .push(text)
-        .push(progress_bar)
-        .push(vertical_space(Length::Fill))
+        .push(title)
+        .push(scrollable(list).height(Length::Fill))
+        .push(footer)
         .spacing(10)
         .padding(10)
-        .align_items(Alignment::Center)
         .into()
 }
@@ -2,21 +2,44 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::components::icon::Icon;
-use iced::widget::{text, vertical_space, Column};
-use iced::{Alignment, Element, Length};
+use iced::widget::{button, container, scrollable, text, vertical_space, Column};
+use iced::{theme, Alignment, Element, Font, Length};
+use lib::i18n::t;
 
+use crate::style;
 use crate::types::messages::Message;
 
 pub fn view(err: &str) -> Element<Message> {
-    let error = text(err).size(30);
+    // the first line says what failed, the rest why
+    let (summary, details) = match err.split_once('\n') {
+        Some((summary, details)) => (summary, Some(details)),
+        None => (err, None),
+    };
 
-    Column::new()
+    let mut col = Column::new()
         .push(vertical_space(Length::Fill))
         .push(Icon::AlertCircleOutline.view(64))
-        .push(error)
-        .push(vertical_space(Length::Fill))
+        .push(text(summary).size(30));
+
+    if let Some(details) = details {
+        col = col
+            .push(
+                container(scrollable(text(details).font(Font::MONOSPACE)))
+                    .max_height(200)
+                    .padding(10)
+                    .style(style::card()),
+            )
+            .push(
+                button(text(t("notifications-copy-details")))
+                    .on_press(Message::CopyToClipboard(err.to_string()))
+                    .style(theme::Button::Secondary),
+            );
+    }
+
+    col.push(vertical_space(Length::Fill))
         .width(Length::Fill)
         .align_items(Alignment::Center)
         .spacing(10)
+        .padding(10)
         .into()
 }
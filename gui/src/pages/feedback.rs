@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{
+        button, container, horizontal_space, text, text_input, toggler, vertical_space, Column, Row,
+    },
+    Alignment, Element, Length,
+};
+
+use lib::i18n::t;
+
+use crate::components::icon::Icon;
+use crate::style;
+use crate::types::feedback::Feedback;
+use crate::types::messages::Message;
+
+pub fn view<'a>(feedback: &'a Feedback, summary: String) -> Element<'a, Message> {
+    let title = text(t("feedback-title")).size(30);
+
+    let description = text(t("feedback-description"));
+
+    let form = Column::new()
+        .push(text(t("feedback-issue-title")))
+        .push(text_input("", &feedback.title).on_input(Message::FeedbackTitleChanged))
+        .push(text(t("feedback-what-happened")))
+        .push(text_input("", &feedback.description).on_input(Message::FeedbackDescriptionChanged))
+        .push(toggler(
+            t("feedback-attach-summary"),
+            feedback.attach_summary,
+            Message::SetAttachFeedbackSummary,
+        ))
+        .spacing(10)
+        .padding(10);
+
+    let mut col = Column::new()
+        .push(title)
+        .push(description)
+        .push(container(form).style(style::card()))
+        .spacing(10)
+        .padding(10);
+
+    if feedback.attach_summary {
+        col = col.push(
+            container(text(summary).size(14))
+                .width(Length::Fill)
+                .padding(10)
+                .style(style::card()),
+        );
+    }
+
+    let mut submit_button = button(
+        Row::new()
+            .push(text(format!(" {} ", t("feedback-open-issue"))))
+            .push(Icon::Github.view(24))
+            .align_items(Alignment::Center)
+            .padding(5),
+    )
+    .style(style::circle_button(theme::Button::Primary));
+
+    if !feedback.title.is_empty() {
+        submit_button = submit_button.on_press(Message::SubmitFeedback);
+    }
+
+    col.push(vertical_space(Length::Fill))
+        .push(
+            Row::new()
+                .push(horizontal_space(Length::Fill))
+                .push(submit_button),
+        )
+        .into()
+}
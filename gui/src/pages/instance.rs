@@ -0,0 +1,562 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{
+        button, container, horizontal_space, pick_list, scrollable, text, text_input, toggler,
+        vertical_space, Column, Row,
+    },
+    Alignment, Element, Font, Length,
+};
+use lib::backups::{self, Backup, BackupSchedule};
+use lib::diagnostics::GcSummary;
+use lib::gpu::Gpu;
+use lib::i18n::{t, t_args};
+use lib::instances::{InheritedSetting, InstanceKind, Instances, Loader};
+use lib::jvm::JvmPreset;
+use lib::running::RunningGames;
+use lib::runtime_manager::system::JavaInstallation;
+use lib::settings::Settings;
+use lib::vanilla_installer::LatestRelease;
+
+use crate::components::icon::Icon;
+use crate::format::{format_bytes, format_playtime};
+use crate::style;
+use crate::types::instance_editor::InstanceEditor;
+use crate::types::messages::Message;
+use crate::types::server_console::ServerConsole;
+
+#[allow(clippy::too_many_arguments)]
+pub fn view<'a>(
+    instances: &'a Instances,
+    settings: &'a Settings,
+    running: &'a RunningGames,
+    java_installations: &'a [JavaInstallation],
+    gpus: &'a [Gpu],
+    gc_summary: Option<&'a GcSummary>,
+    backups: &'a [Backup],
+    editor: &'a InstanceEditor,
+    console: Option<&'a ServerConsole>,
+    latest_release: Option<&'a LatestRelease>,
+    name: &'a str,
+) -> Element<'a, Message> {
+    let Some(info) = instances.list.get(name) else {
+        return text(t("instance-not-found")).into();
+    };
+
+    let mut details = Column::new()
+        .push(text(t_args(
+            "instance-minecraft",
+            &[("version", &info.minecraft)],
+        )))
+        .spacing(10)
+        .padding(10);
+
+    if let Some(loader_version) = &info.loader_version {
+        details = details.push(text(format!("{} {}", info.loader, loader_version)));
+    }
+
+    // empty fields show the default from the settings
+    for setting in InheritedSetting::ALL {
+        let value = editor
+            .drafts
+            .get(&setting)
+            .cloned()
+            .or_else(|| setting.get_override(info));
+
+        let placeholder = match setting.get_default(settings) {
+            Some(default) => t_args("instance-inherited", &[("value", &default)]),
+            None => t("instance-inherited-not-set"),
+        };
+
+        let mut row = Row::new()
+            .push(text(t(&format!("instance-{}", setting.key()))))
+            .push(
+                text_input(&placeholder, value.as_deref().unwrap_or_default())
+                    .on_input(move |value| {
+                        Message::SetInstanceInherited(name.to_string(), setting, Some(value))
+                    })
+                    .width(200),
+            )
+            .align_items(Alignment::Center)
+            .spacing(10);
+
+        if value.is_some() {
+            row = row.push(
+                button(text(t("instance-use-default")))
+                    .on_press(Message::SetInstanceInherited(
+                        name.to_string(),
+                        setting,
+                        None,
+                    ))
+                    .style(theme::Button::Text),
+            );
+        }
+
+        if let Some(Err(error)) = value.as_deref().map(|value| setting.validate(value)) {
+            row = row.push(text(error));
+        }
+        details = details.push(row);
+    }
+
+    if info.playtime > 0 {
+        details = details.push(text(t_args(
+            "instance-playtime",
+            &[("playtime", &format_playtime(info.playtime))],
+        )));
+    }
+
+    let mut jvm_preset = Row::new()
+        .push(text(t("instance-jvm-preset")))
+        .push(
+            pick_list(&JvmPreset::ALL[..], info.jvm_preset, move |jvm_preset| {
+                Message::SetInstanceJvmPreset(name.to_string(), Some(jvm_preset))
+            })
+            .placeholder(t("instance-default-from-settings")),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+
+    if info.jvm_preset.is_some() {
+        jvm_preset = jvm_preset.push(
+            button(text(t("instance-use-default")))
+                .on_press(Message::SetInstanceJvmPreset(name.to_string(), None))
+                .style(theme::Button::Text),
+        );
+    }
+    details = details.push(jvm_preset);
+
+    if gpus.len() > 1 && info.kind == InstanceKind::Client {
+        let selected = gpus
+            .iter()
+            .find(|gpu| info.gpu.as_ref() == Some(&gpu.id))
+            .cloned();
+
+        let mut gpu = Row::new()
+            .push(text(t("instance-gpu")))
+            .push(
+                pick_list(gpus, selected, move |gpu| {
+                    Message::SetInstanceGpu(name.to_string(), Some(gpu.id))
+                })
+                .placeholder(t("instance-gpu-default")),
+            )
+            .align_items(Alignment::Center)
+            .spacing(10);
+
+        if info.gpu.is_some() {
+            gpu = gpu.push(
+                button(text(t("instance-use-default")))
+                    .on_press(Message::SetInstanceGpu(name.to_string(), None))
+                    .style(theme::Button::Text),
+            );
+        }
+        details = details.push(gpu);
+    }
+
+    let profiling = toggler(t("instance-profiling"), info.profiling, move |profiling| {
+        Message::SetInstanceProfiling(name.to_string(), profiling)
+    });
+    details = details.push(profiling);
+
+    if let Some(summary) = gc_summary {
+        let mut text_summary = t_args(
+            "instance-gc-summary",
+            &[
+                ("pauses", &summary.pauses),
+                ("average", &format!("{:.1}", summary.average_pause_ms())),
+                ("max", &format!("{:.1}", summary.max_pause_ms)),
+            ],
+        );
+
+        if let Some(allocation_rate) = summary.allocation_rate {
+            text_summary.push_str(&t_args(
+                "instance-gc-allocation",
+                &[("rate", &format!("{allocation_rate:.0}"))],
+            ));
+        }
+
+        details = details.push(text(text_summary));
+    }
+
+    let selected_java = java_installations
+        .iter()
+        .find(|java| match &info.java_path {
+            Some(path) => !java.managed && &java.path == path,
+            None => java.managed && java.version == info.java.as_deref().unwrap_or("17"),
+        })
+        .cloned();
+
+    let java = Row::new()
+        .push(text(t("instance-java")))
+        .push(
+            pick_list(java_installations, selected_java, move |java| {
+                Message::SetInstanceJava(name.to_string(), java)
+            })
+            .placeholder(match &info.java_path {
+                Some(path) => path.display().to_string(),
+                None => t("instance-java-managed"),
+            }),
+        )
+        .push(
+            button(Icon::FolderOpenOutline.view(24))
+                .on_press(Message::ChooseJavaBinary(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+    details = details.push(java);
+
+    let mut tags = Row::new()
+        .push(text(t("instance-tags")))
+        .align_items(Alignment::Center)
+        .spacing(5);
+    for tag in &info.tags {
+        tags = tags.push(
+            button(text(format!("{tag} ×")))
+                .on_press(Message::RemoveInstanceTag(name.to_string(), tag.clone()))
+                .style(theme::Button::Secondary),
+        );
+    }
+    tags = tags.push(
+        text_input(&t("instance-add-tag"), &editor.new_tag)
+            .on_input(Message::NewTagChanged)
+            .on_submit(Message::AddInstanceTag(
+                name.to_string(),
+                editor.new_tag.to_string(),
+            ))
+            .width(150),
+    );
+
+    // autocomplete from the tags of other instances
+    if !editor.new_tag.is_empty() {
+        let suggestions = instances
+            .get_tags()
+            .into_iter()
+            .filter(|tag| tag.starts_with(editor.new_tag.as_str()) && !info.tags.contains(tag))
+            .take(5);
+
+        for tag in suggestions {
+            tags = tags.push(
+                button(text(&tag))
+                    .on_press(Message::AddInstanceTag(name.to_string(), tag))
+                    .style(theme::Button::Text),
+            );
+        }
+    }
+    details = details.push(tags);
+
+    let mut env = Column::new().push(text(t("instance-env"))).spacing(5);
+    for (key, value) in &info.env {
+        env = env.push(
+            Row::new()
+                .push(text(format!("{key}={value}")))
+                .push(
+                    button(Icon::DeleteOutline.view(16))
+                        .on_press(Message::RemoveInstanceEnv(name.to_string(), key.clone()))
+                        .style(theme::Button::Text),
+                )
+                .align_items(Alignment::Center)
+                .spacing(5),
+        );
+    }
+    env = env.push(
+        Row::new()
+            .push(
+                text_input(&t("instance-env-name"), &editor.env_key)
+                    .on_input(Message::EnvKeyChanged)
+                    .width(200),
+            )
+            .push(
+                text_input(&t("instance-env-value"), &editor.env_value)
+                    .on_input(Message::EnvValueChanged)
+                    .on_submit(Message::AddInstanceEnv(name.to_string()))
+                    .width(200),
+            )
+            .push(
+                button(text(t("instance-env-add")))
+                    .on_press(Message::AddInstanceEnv(name.to_string()))
+                    .style(theme::Button::Secondary),
+            )
+            .spacing(5),
+    );
+    details = details.push(env);
+
+    let is_server = info.kind == InstanceKind::Server;
+
+    // quick play is a client feature
+    let worlds = instances.get_worlds(name);
+    if !is_server && !worlds.is_empty() {
+        let mut list = Column::new().push(text(t("instance-worlds"))).spacing(5);
+        for world in worlds {
+            list = list.push(
+                Row::new()
+                    .push(text(&world))
+                    .push(
+                        button(text(t("instance-play-world")))
+                            .on_press(Message::QuickPlay(name.to_string(), world.clone()))
+                            .style(theme::Button::Secondary),
+                    )
+                    .push(
+                        button(text(t("instance-world-details")))
+                            .on_press(Message::EditWorld(name.to_string(), world.clone()))
+                            .style(theme::Button::Secondary),
+                    )
+                    .align_items(Alignment::Center)
+                    .spacing(5),
+            );
+        }
+        details = details.push(list);
+    }
+
+    if !is_server {
+        details = details.push(
+            button(text(t("instance-servers")))
+                .on_press(Message::EditServerList(name.to_string()))
+                .style(theme::Button::Secondary),
+        );
+
+        let retention = info.backup_retention.map(|r| r.to_string());
+        let settings_row = Row::new()
+            .push(text(t("instance-backups")))
+            .push(pick_list(
+                &BackupSchedule::ALL[..],
+                Some(info.backup_schedule),
+                move |schedule| Message::SetBackupSchedule(name.to_string(), schedule),
+            ))
+            .push(text(t("instance-backups-keep")))
+            .push(
+                text_input(
+                    &backups::DEFAULT_RETENTION.to_string(),
+                    retention.as_deref().unwrap_or_default(),
+                )
+                .on_input(move |value| Message::SetBackupRetention(name.to_string(), value))
+                .width(60),
+            )
+            .push(
+                button(text(t("instance-back-up-now")))
+                    .on_press(Message::BackUpInstance(name.to_string()))
+                    .style(theme::Button::Secondary),
+            )
+            .align_items(Alignment::Center)
+            .spacing(10);
+
+        let mut list = Column::new().push(settings_row).spacing(5);
+        for backup in backups {
+            list = list.push(
+                Row::new()
+                    .push(text(format!(
+                        "{} ({})",
+                        backup.label(),
+                        format_bytes(backup.size as f64)
+                    )))
+                    .push(
+                        button(text(t("instance-backup-restore")))
+                            .on_press(Message::RestoreBackup(
+                                name.to_string(),
+                                backup.path.clone(),
+                            ))
+                            .style(theme::Button::Secondary),
+                    )
+                    .push(
+                        button(text(t("instance-backup-delete")))
+                            .on_press(Message::DeleteBackup(name.to_string(), backup.path.clone()))
+                            .style(theme::Button::Destructive),
+                    )
+                    .align_items(Alignment::Center)
+                    .spacing(5),
+            );
+        }
+        details = details.push(list);
+    }
+
+    if !info.notes.is_empty() {
+        details = details.push(text(&info.notes));
+    }
+
+    let experiment = lib::experiments::is_active(&instances.get_dir(name));
+    if experiment {
+        details = details.push(text(t("instance-experiment-active")));
+    }
+
+    let game = running.get(name);
+
+    let play_button = if let Some(game) = game {
+        details = details.push(text(t_args("instance-running", &[("pid", &game.pid)])));
+
+        button(
+            Row::new()
+                .push(text(format!(" {} ", t("instance-stop"))))
+                .push(Icon::Stop.view(24))
+                .align_items(Alignment::Center)
+                .padding(5),
+        )
+        .on_press(Message::TerminateInstance(name.to_string()))
+        .style(style::circle_button(theme::Button::Destructive))
+    } else {
+        button(
+            Row::new()
+                .push(text(format!(
+                    " {} ",
+                    t(if is_server {
+                        "instance-start"
+                    } else {
+                        "instance-play"
+                    })
+                )))
+                .push(Icon::PlayOutline.view(24))
+                .align_items(Alignment::Center)
+                .padding(5),
+        )
+        .on_press(Message::LaunchInstance(name.to_string()))
+        .style(style::circle_button(theme::Button::Primary))
+    };
+
+    let mut actions = Row::new().push(horizontal_space(Length::Fill)).push(
+        button(container(text(t("instance-optimize"))).padding(5))
+            .on_press(Message::OptimizeInstance(name.to_string()))
+            .style(style::circle_button(theme::Button::Secondary)),
+    );
+
+    // mods need a loader
+    if info.loader != Loader::Vanilla {
+        actions = actions.push(
+            button(container(text(t("instance-browse-mods"))).padding(5))
+                .on_press(Message::OpenModBrowser(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        );
+    }
+
+    let actions = if experiment {
+        actions
+            .push(
+                button(container(text(t("instance-keep-changes"))).padding(5))
+                    .on_press(Message::EndExperiment(name.to_string(), true))
+                    .style(style::circle_button(theme::Button::Secondary)),
+            )
+            .push(
+                button(container(text(t("instance-roll-back"))).padding(5))
+                    .on_press(Message::EndExperiment(name.to_string(), false))
+                    .style(style::circle_button(theme::Button::Destructive)),
+            )
+    } else {
+        actions.push(
+            button(container(text(t("instance-start-experiment"))).padding(5))
+                .on_press(Message::StartExperiment(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+    };
+
+    let actions = if is_server {
+        actions.push(
+            button(container(text(t("instance-server-properties"))).padding(5))
+                .on_press(Message::EditServerProperties(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+    } else {
+        actions.push(
+            button(container(text(t("instance-launch-demo"))).padding(5))
+                .on_press(Message::LaunchDemo(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+    };
+
+    let actions = match latest_release.filter(|latest| info.can_update_to(latest)) {
+        Some(latest) => actions.push(
+            button(
+                container(text(t_args("instance-update", &[("version", &latest.id)]))).padding(5),
+            )
+            .on_press(Message::UpdateInstance(name.to_string()))
+            .style(style::circle_button(theme::Button::Primary)),
+        ),
+        None => actions,
+    };
+
+    let actions = actions
+        .push(
+            button(container(text(t("instance-verify"))).padding(5))
+                .on_press(Message::VerifyInstance(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(container(text(t("instance-classpath-graph"))).padding(5))
+                .on_press(Message::ExportClasspathGraph(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(container(text(t("instance-export-credits"))).padding(5))
+                .on_press(Message::ExportCredits(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(container(text(t("instance-export-zip"))).padding(5))
+                .on_press(Message::OpenInstanceExport(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(Icon::FolderOpenOutline.view(24))
+                .on_press(Message::OpenInstanceFolder(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(Icon::CogOutline.view(24))
+                .on_press(Message::OpenInstanceConfig(name.to_string()))
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .push(
+            button(Icon::DeleteOutline.view(24))
+                .on_press(Message::DeleteInstance(name.to_string()))
+                .style(style::circle_button(theme::Button::Destructive)),
+        )
+        .push(play_button)
+        .align_items(Alignment::Center)
+        .spacing(5);
+
+    let console: Element<Message> = match console {
+        Some(console) => {
+            let mut lines = Column::new();
+            for line in &console.lines {
+                lines = lines.push(text(line).size(12).font(Font::MONOSPACE));
+            }
+
+            let input = Row::new()
+                .push(
+                    text_input(&t("instance-server-command"), &console.input)
+                        .on_input(move |input| Message::ServerInputChanged(name.to_string(), input))
+                        .on_submit(Message::SendServerCommand(name.to_string())),
+                )
+                .push(
+                    button(text(t("instance-server-send")))
+                        .on_press(Message::SendServerCommand(name.to_string()))
+                        .style(theme::Button::Secondary),
+                )
+                .spacing(5);
+
+            Column::new()
+                .push(
+                    container(
+                        scrollable(lines)
+                            .id(scrollable::Id::new("server-console"))
+                            .width(Length::Fill)
+                            .height(Length::Fill),
+                    )
+                    .padding(10)
+                    .style(style::card()),
+                )
+                .push(input)
+                .spacing(5)
+                .height(Length::Fill)
+                .into()
+        }
+        None => vertical_space(Length::Fill).into(),
+    };
+
+    Column::new()
+        .push(text(name).size(30))
+        .push(container(details).width(Length::Fill).style(style::card()))
+        .push(console)
+        .push(actions)
+        .spacing(10)
+        .padding(10)
+        .into()
+}
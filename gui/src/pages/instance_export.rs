@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, checkbox, container, horizontal_space, text, Column, Row},
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::instance_export::ExportPart;
+
+use crate::pages::Page;
+use crate::types::messages::Message;
+use crate::{components::icon::Icon, style};
+
+pub fn view<'a>(parts: &'a [ExportPart], name: &'a str) -> Element<'a, Message> {
+    let mut col = Column::new()
+        .push(text(t("instance-export-include")))
+        .padding(10)
+        .spacing(10);
+
+    for part in ExportPart::ALL {
+        col = col.push(checkbox(
+            part.to_string(),
+            parts.contains(&part),
+            move |_| Message::ToggleExportPart(part),
+        ));
+    }
+
+    let back_button = button(container(text(t("instance-export-back"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Instance(name.to_string())));
+
+    let export_button = button(
+        Row::new()
+            .push(text(format!(" {} ", t("instance-export-save"))))
+            .push(Icon::ContentSaveOutline.view(24))
+            .padding(5)
+            .align_items(Alignment::Center),
+    )
+    .style(style::circle_button(theme::Button::Positive))
+    .on_press(Message::ExportInstance(name.to_string()));
+
+    Column::new()
+        .push(text(t_args("instance-export-title", &[("name", &name)])).size(30))
+        .push(
+            container(col)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(style::card()),
+        )
+        .push(
+            Row::new()
+                .push(back_button)
+                .push(horizontal_space(Length::Fill))
+                .push(export_button),
+        )
+        .spacing(10)
+        .padding(10)
+        .into()
+}
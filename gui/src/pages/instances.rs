@@ -1,32 +1,123 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use iced::widget::{button, horizontal_space, image, scrollable, text, Column, Row};
-use iced::{theme, Element, Length};
+use std::collections::HashMap;
+
+use iced::widget::{
+    button, container, horizontal_space, image, pick_list, scrollable, text, Column, Row,
+};
+use iced::{theme, Alignment, Element, Length};
 use iced_aw::helpers::card;
 use iced_aw::{CardStyles, Wrap};
-use lib::instances::Instances;
+use lib::accounts::Accounts;
+use lib::i18n::{t, t_args};
+use lib::instances::{Instance, Instances, Loader};
+use lib::settings::InstancesView;
+use lib::store::DiskUsage;
 
 use crate::components::icon::Icon;
-use crate::{pages::no_instances, style, Message, LOGO_PNG};
+use crate::format::format_bytes;
+use crate::pages::Page;
+use crate::{logo, pages::no_instances, style, Message};
+use lib::settings::Theme;
+use lib::vanilla_installer::LatestRelease;
+
+fn chip<'a>(label: String) -> Element<'a, Message> {
+    container(text(label).size(14))
+        .padding([2, 8])
+        .style(style::badge())
+        .into()
+}
+
+// version, loader, size and last played
+fn chips<'a>(info: &Instance, size: Option<u64>) -> Vec<Element<'a, Message>> {
+    let mut chips = vec![chip(info.minecraft.clone())];
+
+    if info.loader != Loader::Vanilla {
+        chips.push(chip(info.loader.to_string()));
+    }
+    if let Some(size) = size {
+        chips.push(chip(format_bytes(size as f64)));
+    }
+    if let Some(days) = info.days_since_played() {
+        chips.push(chip(match days {
+            0 => t("instances-played-today"),
+            days => t_args("instances-played-days-ago", &[("days", &days.to_string())]),
+        }));
+    }
 
-pub fn view(instances: &Instances) -> Element<Message> {
+    chips
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn view<'a>(
+    instances: &'a Instances,
+    accounts: &'a Accounts,
+    running_instances: &'a HashMap<String, u32>,
+    tag_filter: &'a [String],
+    latest_release: Option<&'a LatestRelease>,
+    disk_usage: Option<&'a DiskUsage>,
+    instances_view: InstancesView,
+    theme: Theme,
+) -> Element<'a, Message> {
     if instances.list.is_empty() {
         return no_instances::view();
     }
 
+    let mut tags = Row::new().spacing(5);
+    for tag in instances.get_tags() {
+        let style = if tag_filter.contains(&tag) {
+            style::selected_button()
+        } else {
+            theme::Button::Secondary
+        };
+
+        tags = tags.push(
+            button(text(&tag))
+                .style(style)
+                .on_press(Message::ToggleTagFilter(tag)),
+        );
+    }
+
     let mut wrap = Wrap::new().spacing(10.);
-    for (name, _) in &instances.list {
-        let logo = image::Handle::from_memory(LOGO_PNG);
-        let logo = image(logo).width(100).height(100);
+    let mut list = Column::new().spacing(10);
+    for (name, info) in &instances.list {
+        if !tag_filter.iter().all(|tag| info.tags.contains(tag)) {
+            continue;
+        }
+
+        let logo_size = match instances_view {
+            InstancesView::Grid => 100,
+            InstancesView::List => 40,
+        };
+
+        let logo: Element<Message> = if let Some(icon) = &info.icon {
+            let handle = image::Handle::from_path(instances.get_dir(name).join(&icon.path));
+            let icon_image = image(handle).width(logo_size).height(logo_size);
+
+            match &icon.background {
+                Some(background) => container(icon_image)
+                    .style(style::icon_background(background))
+                    .into(),
+                None => icon_image.into(),
+            }
+        } else {
+            let handle = image::Handle::from_memory(logo(theme));
+            image(handle).width(logo_size).height(logo_size).into()
+        };
+
+        let play_button = if running_instances.contains_key(name) {
+            button(Icon::Stop.view(24))
+                .on_press(Message::TerminateInstance(name.clone()))
+                .style(style::circle_button(theme::Button::Destructive))
+        } else {
+            button(Icon::PlayOutline.view(24))
+                .on_press(Message::LaunchInstance(name.clone()))
+                .style(style::circle_button(theme::Button::Secondary))
+        };
 
         let actions = Row::new()
-            .push(horizontal_space(Length::Fill))
-            .push(
-                button(Icon::PlayOutline.view(24))
-                    .on_press(Message::LaunchInstance(name.clone()))
-                    .style(style::circle_button(theme::Button::Secondary)),
-            )
+            .push(play_button)
             .push(
                 button(Icon::CogOutline.view(24))
                     .on_press(Message::OpenInstanceConfig(name.clone()))
@@ -42,21 +133,130 @@ pub fn view(instances: &Instances) -> Element<Message> {
                     .on_press(Message::OpenInstanceFolder(name.clone()))
                     .style(style::circle_button(theme::Button::Secondary)),
             )
-            .push(horizontal_space(Length::Fill))
             .spacing(5);
 
-        let card = card(logo, text(name))
-            .foot(actions)
-            .style(CardStyles::Secondary)
-            .width(Length::Fixed(200.));
+        let selected_account = info
+            .account
+            .as_ref()
+            .and_then(|id| accounts.find(id))
+            .or(accounts.active.as_ref())
+            .cloned();
+
+        let account_picker = pick_list(accounts.list(), selected_account, |account| {
+            Message::SetInstanceAccount(name.clone(), account)
+        })
+        .placeholder(t("instances-account"));
+
+        let title = button(text(name))
+            .style(theme::Button::Text)
+            .padding(0)
+            .on_press(Message::ChangePage(Page::Instance(name.clone())));
+
+        let size = disk_usage.and_then(|usage| {
+            usage
+                .instances
+                .iter()
+                .find(|(instance, _)| instance == name)
+                .map(|(_, size)| *size)
+        });
+
+        let mut chips = chips(info, size);
+        if running_instances.contains_key(name) {
+            chips.push(chip(t("instances-running")));
+        }
+        if latest_release.is_some_and(|latest| info.can_update_to(latest)) {
+            chips.push(chip(t("instances-update-available")));
+        }
+
+        match instances_view {
+            InstancesView::Grid => {
+                // the chips wrap, the card is narrow
+                let badges = Wrap::with_elements(chips).spacing(5.).line_spacing(5.);
+
+                let body = Column::new()
+                    .push(title)
+                    .push(badges)
+                    .push(account_picker.width(Length::Fill))
+                    .spacing(5);
+
+                let actions = Row::new()
+                    .push(horizontal_space(Length::Fill))
+                    .push(actions)
+                    .push(horizontal_space(Length::Fill));
+
+                let card = card(logo, body)
+                    .foot(actions)
+                    .style(CardStyles::Secondary)
+                    .width(Length::Fixed(200.));
+
+                wrap = wrap.push(card);
+            }
+            InstancesView::List => {
+                let row = Row::new()
+                    .push(logo)
+                    .push(
+                        Column::new()
+                            .push(title)
+                            .push(Row::with_children(chips).spacing(5))
+                            .spacing(5),
+                    )
+                    .push(horizontal_space(Length::Fill))
+                    .push(account_picker.width(200))
+                    .push(actions)
+                    .align_items(Alignment::Center)
+                    .spacing(10)
+                    .padding(10);
+
+                list = list.push(container(row).style(style::card()));
+            }
+        }
+    }
+
+    let content: Element<Message> = match instances_view {
+        InstancesView::Grid => scrollable(wrap)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+        InstancesView::List => scrollable(list)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+    };
+
+    let mut view_buttons = Row::new().spacing(5);
+    for view in InstancesView::ALL {
+        let style = if view == instances_view {
+            style::selected_button()
+        } else {
+            theme::Button::Secondary
+        };
 
-        wrap = wrap.push(card);
+        view_buttons = view_buttons.push(
+            button(text(view.to_string()))
+                .style(style)
+                .on_press(Message::SetInstancesView(view)),
+        );
     }
 
-    let content = scrollable(wrap).width(Length::Fill).height(Length::Fill);
+    let title = Row::new()
+        .push(text(t("instances-title")).size(30))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            button(text(t("instances-trash")))
+                .style(theme::Button::Text)
+                .on_press(Message::ChangePage(Page::Trash)),
+        )
+        .push(view_buttons)
+        .align_items(Alignment::Center)
+        .spacing(10);
 
     Column::new()
-        .push(text("Instances").size(30))
+        .push(title)
+        .push(
+            scrollable(tags).direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::default(),
+            )),
+        )
         .push(content)
         .spacing(10)
         .padding(10)
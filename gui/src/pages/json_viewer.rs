@@ -0,0 +1,70 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    font,
+    widget::{container, pick_list, scrollable, text, text_input, Column, Row},
+    Alignment, Element, Font, Length,
+};
+
+use lib::i18n::t;
+
+use crate::style;
+use crate::types::json_viewer::JsonViewer;
+use crate::types::messages::Message;
+
+// past this, rendering the whole document gets slow
+const MAX_LINES: usize = 2000;
+
+pub fn view(json_viewer: &JsonViewer) -> Element<Message> {
+    let title = text(t("json-viewer-title")).size(30);
+
+    let header = Row::new()
+        .push(
+            pick_list(
+                &json_viewer.documents[..],
+                json_viewer.selected.clone(),
+                Message::SelectJsonDocument,
+            )
+            .placeholder(t("json-viewer-select"))
+            .width(Length::Fill),
+        )
+        .push(
+            text_input(&t("json-viewer-search"), &json_viewer.search)
+                .on_input(Message::JsonSearchChanged)
+                .width(200),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+
+    let search = json_viewer.search.to_lowercase();
+
+    // line numbers are kept so matches can be found in the file
+    let lines = json_viewer
+        .content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| search.is_empty() || line.to_lowercase().contains(&search))
+        .take(MAX_LINES)
+        .map(|(i, line)| format!("{:>6}  {}", i + 1, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let monospace = Font {
+        family: font::Family::Monospace,
+        ..Font::DEFAULT
+    };
+
+    let content = container(text(lines).font(monospace).size(14))
+        .width(Length::Fill)
+        .padding(10)
+        .style(style::card());
+
+    Column::new()
+        .push(title)
+        .push(header)
+        .push(scrollable(content).height(Length::Fill))
+        .spacing(10)
+        .padding(10)
+        .into()
+}
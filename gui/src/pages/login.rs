@@ -4,18 +4,20 @@
 use iced::{Alignment, Element, Length, theme};
 use iced::widget::{button, Column, container, text, vertical_space};
 
+use lib::i18n::{t, t_args};
+
 use crate::style;
 use crate::types::login::Login;
 use crate::types::messages::Message;
 
 pub fn view(login: &Login) -> Element<Message> {
-    let message = text(format!(
-        "Please open up {} in a browser and put in the code {} to proceed with login",
-        login.url, login.code
+    let message = text(t_args(
+        "login-instructions",
+        &[("url", &login.url), ("code", &login.code)],
     ))
         .size(20);
 
-    let open_button = button(container(text("Open page and copy code")).padding(5))
+    let open_button = button(container(text(t("login-open-page"))).padding(5))
         .style(style::circle_button(theme::Button::Primary))
         .on_press(Message::OpenLoginUrl);
 
@@ -3,24 +3,40 @@
 
 mod about;
 mod accounts;
+mod adding_authlib_account;
 mod adding_offline_account;
 mod download;
 mod error;
+mod feedback;
+mod instance;
+mod instance_export;
 mod instances;
+mod json_viewer;
 mod login;
+mod mod_browser;
+mod mod_suggestions;
 mod modrinth_modpacks;
+mod modrinth_project;
 mod new_instance;
 mod no_instances;
+mod pack_import;
 pub mod root;
+mod server_list;
+mod server_properties;
 mod settings;
 mod status;
+mod storage;
+mod tasks;
+mod trash;
 mod vanilla_installer;
+mod world;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Page {
     Status(String),
     Error(String),
     Instances,
+    Instance(String),
     NewInstance,
     VanillaInstaller,
     Settings,
@@ -28,6 +44,59 @@ pub enum Page {
     Accounts,
     AddingAccount,
     AddingOfflineAccount,
+    AddingAuthlibAccount,
     Download,
     ModrinthModpacks,
+    ModrinthProject,
+    ModSuggestions,
+    JsonViewer,
+    Storage,
+    Feedback,
+    ModBrowser,
+    PackImport,
+    ServerProperties(String),
+    InstanceExport(String),
+    Tasks,
+    Trash,
+    // instance name, world folder
+    World(String, String),
+    ServerList(String),
+}
+
+impl Page {
+    // everything from_name knows, for the command palette
+    pub const NAMES: [&'static str; 13] = [
+        "instances",
+        "new-instance",
+        "vanilla-installer",
+        "modrinth-modpacks",
+        "downloads",
+        "tasks",
+        "trash",
+        "accounts",
+        "settings",
+        "about",
+        "json-viewer",
+        "storage",
+        "feedback",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "instances" => Some(Self::Instances),
+            "new-instance" => Some(Self::NewInstance),
+            "vanilla-installer" => Some(Self::VanillaInstaller),
+            "modrinth-modpacks" => Some(Self::ModrinthModpacks),
+            "downloads" => Some(Self::Download),
+            "tasks" => Some(Self::Tasks),
+            "trash" => Some(Self::Trash),
+            "accounts" => Some(Self::Accounts),
+            "settings" => Some(Self::Settings),
+            "about" => Some(Self::About),
+            "json-viewer" => Some(Self::JsonViewer),
+            "storage" => Some(Self::Storage),
+            "feedback" => Some(Self::Feedback),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{
+        button, container, horizontal_space, image, scrollable, text, text_input, Column, Row,
+    },
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::modrinth::{ModList, MOD_CATEGORIES};
+
+use crate::components::{icon::Icon, search_filters};
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::mod_browser::ModBrowser;
+
+pub fn view(mod_browser: &ModBrowser) -> Element<Message> {
+    let title = text(t_args(
+        "mod-browser-title",
+        &[("instance", &mod_browser.instance)],
+    ))
+    .size(30);
+
+    let mut lists = Row::new().spacing(5);
+    for list in ModList::ALL {
+        let style = if list == mod_browser.list {
+            style::selected_button()
+        } else {
+            theme::Button::Text
+        };
+
+        lists = lists.push(
+            button(text(list.to_string()))
+                .style(style)
+                .on_press(Message::SelectModList(list)),
+        );
+    }
+
+    let search = text_input(&t("mod-browser-search"), &mod_browser.query)
+        .on_input(Message::ModQueryChanged)
+        .on_submit(Message::BrowseMods);
+
+    let filters = search_filters::view(
+        &mod_browser.filters,
+        &MOD_CATEGORIES,
+        false,
+        Message::ModFiltersChanged,
+        Message::SetModFilters,
+        Message::BrowseMods,
+        Message::SelectModList(mod_browser.list),
+    );
+
+    let mut projects = Column::new().spacing(10).padding([0, 20, 0, 0]);
+    for project in &mod_browser.projects {
+        let cover: Element<Message> = match mod_browser.images.get(&project.project_id) {
+            Some(handle) => image(handle.clone()).width(64).height(64).into(),
+            None => Icon::PackageVariant.view(64),
+        };
+
+        let info = Column::new()
+            .push(text(&project.title).size(20))
+            .push(text(&project.description).size(14))
+            .push(
+                text(t_args(
+                    "mod-browser-downloads",
+                    &[("downloads", &project.downloads)],
+                ))
+                .size(14),
+            )
+            .spacing(5);
+
+        // the details page has the full description
+        let info = button(info)
+            .style(theme::Button::Text)
+            .on_press(Message::OpenModrinthProject(project.clone()));
+
+        let row = Row::new()
+            .push(cover)
+            .push(info)
+            .push(horizontal_space(Length::Fill))
+            .push(
+                button(Icon::DownloadOutline.view(24))
+                    .on_press(Message::InstallMod(project.clone()))
+                    .style(style::circle_button(theme::Button::Primary)),
+            )
+            .align_items(Alignment::Center)
+            .padding(10)
+            .spacing(10);
+
+        projects = projects.push(container(row).style(style::card()));
+    }
+
+    let shown = mod_browser.projects.len();
+    let total = mod_browser.total_hits;
+    if shown < total {
+        projects =
+            projects.push(button(text(t("mod-browser-load-more"))).on_press(Message::LoadMoreMods));
+    }
+
+    let count = text(t_args(
+        "mod-browser-results",
+        &[("shown", &shown), ("total", &total)],
+    ))
+    .size(14);
+
+    Column::new()
+        .push(title)
+        .push(
+            scrollable(lists).direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::default(),
+            )),
+        )
+        .push(search)
+        .push(filters)
+        .push(count)
+        .push(scrollable(projects).height(Length::Fill))
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, scrollable, text, Column, Row},
+    Alignment, Element, Length,
+};
+
+use lib::i18n::t;
+
+use crate::components::icon::Icon;
+use crate::pages::Page;
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::mod_suggestions::ModSuggestions;
+
+pub fn view(mod_suggestions: &ModSuggestions) -> Element<Message> {
+    let title = text(t("mod-suggestions-title")).size(30);
+
+    let description = text(&mod_suggestions.description);
+
+    let mut list = Column::new().spacing(10);
+    for suggestion in &mod_suggestions.suggestions {
+        let info = Column::new()
+            .push(text(&suggestion.title).size(20))
+            .push(text(&suggestion.description).size(14))
+            .spacing(5);
+
+        let row = Row::new()
+            .push(info)
+            .push(text(&suggestion.version.name))
+            .push(horizontal_space(Length::Fill))
+            .push(
+                button(Icon::DownloadOutline.view(24))
+                    .on_press(Message::InstallSuggestion(suggestion.clone()))
+                    .style(style::circle_button(theme::Button::Primary)),
+            )
+            .align_items(Alignment::Center)
+            .padding(10)
+            .spacing(10);
+
+        list = list.push(container(row).style(style::card()));
+    }
+
+    let skip_button = button(container(text(t("mod-suggestions-skip"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Instances));
+
+    let install_all_button = button(container(text(t("mod-suggestions-install-all"))).padding(5))
+        .style(style::circle_button(theme::Button::Primary))
+        .on_press(Message::InstallAllSuggestions);
+
+    let footer = Row::new()
+        .push(horizontal_space(Length::Fill))
+        .push(skip_button)
+        .push(install_all_button)
+        .spacing(10);
+
+    Column::new()
+        .push(title)
+        .push(description)
+        .push(scrollable(list).height(Length::Fill))
+        .push(footer)
+        .spacing(10)
+        .padding(10)
+        .into()
+}
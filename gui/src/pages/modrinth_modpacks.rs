@@ -1,17 +1,33 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::components::icon::Icon;
+use crate::components::{icon::Icon, search_filters};
 use iced::{
-    widget::{button, horizontal_space, scrollable, text, Column, Row},
+    widget::{button, horizontal_space, scrollable, text, text_input, Column, Row},
     Alignment, Element, Length,
 };
+use lib::i18n::{t, t_args};
+use lib::modrinth::MODPACK_CATEGORIES;
 
 use crate::types::messages::Message;
 use crate::types::modrinth_modpacks::ModrinthModpacks;
 
 pub fn view(modrinth_modpacks: &ModrinthModpacks) -> Element<Message> {
-    let title = text("Modrinth Modpacks").size(30);
+    let title = text(t("modrinth-modpacks-title")).size(30);
+
+    let search = text_input(&t("modrinth-modpacks-search"), &modrinth_modpacks.query)
+        .on_input(Message::ModpackQueryChanged)
+        .on_submit(Message::GetModpacks);
+
+    let filters = search_filters::view(
+        &modrinth_modpacks.filters,
+        &MODPACK_CATEGORIES,
+        true,
+        Message::ModpackFiltersChanged,
+        Message::SetModpackFilters,
+        Message::GetModpacks,
+        Message::SetModpackFilters(ModrinthModpacks::default_filters()),
+    );
 
     let mut list = Column::new().spacing(10).padding([0, 20, 0, 0]);
     for project in &modrinth_modpacks.projects {
@@ -30,17 +46,37 @@ pub fn view(modrinth_modpacks: &ModrinthModpacks) -> Element<Message> {
         info = info
             .push(horizontal_space(Length::Fill))
             .push(Icon::DownloadOutline.view(24))
-            .push(text(format!("{} Downloads", project.downloads)));
+            .push(text(t_args(
+                "modrinth-modpacks-downloads",
+                &[("downloads", &project.downloads)],
+            )));
 
-        let button = button(info);
+        let button = button(info).on_press(Message::OpenModrinthProject(project.clone()));
 
         list = list.push(button);
     }
 
+    let shown = modrinth_modpacks.projects.len();
+    let total = modrinth_modpacks.total_hits;
+    if shown < total {
+        list = list.push(
+            button(text(t("modrinth-modpacks-load-more"))).on_press(Message::LoadMoreModpacks),
+        );
+    }
+
+    let count = text(t_args(
+        "modrinth-modpacks-results",
+        &[("shown", &shown), ("total", &total)],
+    ))
+    .size(14);
+
     let scrollable = scrollable(list).height(Length::Fill);
 
     Column::new()
         .push(title)
+        .push(search)
+        .push(filters)
+        .push(count)
         .push(scrollable)
         .spacing(10)
         .padding(10)
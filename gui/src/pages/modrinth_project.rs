@@ -0,0 +1,214 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::widget::{
+    button, container, horizontal_space, image, scrollable, text, Column, Row, Space,
+};
+use iced::{theme, Alignment, Element, Length};
+use iced_aw::Spinner;
+use lib::i18n::{t, t_args};
+use lib::modrinth::GalleryImage;
+
+use crate::components::icon::Icon;
+use crate::components::markdown;
+use crate::pages::Page;
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::modrinth_project::ModrinthProject;
+
+const THUMBNAIL_HEIGHT: u16 = 120;
+
+fn gallery<'a>(
+    modrinth_project: &ModrinthProject,
+    images: &[GalleryImage],
+) -> Element<'a, Message> {
+    let mut row = Row::new().spacing(10).padding([0, 0, 15, 0]);
+
+    for (i, gallery_image) in images.iter().enumerate() {
+        let thumbnail: Element<Message> = match modrinth_project.images.get(&gallery_image.url) {
+            Some(Ok(handle)) => button(image(handle.clone()).height(THUMBNAIL_HEIGHT))
+                .style(theme::Button::Text)
+                .padding(0)
+                .on_press(Message::ShowGalleryImage(Some(i)))
+                .into(),
+            Some(Err(_)) => container(Icon::AlertCircleOutline.view(32))
+                .width(THUMBNAIL_HEIGHT)
+                .height(THUMBNAIL_HEIGHT)
+                .center_x()
+                .center_y()
+                .into(),
+            None => container(Spinner::new())
+                .width(THUMBNAIL_HEIGHT)
+                .height(THUMBNAIL_HEIGHT)
+                .center_x()
+                .center_y()
+                .into(),
+        };
+
+        row = row.push(thumbnail);
+    }
+
+    scrollable(row)
+        .direction(scrollable::Direction::Horizontal(
+            scrollable::Properties::default(),
+        ))
+        .into()
+}
+
+// one screenshot at a time, in place of the description
+fn lightbox<'a>(
+    modrinth_project: &ModrinthProject,
+    images: &[GalleryImage],
+    index: usize,
+) -> Element<'a, Message> {
+    let Some(gallery_image) = images.get(index) else {
+        return Space::new(0, 0).into();
+    };
+
+    let picture: Element<Message> = match modrinth_project.images.get(&gallery_image.url) {
+        Some(Ok(handle)) => image(handle.clone())
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+        Some(Err(error)) => Column::new()
+            .push(Icon::AlertCircleOutline.view(32))
+            .push(text(error))
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .into(),
+        None => Spinner::new().into(),
+    };
+
+    let mut previous = button(text(t("modrinth-project-previous")));
+    if index > 0 {
+        previous = previous.on_press(Message::ShowGalleryImage(Some(index - 1)));
+    }
+    let mut next = button(text(t("modrinth-project-next")));
+    if index + 1 < images.len() {
+        next = next.on_press(Message::ShowGalleryImage(Some(index + 1)));
+    }
+
+    let controls = Row::new()
+        .push(text(gallery_image.title.clone().unwrap_or_default()))
+        .push(horizontal_space(Length::Fill))
+        .push(previous)
+        .push(text(format!("{}/{}", index + 1, images.len())))
+        .push(next)
+        .push(
+            button(text(t("modrinth-project-close")))
+                .style(theme::Button::Secondary)
+                .on_press(Message::ShowGalleryImage(None)),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+
+    Column::new()
+        .push(
+            container(picture)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y(),
+        )
+        .push(controls)
+        .spacing(10)
+        .into()
+}
+
+pub fn view(modrinth_project: &Option<ModrinthProject>) -> Element<'_, Message> {
+    let Some(modrinth_project) = modrinth_project else {
+        return text(t("modrinth-project-empty")).into();
+    };
+    let project = &modrinth_project.project;
+
+    let title = text(&project.title).size(30);
+
+    if let (Some(details), Some(index)) = (&modrinth_project.details, modrinth_project.lightbox) {
+        return Column::new()
+            .push(title)
+            .push(lightbox(modrinth_project, &details.gallery, index))
+            .spacing(10)
+            .padding(10)
+            .into();
+    }
+
+    let mut info = Column::new().spacing(5);
+    let body: Element<Message> = match &modrinth_project.details {
+        Some(details) => {
+            if !details.authors.is_empty() {
+                info = info.push(text(t_args(
+                    "modrinth-project-authors",
+                    &[("authors", &details.authors.join(", "))],
+                )));
+            }
+
+            info = info.push(text(t_args(
+                "modrinth-project-downloads",
+                &[("downloads", &details.downloads)],
+            )));
+
+            if let Some(license) = &details.license {
+                info = info.push(text(t_args(
+                    "modrinth-project-license",
+                    &[("license", &license.id)],
+                )));
+            }
+
+            info = info
+                .push(text(t_args(
+                    "modrinth-project-loaders",
+                    &[("loaders", &details.loaders.join(", "))],
+                )))
+                .push(text(t_args(
+                    "modrinth-project-versions",
+                    &[("versions", &details.game_versions.join(", "))],
+                )));
+
+            let mut body = Column::new().spacing(10);
+            if !details.gallery.is_empty() {
+                body = body.push(gallery(modrinth_project, &details.gallery));
+            }
+
+            body.push(markdown::view(&modrinth_project.description))
+                .into()
+        }
+        None => {
+            info = info.push(text(&project.description));
+
+            text(t("modrinth-project-loading")).into()
+        }
+    };
+
+    let info = container(info.padding(10))
+        .width(Length::Fill)
+        .style(style::card());
+
+    let install = match modrinth_project.back {
+        Page::ModBrowser => Message::InstallMod(project.clone()),
+        _ => Message::InstallModpack(project.project_id.clone()),
+    };
+
+    let footer = Row::new()
+        .push(
+            button(text(t("modrinth-project-back")))
+                .style(theme::Button::Secondary)
+                .padding(10)
+                .on_press(Message::ChangePage(modrinth_project.back.clone())),
+        )
+        .push(horizontal_space(Length::Fill))
+        .push(
+            button(text(t("modrinth-project-install")))
+                .style(style::circle_button(theme::Button::Primary))
+                .padding(10)
+                .on_press(install),
+        );
+
+    Column::new()
+        .push(title)
+        .push(info)
+        .push(scrollable(container(body).padding([0, 20, 0, 0])).height(Length::Fill))
+        .push(footer)
+        .spacing(10)
+        .padding(10)
+        .into()
+}
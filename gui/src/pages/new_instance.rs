@@ -6,6 +6,7 @@ use iced::{
     Alignment, Element, Length,
 };
 use iced_aw::Wrap;
+use lib::i18n::t;
 
 use crate::{components::icon::Icon, pages::Page, Message};
 
@@ -29,17 +30,24 @@ fn installer_button(
 }
 
 pub fn view() -> Element<'static, Message> {
-    let title = text("New instance").size(30);
+    let title = text(t("new-instance-title")).size(30);
 
     let mut wrap = Wrap::new().spacing(10.);
 
     // Vanilla
-    let vanilla_btn = installer_button("Vanilla", Page::VanillaInstaller, Icon::Minecraft.view(64));
+    let vanilla_btn = installer_button(
+        &t("new-instance-vanilla"),
+        Page::VanillaInstaller,
+        Icon::Minecraft.view(64),
+    );
     wrap = wrap.push(vanilla_btn);
 
     // Modrinth
-    let modrinth_btn =
-        installer_button("Modrinth", Page::ModrinthModpacks, Icon::Modrinth.view(64));
+    let modrinth_btn = installer_button(
+        &t("new-instance-modrinth"),
+        Page::ModrinthModpacks,
+        Icon::Modrinth.view(64),
+    );
     wrap = wrap.push(modrinth_btn);
 
     Column::new()
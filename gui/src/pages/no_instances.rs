@@ -6,6 +6,8 @@ use iced::{
     Alignment, Element,
 };
 
+use lib::i18n::t;
+
 use crate::{components::icon::Icon, Message};
 
 pub fn view() -> Element<'static, Message> {
@@ -14,7 +16,7 @@ pub fn view() -> Element<'static, Message> {
         .push(
             Row::new()
                 .push(Icon::ArrowLeft.view(24))
-                .push(text("You don't have any instances yet. Create one!").size(25))
+                .push(text(t("no-instances")).size(25))
                 .align_items(Alignment::Center)
                 .spacing(10),
         )
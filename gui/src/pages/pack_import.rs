@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::widget::{button, container, horizontal_space, text, text_input, Column, Row};
+use iced::{theme, Element, Length};
+use lib::i18n::{t, t_args};
+use lib::import::FileKind;
+
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::pack_import::PackImport;
+
+pub fn view(pack_import: &Option<PackImport>) -> Element<Message> {
+    let Some(pack_import) = pack_import else {
+        return text(t("pack-import-empty")).into();
+    };
+
+    let title = match pack_import.info.kind {
+        FileKind::MultiMc => t("pack-import-multimc-title"),
+        FileKind::Technic => t("pack-import-technic-title"),
+        FileKind::AtLauncher => t("pack-import-atlauncher-title"),
+        _ => t("pack-import-mrpack-title"),
+    };
+    let title = text(title).size(30);
+
+    let file = pack_import
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut details = Column::new()
+        .push(text(file))
+        .push(text(t_args(
+            "pack-import-minecraft",
+            &[("version", &pack_import.info.minecraft)],
+        )))
+        .spacing(5);
+
+    if let Some(loader_version) = &pack_import.info.loader_version {
+        details = details.push(text(t_args(
+            "pack-import-fabric",
+            &[("version", loader_version)],
+        )));
+    }
+
+    let details = container(details.padding(10))
+        .width(Length::Fill)
+        .style(style::card());
+
+    let name = Column::new()
+        .push(text(t("pack-import-name")))
+        .push(text_input("", &pack_import.name).on_input(Message::ImportNameChanged))
+        .spacing(10)
+        .padding(10);
+    let name = container(name).width(Length::Fill).style(style::card());
+
+    let import_button = button(text(t("pack-import-import")))
+        .style(style::circle_button(theme::Button::Primary))
+        .padding(10)
+        .on_press(Message::ImportPack);
+    let footer = Row::new()
+        .push(horizontal_space(Length::Fill))
+        .push(import_button);
+
+    Column::new()
+        .push(title)
+        .push(details)
+        .push(name)
+        .push(footer)
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use iced::widget::Row;
+use iced::widget::{Column, Row};
 use iced::Element;
 
 use crate::pages::Page;
@@ -10,24 +10,82 @@ use crate::types::messages::Message;
 use crate::{components, pages};
 
 pub fn view(launcher: &Launcher) -> Element<Message> {
-    let navbar = components::navbar::view(launcher.name, &launcher.page, &launcher.accounts);
+    let navbar = components::navbar::view(
+        launcher.name,
+        &launcher.page,
+        &launcher.accounts,
+        launcher.offline,
+    );
 
     let page_view = match &launcher.page {
         Page::Status(status) => pages::status::view(status),
         Page::Error(err) => pages::error::view(err),
-        Page::About => pages::about::view(launcher.name),
-        Page::Instances => pages::instances::view(&launcher.instances),
+        Page::About => pages::about::view(launcher.name, launcher.settings.theme),
+        Page::Instances => pages::instances::view(
+            &launcher.instances,
+            &launcher.accounts,
+            &launcher.running_instances,
+            &launcher.tag_filter,
+            launcher.latest_release.as_ref(),
+            launcher.disk_usage.as_ref(),
+            launcher.settings.instances_view,
+            launcher.settings.theme,
+        ),
+        Page::Instance(name) => pages::instance::view(
+            &launcher.instances,
+            &launcher.settings,
+            &launcher.running,
+            &launcher.java_installations,
+            &launcher.gpus,
+            launcher.gc_summaries.get(name),
+            launcher.backups.get(name).map_or(&[], Vec::as_slice),
+            &launcher.instance_editor,
+            launcher.server_consoles.get(name),
+            launcher.latest_release.as_ref(),
+            name,
+        ),
         Page::NewInstance => pages::new_instance::view(),
-        Page::Accounts => pages::accounts::view(&launcher.accounts),
+        Page::Accounts => pages::accounts::view(&launcher.accounts, launcher.skin_preview.as_ref()),
         Page::AddingAccount => pages::login::view(&launcher.login),
+        Page::AddingAuthlibAccount => pages::adding_authlib_account::view(&launcher.authlib_login),
         Page::AddingOfflineAccount => {
             pages::adding_offline_account::view(&launcher.offline_account_username)
         }
         Page::VanillaInstaller => pages::vanilla_installer::view(&launcher.vanilla_installer),
-        Page::Settings => pages::settings::view(&launcher.settings),
+        Page::Settings => pages::settings::view(&launcher.settings, &launcher.predownload_versions),
         Page::Download => pages::download::view(&launcher.download),
+        Page::Tasks => pages::tasks::view(&launcher.tasks),
+        Page::Trash => {
+            pages::trash::view(&launcher.instances, launcher.settings.trash_retention_days)
+        }
         Page::ModrinthModpacks => pages::modrinth_modpacks::view(&launcher.modrinth_modpacks),
+        Page::ModrinthProject => pages::modrinth_project::view(&launcher.modrinth_project),
+        Page::ModSuggestions => pages::mod_suggestions::view(&launcher.mod_suggestions),
+        Page::JsonViewer => pages::json_viewer::view(&launcher.json_viewer),
+        Page::Storage => pages::storage::view(&launcher.disk_usage),
+        Page::ModBrowser => pages::mod_browser::view(&launcher.mod_browser),
+        Page::PackImport => pages::pack_import::view(&launcher.pack_import),
+        Page::ServerProperties(name) => {
+            pages::server_properties::view(&launcher.server_properties, name)
+        }
+        Page::InstanceExport(name) => pages::instance_export::view(&launcher.export_parts, name),
+        Page::World(name, world) => pages::world::view(&launcher.level_dat, name, world),
+        Page::ServerList(name) => pages::server_list::view(&launcher.server_list, name),
+        Page::Feedback => pages::feedback::view(&launcher.feedback, launcher.feedback_summary()),
     };
 
+    // notifications sit on top of the page, which stays usable
+    let mut top = Column::new();
+    if launcher.palette.open {
+        top = top.push(components::palette::view(
+            &launcher.palette,
+            &launcher.instances,
+        ));
+    }
+
+    let page_view = top
+        .push(components::notifications::view(&launcher.notifications))
+        .push(page_view);
+
     Row::new().push(navbar).push(page_view).into()
 }
@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, scrollable, text, text_input, Column, Row},
+    Alignment, Element, Length,
+};
+use iced_aw::Spinner;
+use lib::i18n::{t, t_args};
+
+use crate::pages::Page;
+use crate::types::messages::Message;
+use crate::types::server_list::ServerListEditor;
+use crate::{components::icon::Icon, style};
+
+fn status_view<'a>(editor: &'a ServerListEditor, address: &str) -> Element<'a, Message> {
+    match editor.status.get(address) {
+        None => Spinner::new()
+            .width(Length::Fixed(20.))
+            .height(Length::Fixed(20.))
+            .into(),
+        Some(Err(_)) if lib::server_list::may_use_srv(address) => {
+            text(t("server-list-offline-srv")).into()
+        }
+        Some(Err(_)) => text(t("server-list-offline")).into(),
+        Some(Ok(status)) => Column::new()
+            .push(text(&status.motd))
+            .push(
+                text(t_args(
+                    "server-list-online",
+                    &[
+                        ("online", &status.online.to_string()),
+                        ("max", &status.max.to_string()),
+                        ("latency", &status.latency.as_millis().to_string()),
+                        ("version", &status.version),
+                    ],
+                ))
+                .size(14),
+            )
+            .into(),
+    }
+}
+
+pub fn view<'a>(editor: &'a ServerListEditor, instance: &'a str) -> Element<'a, Message> {
+    let entries = editor.list.entries();
+
+    let mut col = Column::new().padding(10).spacing(10);
+    if entries.is_empty() {
+        col = col.push(text(t("server-list-empty")));
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        col = col.push(
+            Row::new()
+                .push(
+                    Column::new()
+                        .push(text(&entry.name).size(20))
+                        .push(text(&entry.address).size(14))
+                        .width(200),
+                )
+                .push(status_view(editor, &entry.address))
+                .push(horizontal_space(Length::Fill))
+                .push(
+                    button(Icon::DeleteOutline.view(20))
+                        .style(theme::Button::Destructive)
+                        .on_press(Message::RemoveServer(instance.to_string(), i)),
+                )
+                .align_items(Alignment::Center)
+                .spacing(10),
+        );
+    }
+
+    let add_row = Row::new()
+        .push(
+            text_input(&t("server-list-name"), &editor.new_name)
+                .on_input(Message::ServerNameChanged),
+        )
+        .push(
+            text_input(&t("server-list-address"), &editor.new_address)
+                .on_input(Message::ServerAddressChanged)
+                .on_submit(Message::AddServer(instance.to_string())),
+        )
+        .push(
+            button(text(t("server-list-add")))
+                .on_press(Message::AddServer(instance.to_string()))
+                .style(theme::Button::Secondary),
+        )
+        .spacing(5);
+
+    let back_button = button(container(text(t("server-list-back"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Instance(instance.to_string())));
+
+    let refresh_button = button(container(text(t("server-list-refresh"))).padding(5))
+        .style(style::circle_button(theme::Button::Primary))
+        .on_press(Message::PingServers(instance.to_string()));
+
+    Column::new()
+        .push(text(t_args("server-list-title", &[("name", &instance)])).size(30))
+        .push(
+            container(scrollable(col).height(Length::Fill))
+                .height(Length::Fill)
+                .style(style::card()),
+        )
+        .push(add_row)
+        .push(
+            Row::new()
+                .push(back_button)
+                .push(horizontal_space(Length::Fill))
+                .push(refresh_button),
+        )
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{
+        button, container, horizontal_space, pick_list, scrollable, text, text_input, toggler,
+        Column, Row,
+    },
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::server::{PropertyKind, ServerProperties, PROPERTIES};
+
+use crate::pages::Page;
+use crate::types::messages::Message;
+use crate::{components::icon::Icon, style};
+
+pub fn view<'a>(properties: &'a ServerProperties, name: &'a str) -> Element<'a, Message> {
+    let mut col = Column::new().padding(10).spacing(10);
+    let mut valid = true;
+
+    for property in PROPERTIES {
+        let value = properties.get(property);
+
+        let field: Element<Message> = match property.kind {
+            PropertyKind::Bool => toggler(None, value == "true", move |value| {
+                Message::SetServerProperty(property.key, value.to_string())
+            })
+            .width(Length::Shrink)
+            .into(),
+            PropertyKind::Choice(choices) => {
+                let selected = choices.iter().find(|choice| **choice == value).copied();
+
+                pick_list(choices, selected, move |value| {
+                    Message::SetServerProperty(property.key, value.to_string())
+                })
+                .into()
+            }
+            PropertyKind::Int { .. } | PropertyKind::Text => text_input(property.default, value)
+                .on_input(move |value| Message::SetServerProperty(property.key, value))
+                .width(300)
+                .into(),
+        };
+
+        let mut row = Row::new()
+            .push(text(property.key))
+            .push(horizontal_space(Length::Fill))
+            .align_items(Alignment::Center)
+            .spacing(10);
+
+        if let Err(error) = property.validate(value) {
+            valid = false;
+            row = row.push(text(error));
+        }
+
+        col = col.push(row.push(field));
+    }
+
+    let back_button = button(container(text(t("server-properties-back"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Instance(name.to_string())));
+
+    let mut save_button = button(
+        Row::new()
+            .push(text(format!(" {} ", t("server-properties-save"))))
+            .push(Icon::ContentSaveOutline.view(24))
+            .padding(5)
+            .align_items(Alignment::Center),
+    )
+    .style(style::circle_button(theme::Button::Positive));
+
+    if valid {
+        save_button = save_button.on_press(Message::SaveServerProperties(name.to_string()));
+    }
+
+    Column::new()
+        .push(text(t_args("server-properties-title", &[("name", &name)])).size(30))
+        .push(
+            container(scrollable(col).height(Length::Fill))
+                .height(Length::Fill)
+                .style(style::card()),
+        )
+        .push(
+            Row::new()
+                .push(back_button)
+                .push(horizontal_space(Length::Fill))
+                .push(save_button),
+        )
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -3,21 +3,31 @@
 
 use iced::{
     theme,
-    widget::{button, container, horizontal_space, text, toggler, vertical_space, Column, Row},
+    widget::{
+        button, container, horizontal_space, pick_list, text, text_input, toggler, vertical_space,
+        Column, Row,
+    },
     Alignment, Element, Length,
 };
-use lib::settings::Settings;
+use lib::i18n::{t, Language};
+use lib::instances::InheritedSetting;
+use lib::jvm::{GarbageCollector, JvmPreset};
+use lib::logging::LogLevel;
+use lib::runtime_manager::JavaVendor;
+use lib::settings::{Settings, Theme};
+use lib::store::StorageArea;
 
+use crate::pages::Page;
 use crate::types::messages::Message;
 use crate::{components::icon::Icon, style};
 
-pub fn view(settings: &Settings) -> Element<Message> {
-    let mut col = Column::new().padding(10);
+pub fn view<'a>(settings: &'a Settings, predownload_versions: &'a str) -> Element<'a, Message> {
+    let mut col = Column::new().padding(10).spacing(10);
 
     #[cfg(feature = "updater")]
     {
         let check_for_updates = toggler(
-            "Automatically check for updates".to_owned(),
+            t("settings-check-for-updates"),
             settings.check_for_updates,
             Message::SetCheckForUpdates,
         );
@@ -25,9 +35,224 @@ pub fn view(settings: &Settings) -> Element<Message> {
         col = col.push(check_for_updates);
     }
 
+    let crash_reports = toggler(
+        t("settings-crash-reports"),
+        settings.crash_reports,
+        Message::SetCrashReports,
+    );
+    col = col.push(crash_reports);
+
+    let minimize_on_launch = toggler(
+        t("settings-minimize-on-launch"),
+        settings.minimize_on_launch,
+        Message::SetMinimizeOnLaunch,
+    );
+    col = col.push(minimize_on_launch);
+
+    let language = Row::new()
+        .push(text(t("settings-language")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &Language::ALL[..],
+            Some(settings.language),
+            Message::SetLanguage,
+        ))
+        .align_items(Alignment::Center);
+    col = col.push(language);
+
+    let theme = Row::new()
+        .push(text(t("settings-theme")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &Theme::ALL[..],
+            Some(settings.theme),
+            Message::SetTheme,
+        ))
+        .align_items(Alignment::Center);
+    col = col.push(theme);
+
+    let java_vendor = Row::new()
+        .push(text(t("settings-java-vendor")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &JavaVendor::ALL[..],
+            Some(settings.java_vendor),
+            Message::SetJavaVendor,
+        ))
+        .align_items(Alignment::Center);
+    col = col.push(java_vendor);
+
+    let jvm_preset = Row::new()
+        .push(text(t("settings-jvm-preset")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &JvmPreset::ALL[..],
+            Some(settings.jvm_preset),
+            Message::SetJvmPreset,
+        ))
+        .align_items(Alignment::Center);
+    col = col.push(jvm_preset);
+
+    // replaces the preset's collector, checked against the runtime at launch
+    let garbage_collector = Row::new()
+        .push(text(t("settings-gc")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &GarbageCollector::ALL[..],
+            Some(settings.garbage_collector),
+            Message::SetGarbageCollector,
+        ))
+        .align_items(Alignment::Center);
+    col = col.push(garbage_collector);
+
+    // instances inherit these unless they override them
+    for setting in InheritedSetting::ALL {
+        let value = setting.get_default(settings).unwrap_or_default();
+
+        let mut row = Row::new()
+            .push(text(t(&format!("settings-{}", setting.key()))))
+            .push(horizontal_space(Length::Fill))
+            .align_items(Alignment::Center)
+            .spacing(10);
+
+        if let Err(error) = setting.validate(&value) {
+            row = row.push(text(error));
+        }
+
+        let row = row.push(
+            text_input(&t("settings-not-set"), &value)
+                .on_input(move |value| Message::SetDefault(setting, value))
+                .width(200),
+        );
+        col = col.push(row);
+    }
+
+    let download_threads = Row::new()
+        .push(text(t("settings-download-threads")))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            text_input("", &settings.download_threads.to_string())
+                .on_input(Message::SetDownloadThreads)
+                .width(100),
+        )
+        .align_items(Alignment::Center);
+    col = col.push(download_threads);
+
+    let download_retries = Row::new()
+        .push(text(t("settings-download-retries")))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            text_input("", &settings.download_retries.to_string())
+                .on_input(Message::SetDownloadRetries)
+                .width(100),
+        )
+        .align_items(Alignment::Center);
+    col = col.push(download_retries);
+
+    let trash_retention = Row::new()
+        .push(text(t("settings-trash-retention")))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            text_input("", &settings.trash_retention_days.to_string())
+                .on_input(Message::SetTrashRetention)
+                .width(100),
+        )
+        .align_items(Alignment::Center);
+    col = col.push(trash_retention);
+
+    let use_mirrors = toggler(
+        t("settings-mirrors"),
+        settings.use_mirrors,
+        Message::SetUseMirrors,
+    );
+    col = col.push(use_mirrors);
+
+    let download_speed_limit = Row::new()
+        .push(text(t("settings-speed-limit")))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            text_input("", &settings.download_speed_limit.to_string())
+                .on_input(Message::SetDownloadSpeedLimit)
+                .width(100),
+        )
+        .align_items(Alignment::Center);
+    col = col.push(download_speed_limit);
+
+    // lets the data dir be copied to machines without network access
+    let predownload = Row::new()
+        .push(text(t("settings-predownload")))
+        .push(horizontal_space(Length::Fill))
+        .push(
+            text_input("1.20.1, 1.19.4", predownload_versions)
+                .on_input(Message::PredownloadVersionsChanged)
+                .width(200),
+        )
+        .push(
+            button(Icon::DownloadOutline.view(24))
+                .on_press(Message::Predownload)
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+    col = col.push(predownload);
+
+    let log_level = Row::new()
+        .push(text(t("settings-log-level")))
+        .push(horizontal_space(Length::Fill))
+        .push(pick_list(
+            &LogLevel::ALL[..],
+            Some(settings.log_level),
+            Message::SetLogLevel,
+        ))
+        .push(
+            button(Icon::FolderOpenOutline.view(24))
+                .on_press(Message::OpenLogsFolder)
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+    col = col.push(log_level);
+
+    let instances_dir = Row::new()
+        .push(text(t("settings-instances-dir")))
+        .push(horizontal_space(Length::Fill))
+        .push(text(settings.instances_dir().display()))
+        .push(
+            button(Icon::FolderOpenOutline.view(24))
+                .on_press(Message::ChooseInstancesDir)
+                .style(style::circle_button(theme::Button::Secondary)),
+        )
+        .align_items(Alignment::Center)
+        .spacing(10);
+    col = col.push(instances_dir);
+
+    // for debugging weird versions and packs
+    let json_viewer_button = button(container(text(t("settings-json-viewer"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::JsonViewer));
+
+    let storage_button = button(container(text(t("settings-storage"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Storage));
+
+    let export_button = button(container(text(t("settings-export-config"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ExportConfig);
+
+    let import_button = button(container(text(t("settings-import-config"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ImportConfig);
+
+    let clean_button = button(container(text(t("settings-clean"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::CleanUnusedFiles(vec![
+            StorageArea::Libraries,
+            StorageArea::Assets,
+        ]));
+
     let save_button = button(
         Row::new()
-            .push(text(" Save "))
+            .push(text(format!(" {} ", t("settings-save"))))
             .push(Icon::ContentSaveOutline.view(24))
             .padding(5)
             .align_items(Alignment::Center),
@@ -36,11 +261,17 @@ pub fn view(settings: &Settings) -> Element<Message> {
     .on_press(Message::SaveSettings);
 
     Column::new()
-        .push(text("Settings").size(30))
+        .push(text(t("settings-title")).size(30))
         .push(container(col).style(style::card()))
         .push(vertical_space(Length::Fill))
         .push(
             Row::new()
+                .push(json_viewer_button)
+                .push(storage_button)
+                .push(clean_button)
+                .push(export_button)
+                .push(import_button)
+                .spacing(10)
                 .push(horizontal_space(Length::Fill))
                 .push(save_button),
         )
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, scrollable, text, Column, Row},
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::store::DiskUsage;
+
+use crate::components::icon::Icon;
+use crate::format::format_bytes;
+use crate::style;
+use crate::types::messages::Message;
+
+fn size_text(size: u64) -> Element<'static, Message> {
+    text(format_bytes(size as f64)).into()
+}
+
+pub fn view(disk_usage: &Option<DiskUsage>) -> Element<Message> {
+    let title = text(t("storage-title")).size(30);
+
+    let Some(disk_usage) = disk_usage else {
+        return Column::new()
+            .push(title)
+            .push(text(t("storage-calculating")))
+            .spacing(10)
+            .padding(10)
+            .into();
+    };
+
+    let mut areas = Column::new().spacing(10);
+    for (area, size) in &disk_usage.areas {
+        let row = Row::new()
+            .push(text(area.to_string()))
+            .push(horizontal_space(Length::Fill))
+            .push(size_text(*size))
+            .push(
+                button(Icon::FolderOpenOutline.view(24))
+                    .on_press(Message::OpenStorageArea(*area))
+                    .style(style::circle_button(theme::Button::Secondary)),
+            )
+            .push(
+                button(Icon::DeleteOutline.view(24))
+                    .on_press(Message::CleanUnusedFiles(vec![*area]))
+                    .style(style::circle_button(theme::Button::Destructive)),
+            )
+            .align_items(Alignment::Center)
+            .padding(10)
+            .spacing(10);
+
+        areas = areas.push(container(row).style(style::card()));
+    }
+
+    let mut instances = Column::new().spacing(10);
+    for (name, size) in &disk_usage.instances {
+        let row = Row::new()
+            .push(text(name))
+            .push(horizontal_space(Length::Fill))
+            .push(size_text(*size))
+            .push(
+                button(Icon::FolderOpenOutline.view(24))
+                    .on_press(Message::OpenInstanceFolder(name.to_owned()))
+                    .style(style::circle_button(theme::Button::Secondary)),
+            )
+            .align_items(Alignment::Center)
+            .padding(10)
+            .spacing(10);
+
+        instances = instances.push(container(row).style(style::card()));
+    }
+
+    let content = Column::new()
+        .push(text(t("storage-shared")))
+        .push(areas)
+        .push(text(t("storage-instances")))
+        .push(instances)
+        .spacing(10);
+
+    Column::new()
+        .push(title)
+        .push(text(t_args(
+            "storage-total",
+            &[("size", &format_bytes(disk_usage.total() as f64))],
+        )))
+        .push(scrollable(content).height(Length::Fill))
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, progress_bar, scrollable, text, Column, Row},
+    Alignment, Element, Length,
+};
+use iced_aw::Spinner;
+
+use lib::i18n::t;
+
+use crate::style;
+use crate::types::messages::Message;
+use crate::types::tasks::{Task, Tasks};
+
+fn task_view(task: &Task) -> Element<'_, Message> {
+    let mut header = Row::new()
+        .push(text(&task.name))
+        .push(horizontal_space(Length::Fill))
+        .align_items(Alignment::Center);
+
+    if task.can_cancel() {
+        header = header.push(
+            button(container(text(t("tasks-cancel"))).padding(5))
+                .style(style::circle_button(theme::Button::Destructive))
+                .on_press(Message::CancelTask(task.id)),
+        );
+    }
+
+    let progress: Element<_> = match task.progress {
+        Some(progress) => Row::new()
+            .push(progress_bar(0.0..=100.0, progress).height(10))
+            .push(text(format!("{progress:.0}%")))
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .into(),
+        None => Row::new()
+            .push(
+                Spinner::new()
+                    .width(Length::Fixed(20.))
+                    .height(Length::Fixed(20.)),
+            )
+            .push(text(t("tasks-working")))
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .into(),
+    };
+
+    let col = Column::new()
+        .push(header)
+        .push(progress)
+        .spacing(10)
+        .padding(10);
+
+    container(col).style(style::card()).into()
+}
+
+pub fn view(tasks: &Tasks) -> Element<'_, Message> {
+    let title = text(t("tasks-title")).size(30);
+
+    if tasks.list.is_empty() {
+        return Column::new()
+            .push(title)
+            .push(text(t("tasks-empty")))
+            .spacing(10)
+            .padding(10)
+            .into();
+    }
+
+    let mut list = Column::new().spacing(10);
+    for task in &tasks.list {
+        list = list.push(task_view(task));
+    }
+
+    Column::new()
+        .push(title)
+        .push(scrollable(list).height(Length::Fill))
+        .spacing(10)
+        .padding(10)
+        .into()
+}
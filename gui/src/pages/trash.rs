@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{button, container, horizontal_space, scrollable, text, Column, Row},
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::instances::Instances;
+
+use crate::style;
+use crate::types::messages::Message;
+
+pub fn view(instances: &Instances, retention_days: u32) -> Element<'_, Message> {
+    let title = text(t("trash-title")).size(30);
+    let trashed = instances.list_trash();
+
+    if trashed.is_empty() {
+        return Column::new()
+            .push(title)
+            .push(text(t("trash-empty")))
+            .spacing(10)
+            .padding(10)
+            .into();
+    }
+
+    let mut list = Column::new().spacing(10);
+    for trashed in trashed {
+        let days_left = trashed.days_left(retention_days).to_string();
+
+        let row = Row::new()
+            .push(
+                Column::new()
+                    .push(text(&trashed.name))
+                    .push(text(t_args("trash-days-left", &[("days", &days_left)])).size(14))
+                    .spacing(5),
+            )
+            .push(horizontal_space(Length::Fill))
+            .push(
+                button(container(text(t("trash-restore"))).padding(5))
+                    .style(style::circle_button(theme::Button::Secondary))
+                    .on_press(Message::RestoreTrashed(trashed.clone())),
+            )
+            .push(
+                button(container(text(t("trash-delete"))).padding(5))
+                    .style(style::circle_button(theme::Button::Destructive))
+                    .on_press(Message::DeleteTrashed(trashed)),
+            )
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .padding(10);
+
+        list = list.push(container(row).style(style::card()));
+    }
+
+    Column::new()
+        .push(title)
+        .push(scrollable(list).height(Length::Fill))
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -2,49 +2,71 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use iced::{
-    Element,
+    Alignment, Element,
     Length, theme, widget::{
-        button, Column, container, horizontal_space, radio, Row, scrollable, text,
-        text_input, toggler,
+        button, checkbox, Column, container, horizontal_space, pick_list, radio, Row, scrollable, text,
+        text_input,
     },
 };
+use lib::i18n::t;
+use lib::jvm::JvmPreset;
+use lib::vanilla_installer::ReleaseType;
 
 use crate::style;
 use crate::types::messages::Message;
 use crate::types::vanilla_installer::VanillaInstaller;
 
 pub fn view(vanilla_installer: &VanillaInstaller) -> Element<Message> {
-    let title = text("Vanilla Installer").size(30);
+    let title = text(t("vanilla-installer-title")).size(30);
 
-    let name_text = text("Instance name");
+    let name_text = text(t("vanilla-installer-name"));
     let name = text_input("", &vanilla_installer.name).on_input(Message::ChangeName);
     let choose_name = Column::new().push(name_text).push(name).spacing(10).padding(10);
     let choose_name = container(choose_name)
         .width(Length::Fill)
         .style(style::card());
 
-    let memory_text = text("Memory");
-    let memory = text_input("", &vanilla_installer.memory).on_input(Message::SetMemory);
+    let memory_text = text(t("vanilla-installer-memory"));
+    let memory = text_input(&t("vanilla-installer-default-from-settings"), &vanilla_installer.memory).on_input(Message::SetMemory);
     let choose_memory = Column::new().push(memory_text).push(memory).spacing(10).padding(10);
     let choose_memory = container(choose_memory)
         .width(Length::Fill)
         .style(style::card());
 
-    let optimize_jvm = toggler(
-        "Optimize JVM".to_string(),
-        vanilla_installer.optimize_jvm,
-        Message::SetOptimizeJvm,
-    );
-    let optimize_jvm = container(optimize_jvm).padding(10);
-    let optimize_jvm = container(optimize_jvm)
+    let jvm_preset_text = text(t("vanilla-installer-jvm-preset"));
+    let jvm_preset = pick_list(
+        &JvmPreset::ALL[..],
+        vanilla_installer.jvm_preset,
+        Message::SetInstallerJvmPreset,
+    )
+    .placeholder(t("vanilla-installer-default-from-settings"));
+    let choose_jvm_preset = Column::new().push(jvm_preset_text).push(jvm_preset).spacing(10).padding(10);
+    let choose_jvm_preset = container(choose_jvm_preset)
         .width(Length::Fill)
         .style(style::card());
 
-    let version_text = text("Select version");
+    let version_text = text(t("vanilla-installer-select-version"));
+
+    let mut release_types = Row::new().spacing(10);
+    for release_type in ReleaseType::ALL {
+        release_types = release_types.push(checkbox(
+            release_type.to_string(),
+            vanilla_installer.release_types.contains(&release_type),
+            move |_| Message::ToggleReleaseType(release_type),
+        ));
+    }
+
+    let version_filter = text_input(&t("vanilla-installer-search-versions"), &vanilla_installer.version_filter)
+        .on_input(Message::VersionFilterChanged);
+
     let mut version_picker = Column::new().spacing(5);
     for (i, version) in vanilla_installer.versions.iter().enumerate() {
+        if !vanilla_installer.is_shown(version) {
+            continue;
+        }
+
         version_picker = version_picker.push(radio(
-            version.to_owned(),
+            version.id.to_owned(),
             i,
             vanilla_installer.selected_version,
             Message::SelectVersion,
@@ -53,24 +75,69 @@ pub fn view(vanilla_installer: &VanillaInstaller) -> Element<Message> {
 
     let version_picker = scrollable(version_picker).width(Length::Fill);
 
-    let select_version = Column::new().push(version_text).push(version_picker)
+    let select_version = Column::new()
+        .push(version_text)
+        .push(release_types)
+        .push(version_filter)
+        .push(version_picker)
         .spacing(10)
         .padding(10);
     let select_version = container(select_version)
+        .width(Length::Fill)
         .height(Length::Fill)
         .style(style::card());
 
-    let create_button = button("Create")
-        .style(style::circle_button(theme::Button::Primary))
+    let patch_notes: Element<Message> = match (
+        &vanilla_installer.patch_notes,
+        vanilla_installer.selected_version,
+    ) {
+        (Some(notes), _) => Column::new()
+            .push(text(&notes.title).size(20))
+            .push(text(notes.date.split('T').next().unwrap_or_default()))
+            .push(text(&notes.short_text))
+            .spacing(10)
+            .into(),
+        (None, Some(_)) => text(t("vanilla-installer-no-patch-notes")).into(),
+        (None, None) => text(t("vanilla-installer-select-for-patch-notes")).into(),
+    };
+    let patch_notes = container(scrollable(patch_notes).width(Length::Fill))
         .padding(10)
-        .on_press(Message::CreateInstance);
-    let footer = Row::new().push(horizontal_space(Length::Fill)).push(create_button);
+        .width(300)
+        .height(Length::Fill)
+        .style(style::card());
+
+    let select_version = Row::new().push(select_version).push(patch_notes).spacing(10);
+
+    let mut create_button = button(text(t("vanilla-installer-create")))
+        .style(style::circle_button(theme::Button::Primary))
+        .padding(10);
+    if vanilla_installer.selected_version.is_some() {
+        create_button = create_button.on_press(Message::CreateInstance);
+    }
+    let server = checkbox(
+        t("vanilla-installer-server"),
+        vanilla_installer.server,
+        Message::SetInstallerServer,
+    );
+    let mut footer = Row::new().push(server).spacing(10);
+    // servers are always vanilla
+    if !vanilla_installer.server {
+        footer = footer.push(checkbox(
+            t("vanilla-installer-fabric"),
+            vanilla_installer.fabric,
+            Message::SetInstallerFabric,
+        ));
+    }
+    let footer = footer
+        .push(horizontal_space(Length::Fill))
+        .push(create_button)
+        .align_items(Alignment::Center);
 
     Column::new()
         .push(title)
         .push(choose_name)
         .push(choose_memory)
-        .push(optimize_jvm)
+        .push(choose_jvm_preset)
         .push(select_version)
         .push(footer)
         .spacing(10)
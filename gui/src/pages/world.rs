@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::{
+    theme,
+    widget::{
+        button, container, horizontal_space, pick_list, scrollable, text, text_input, toggler,
+        Column, Row,
+    },
+    Alignment, Element, Length,
+};
+use lib::i18n::{t, t_args};
+use lib::world::{Difficulty, GameMode, LevelDat};
+
+use crate::pages::Page;
+use crate::types::messages::Message;
+use crate::{components::icon::Icon, style};
+
+fn field<'a>(label: String, field: impl Into<Element<'a, Message>>) -> Row<'a, Message> {
+    Row::new()
+        .push(text(label))
+        .push(horizontal_space(Length::Fill))
+        .push(field)
+        .align_items(Alignment::Center)
+        .spacing(10)
+}
+
+pub fn view<'a>(
+    level_dat: &'a Option<LevelDat>,
+    instance: &'a str,
+    world: &'a str,
+) -> Element<'a, Message> {
+    let Some(level_dat) = level_dat else {
+        return text(t("world-empty")).into();
+    };
+
+    let mut name = field(
+        t("world-name"),
+        text_input("", &level_dat.name)
+            .on_input(Message::SetWorldName)
+            .width(300),
+    );
+    if let Err(error) = level_dat.validate() {
+        name = name.push(text(error));
+    }
+
+    let mut col = Column::new()
+        .push(name)
+        .push(field(
+            t("world-game-mode"),
+            pick_list(
+                &GameMode::ALL[..],
+                Some(level_dat.game_mode),
+                Message::SetWorldGameMode,
+            ),
+        ))
+        .push(field(
+            t("world-difficulty"),
+            pick_list(
+                &Difficulty::ALL[..],
+                Some(level_dat.difficulty),
+                Message::SetWorldDifficulty,
+            ),
+        ))
+        .push(field(
+            t("world-cheats"),
+            toggler(None, level_dat.cheats, Message::SetWorldCheats).width(Length::Shrink),
+        ))
+        .padding(10)
+        .spacing(10);
+
+    if let Some(version) = &level_dat.version {
+        col = col.push(field(t("world-version"), text(version)));
+    }
+    if let Some(seed) = level_dat.seed {
+        col = col.push(field(t("world-seed"), text(seed)));
+    }
+    if level_dat.hardcore {
+        col = col.push(text(t("world-hardcore")));
+    }
+
+    let back_button = button(container(text(t("world-back"))).padding(5))
+        .style(style::circle_button(theme::Button::Secondary))
+        .on_press(Message::ChangePage(Page::Instance(instance.to_string())));
+
+    let mut save_button = button(
+        Row::new()
+            .push(text(format!(" {} ", t("world-save"))))
+            .push(Icon::ContentSaveOutline.view(24))
+            .padding(5)
+            .align_items(Alignment::Center),
+    )
+    .style(style::circle_button(theme::Button::Positive));
+
+    if level_dat.validate().is_ok() {
+        save_button =
+            save_button.on_press(Message::SaveWorld(instance.to_string(), world.to_string()));
+    }
+
+    Column::new()
+        .push(text(t_args("world-title", &[("world", &world)])).size(30))
+        .push(
+            container(scrollable(col).height(Length::Fill))
+                .height(Length::Fill)
+                .style(style::card()),
+        )
+        .push(
+            Row::new()
+                .push(back_button)
+                .push(horizontal_space(Length::Fill))
+                .push(save_button),
+        )
+        .spacing(10)
+        .padding(10)
+        .into()
+}
@@ -4,8 +4,32 @@
 use iced::{
     color, theme,
     widget::{button, container},
-    Background, Theme,
+    Background, Color, Theme,
 };
+
+use crate::types::notifications::Severity;
+
+fn is_dark(theme: &Theme) -> bool {
+    let background = theme.palette().background;
+    background.r + background.g + background.b < 1.5
+}
+
+fn dark_background(theme: &Theme) -> Color {
+    if is_dark(theme) {
+        color!(0x18181b)
+    } else {
+        color!(0xe4e4e7)
+    }
+}
+
+fn selected_background(theme: &Theme) -> Color {
+    if is_dark(theme) {
+        color!(0x3f3f46)
+    } else {
+        color!(0xd4d4d8)
+    }
+}
+
 pub struct CardContainerStyle {
     theme: theme::Container,
 }
@@ -23,8 +47,13 @@ impl container::StyleSheet for CardContainerStyle {
         let mut appearance = style.appearance(&self.theme);
         appearance.border_radius = 5.0.into();
         appearance.border_width = 1.0;
-        appearance.border_color = color!(0x3f3f46);
-        appearance.background = Some(Background::Color(color!(0x27272a)));
+        if is_dark(style) {
+            appearance.border_color = color!(0x3f3f46);
+            appearance.background = Some(Background::Color(color!(0x27272a)));
+        } else {
+            appearance.border_color = color!(0xd4d4d8);
+            appearance.background = Some(Background::Color(color!(0xf4f4f5)));
+        }
 
         appearance
     }
@@ -51,7 +80,7 @@ impl container::StyleSheet for DarkContainerStyle {
 
     fn appearance(&self, style: &Self::Style) -> container::Appearance {
         let mut appearance = style.appearance(&self.theme);
-        appearance.background = Some(Background::Color(color!(0x18181b)));
+        appearance.background = Some(Background::Color(dark_background(style)));
 
         appearance
     }
@@ -124,28 +153,28 @@ impl button::StyleSheet for SelectedButtonStyle {
 
     fn active(&self, style: &Self::Style) -> button::Appearance {
         let mut appearance = style.active(&self.theme);
-        appearance.background = Some(Background::Color(color!(0x3f3f46)));
+        appearance.background = Some(Background::Color(selected_background(style)));
 
         appearance
     }
 
     fn hovered(&self, style: &Self::Style) -> button::Appearance {
         let mut appearance = style.hovered(&self.theme);
-        appearance.background = Some(Background::Color(color!(0x3f3f46)));
+        appearance.background = Some(Background::Color(selected_background(style)));
 
         appearance
     }
 
     fn pressed(&self, style: &Self::Style) -> button::Appearance {
         let mut appearance = style.pressed(&self.theme);
-        appearance.background = Some(Background::Color(color!(0x3f3f46)));
+        appearance.background = Some(Background::Color(selected_background(style)));
 
         appearance
     }
 
     fn disabled(&self, style: &Self::Style) -> button::Appearance {
         let mut appearance = style.disabled(&self.theme);
-        appearance.background = Some(Background::Color(color!(0x3f3f46)));
+        appearance.background = Some(Background::Color(selected_background(style)));
 
         appearance
     }
@@ -154,3 +183,78 @@ impl button::StyleSheet for SelectedButtonStyle {
 pub fn selected_button() -> theme::Button {
     theme::Button::Custom(Box::new(SelectedButtonStyle::new(theme::Button::Primary)))
 }
+
+pub struct IconBackgroundStyle {
+    color: Color,
+}
+
+impl container::StyleSheet for IconBackgroundStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(Background::Color(self.color)),
+            border_radius: 5.0.into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn icon_background(hex: &str) -> theme::Container {
+    let color = u32::from_str_radix(hex.trim_start_matches('#'), 16)
+        .map(|rgb| Color::from_rgb8((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+        .unwrap_or(Color::TRANSPARENT);
+
+    theme::Container::Custom(Box::new(IconBackgroundStyle { color }))
+}
+
+pub struct BadgeStyle;
+
+impl container::StyleSheet for BadgeStyle {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Self::Style) -> container::Appearance {
+        let palette = style.extended_palette();
+
+        container::Appearance {
+            text_color: Some(palette.success.base.text),
+            background: Some(Background::Color(palette.success.base.color)),
+            border_radius: 200.0.into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn badge() -> theme::Container {
+    theme::Container::Custom(Box::new(BadgeStyle))
+}
+
+pub struct NotificationStyle {
+    severity: Severity,
+}
+
+impl container::StyleSheet for NotificationStyle {
+    type Style = Theme;
+
+    fn appearance(&self, style: &Self::Style) -> container::Appearance {
+        let palette = style.extended_palette();
+
+        let pair = match self.severity {
+            Severity::Info => palette.primary.weak,
+            Severity::Success => palette.success.weak,
+            Severity::Warning => palette.secondary.strong,
+            Severity::Error => palette.danger.weak,
+        };
+
+        container::Appearance {
+            text_color: Some(pair.text),
+            background: Some(Background::Color(pair.color)),
+            border_radius: 5.0.into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn notification(severity: Severity) -> theme::Container {
+    theme::Container::Custom(Box::new(NotificationStyle { severity }))
+}
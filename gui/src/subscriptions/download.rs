@@ -1,68 +1,217 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::BTreeMap;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
 use iced::{subscription, Subscription};
-use lib::DownloadQueue;
+use lib::{DownloadControl, DownloadEvent, DownloadItem, DownloadOptions, DownloadQueue};
+
+// how often the page gets refreshed
+const UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+// a file one of the workers is busy with
+struct Current {
+    item: DownloadItem,
+    transferred: u64,
+    extracted: Option<f32>,
+}
+
+impl Current {
+    // bytes of the current request that count towards the progress bar
+    fn progress(&self) -> u64 {
+        self.item.size.map_or(0, |size| self.transferred.min(size))
+    }
+}
 
 enum State {
-    Ready(DownloadQueue),
+    Ready(DownloadQueue, DownloadOptions, DownloadControl),
     Downloading {
-        queue: DownloadQueue,
-        total: usize,
-        downloaded: usize,
+        receiver: mpsc::Receiver<DownloadEvent>,
+        started: Instant,
+        current: BTreeMap<usize, Current>,
+        // sizes of the files already done
+        finished_bytes: u64,
+        stats: Stats,
     },
     Finished,
 }
 
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stats {
+    pub total: usize,
+    pub downloaded: usize,
+    pub current: Vec<String>,
+    // transferred over the network
+    pub bytes: u64,
+    // known sizes only, these drive the progress bar
+    pub total_bytes: u64,
+    pub downloaded_bytes: u64,
+    // bytes per second
+    pub speed: f64,
+    pub eta: Option<Duration>,
+}
+
+impl Stats {
+    pub fn percentage(&self) -> f32 {
+        if self.total_bytes > 0 {
+            return (self.downloaded_bytes as f32 / self.total_bytes as f32) * 100.0;
+        }
+
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        (self.downloaded as f32 / self.total as f32) * 100.0
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Progress {
     Started,
-    Advanced(f32),
+    Advanced(Stats),
     Finished,
     Errored,
 }
 
-pub fn files(queue: DownloadQueue) -> Subscription<Progress> {
+pub fn files(
+    id: usize,
+    queue: DownloadQueue,
+    options: DownloadOptions,
+    control: DownloadControl,
+) -> Subscription<(usize, Progress)> {
     struct DownloadFiles;
 
     subscription::unfold(
-        std::any::TypeId::of::<DownloadFiles>(),
-        State::Ready(queue),
-        download,
+        (std::any::TypeId::of::<DownloadFiles>(), id),
+        State::Ready(queue, options, control),
+        move |state| async move {
+            let (progress, state) = download(state).await;
+            ((id, progress), state)
+        },
     )
 }
 
 async fn download(state: State) -> (Progress, State) {
     match state {
-        State::Ready(queue) => (
-            Progress::Advanced(0.0),
+        State::Ready(queue, options, control) => (
+            Progress::Started,
             State::Downloading {
-                total: queue.len(),
-                queue,
-                downloaded: 0,
+                stats: Stats {
+                    total: queue.len(),
+                    total_bytes: queue.total_size(),
+                    ..Default::default()
+                },
+                receiver: queue.start(options, control),
+                started: Instant::now(),
+                current: BTreeMap::new(),
+                finished_bytes: 0,
             },
         ),
         State::Downloading {
-            mut queue,
-            total,
-            downloaded,
-        } => match queue.download_next() {
-            Ok(true) => {
-                let downloaded = downloaded + 1;
-                let percentage = (downloaded as f32 / total as f32) * 100.0;
-
-                (
-                    Progress::Advanced(percentage),
-                    State::Downloading {
-                        queue,
-                        total,
-                        downloaded,
-                    },
-                )
+            receiver,
+            started,
+            mut current,
+            mut finished_bytes,
+            mut stats,
+        } => {
+            let deadline = Instant::now() + UPDATE_INTERVAL;
+            let bytes_before = stats.bytes;
+
+            loop {
+                // extraction sends an event per entry, waiting for a quiet channel
+                // would hold the page back until the archive is done
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                if timeout.is_zero() {
+                    break;
+                }
+
+                match receiver.recv_timeout(timeout) {
+                    Ok(DownloadEvent::Started(worker, item)) => {
+                        current.insert(
+                            worker,
+                            Current {
+                                item,
+                                transferred: 0,
+                                extracted: None,
+                            },
+                        );
+                    }
+                    Ok(DownloadEvent::Transferred(worker, bytes)) => {
+                        stats.bytes += bytes;
+
+                        if let Some(file) = current.get_mut(&worker) {
+                            file.transferred += bytes;
+                        }
+                    }
+                    Ok(DownloadEvent::Extracted { path, done, total }) => {
+                        if let Some(file) = current.values_mut().find(|f| f.item.path == path) {
+                            file.extracted = Some(done as f32 / total.max(1) as f32 * 100.0);
+                        }
+                    }
+                    Ok(DownloadEvent::Finished(worker, item)) => {
+                        current.remove(&worker);
+                        finished_bytes += item.size.unwrap_or(0);
+                        stats.downloaded += 1;
+                    }
+                    // a resumed file only reports what its new request transfers and
+                    // counts whole once finished, what it got so far isn't kept
+                    Ok(DownloadEvent::Parked(worker)) => {
+                        current.remove(&worker);
+                    }
+                    Ok(DownloadEvent::Failed(_)) => return (Progress::Errored, State::Finished),
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    // every worker is done
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        return (Progress::Finished, State::Finished)
+                    }
+                }
             }
-            Ok(false) => (Progress::Finished, State::Finished),
-            Err(_) => (Progress::Errored, State::Finished),
-        },
+
+            stats.downloaded_bytes =
+                finished_bytes + current.values().map(Current::progress).sum::<u64>();
+
+            // smooth out the speed so it doesn't jump around
+            let speed = (stats.bytes - bytes_before) as f64 / UPDATE_INTERVAL.as_secs_f64();
+            stats.speed = stats.speed * 0.8 + speed * 0.2;
+
+            // a tiny speed or a long queue can make it too big for a duration
+            stats.eta = if stats.total_bytes > 0 && stats.speed > 0.0 {
+                let remaining = stats.total_bytes.saturating_sub(stats.downloaded_bytes);
+                Duration::try_from_secs_f64(remaining as f64 / stats.speed).ok()
+            } else {
+                let remaining = stats.total.saturating_sub(stats.downloaded);
+                u32::try_from(stats.downloaded)
+                    .ok()
+                    .and_then(|downloaded| started.elapsed().checked_div(downloaded))
+                    .zip(u32::try_from(remaining).ok())
+                    .and_then(|(per_file, remaining)| per_file.checked_mul(remaining))
+            };
+
+            stats.current = current
+                .values()
+                .filter_map(|file| {
+                    let name = file.item.path.file_name()?.to_string_lossy();
+
+                    Some(match file.extracted {
+                        Some(percentage) => format!("{name} (extracting {percentage:.0}%)"),
+                        None => name.to_string(),
+                    })
+                })
+                .collect();
+
+            (
+                Progress::Advanced(stats.clone()),
+                State::Downloading {
+                    receiver,
+                    started,
+                    current,
+                    finished_bytes,
+                    stats,
+                },
+            )
+        }
         State::Finished => iced::futures::future::pending().await,
     }
 }
@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+
+// links handed over by launchers started later
+pub fn links(listener: Arc<TcpListener>) -> Subscription<String> {
+    struct Links;
+
+    subscription::channel(
+        std::any::TypeId::of::<Links>(),
+        100,
+        |mut output| async move {
+            let (sender, mut receiver) = mpsc::unbounded();
+
+            // accepting blocks, so it's done on its own thread
+            thread::spawn(move || loop {
+                match lib::ipc::accept(&listener) {
+                    Ok(link) => {
+                        if sender.unbounded_send(link).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => tracing::warn!("ipc error: {error}"),
+                }
+            });
+
+            while let Some(link) = receiver.next().await {
+                let _ = output.send(link).await;
+            }
+
+            iced::futures::future::pending().await
+        },
+    )
+}
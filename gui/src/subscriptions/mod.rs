@@ -2,3 +2,5 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub mod download;
+pub mod ipc;
+pub mod server_console;
@@ -0,0 +1,35 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::sync::{Arc, Mutex};
+
+use iced::futures::channel::mpsc::UnboundedReceiver;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+
+use crate::types::messages::Message;
+
+// the pid tells a restarted server apart from its previous run
+pub fn output(
+    name: String,
+    pid: u32,
+    receiver: Arc<Mutex<Option<UnboundedReceiver<String>>>>,
+) -> Subscription<Message> {
+    struct Console;
+
+    subscription::channel(
+        (std::any::TypeId::of::<Console>(), name.clone(), pid),
+        100,
+        |mut output| async move {
+            let receiver = receiver.lock().unwrap().take();
+
+            if let Some(mut receiver) = receiver {
+                while let Some(line) = receiver.next().await {
+                    let _ = output.send(Message::ServerOutput(name.clone(), line)).await;
+                }
+            }
+
+            iced::futures::future::pending().await
+        },
+    )
+}
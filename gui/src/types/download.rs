@@ -4,63 +4,205 @@
 use crate::subscriptions::download;
 use crate::types::messages::Message;
 use iced::Subscription;
-use lib::DownloadQueue;
+use lib::{DownloadControl, DownloadOptions, DownloadQueue};
 
 pub enum State {
-    Idle,
-    Downloading { progress: f32, queue: DownloadQueue },
+    Queued,
+    Downloading {
+        stats: download::Stats,
+        control: DownloadControl,
+    },
     Finished,
     Errored,
 }
 
-pub struct Download {
+pub struct Job {
+    pub id: usize,
+    pub name: String,
     pub state: State,
+    queue: DownloadQueue,
+    // instance being created, removed if the job is cancelled
+    pub instance: Option<String>,
+    // sent once every file is in place, not if the job fails or is cancelled
+    then: Option<Message>,
 }
 
-impl Default for Download {
-    fn default() -> Self {
-        Self { state: State::Idle }
+impl Job {
+    pub fn is_paused(&self) -> bool {
+        match &self.state {
+            State::Downloading { control, .. } => control.is_paused(),
+            _ => false,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self.state, State::Finished | State::Errored)
     }
 }
 
+// jobs are downloaded one at a time, in the order they were added
+#[derive(Default)]
+pub struct Download {
+    pub jobs: Vec<Job>,
+    next_id: usize,
+    // no job gets started while the whole queue is paused
+    pub paused: bool,
+}
+
 impl Download {
-    pub fn start(&mut self, queue: DownloadQueue) {
-        match self.state {
-            State::Idle { .. } | State::Finished { .. } | State::Errored { .. } => {
-                self.state = State::Downloading {
-                    progress: 0.0,
-                    queue,
-                };
+    // returns the id of the job
+    pub fn enqueue(
+        &mut self,
+        name: String,
+        queue: DownloadQueue,
+        instance: Option<String>,
+    ) -> usize {
+        let id = self.next_id;
+
+        self.jobs.push(Job {
+            id,
+            name,
+            state: State::Queued,
+            queue,
+            instance,
+            then: None,
+        });
+        self.next_id += 1;
+
+        self.start_next();
+
+        id
+    }
+
+    fn start_next(&mut self) {
+        let downloading = self
+            .jobs
+            .iter()
+            .any(|job| matches!(job.state, State::Downloading { .. }));
+
+        if downloading || self.paused {
+            return;
+        }
+
+        if let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| matches!(job.state, State::Queued))
+        {
+            job.state = State::Downloading {
+                stats: download::Stats::default(),
+                control: DownloadControl::default(),
+            };
+        }
+    }
+
+    pub fn then(&mut self, id: usize, message: Message) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.then = Some(message);
+        }
+    }
+
+    fn get_control(&self, id: usize) -> Option<&DownloadControl> {
+        self.jobs.iter().find(|job| job.id == id).and_then(|job| {
+            if let State::Downloading { control, .. } = &job.state {
+                Some(control)
+            } else {
+                None
             }
-            _ => {}
+        })
+    }
+
+    pub fn pause(&self, id: usize) {
+        if let Some(control) = self.get_control(id) {
+            control.pause();
         }
     }
 
-    pub fn subscription(&self) -> Subscription<Message> {
-        match &self.state {
-            State::Downloading { progress: _, queue } => {
-                download::files(queue.clone()).map(Message::DownloadProgressed)
+    pub fn resume(&self, id: usize) {
+        if let Some(control) = self.get_control(id) {
+            control.resume();
+        }
+    }
+
+    pub fn pause_all(&mut self) {
+        self.paused = true;
+
+        for job in &self.jobs {
+            if let State::Downloading { control, .. } = &job.state {
+                control.pause();
+            }
+        }
+    }
+
+    pub fn resume_all(&mut self) {
+        self.paused = false;
+
+        for job in &self.jobs {
+            if let State::Downloading { control, .. } = &job.state {
+                control.resume();
             }
-            _ => Subscription::none(),
         }
+
+        self.start_next();
+    }
+
+    // removes the job from the list and returns it
+    pub fn cancel(&mut self, id: usize) -> Option<Job> {
+        if let Some(control) = self.get_control(id) {
+            control.cancel();
+        }
+
+        let index = self.jobs.iter().position(|job| job.id == id)?;
+        let job = self.jobs.remove(index);
+
+        self.start_next();
+
+        Some(job)
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.jobs.retain(|job| !job.is_done());
     }
 
-    pub fn update(&mut self, new_progress: download::Progress) {
-        if let State::Downloading { progress, queue: _ } = &mut self.state {
+    pub fn subscription(&self, options: DownloadOptions) -> Subscription<Message> {
+        Subscription::batch(self.jobs.iter().filter_map(|job| {
+            match &job.state {
+                State::Downloading { control, .. } => Some(
+                    download::files(job.id, job.queue.clone(), options, control.clone())
+                        .map(|(id, progress)| Message::DownloadProgressed(id, progress)),
+                ),
+                _ => None,
+            }
+        }))
+    }
+
+    // returns the message to send when the job has just finished
+    pub fn update(&mut self, id: usize, new_progress: download::Progress) -> Option<Message> {
+        let job = self.jobs.iter_mut().find(|job| job.id == id)?;
+
+        if let State::Downloading { stats, .. } = &mut job.state {
             match new_progress {
                 download::Progress::Started => {
-                    *progress = 0.0;
+                    *stats = download::Stats::default();
                 }
-                download::Progress::Advanced(percentage) => {
-                    *progress = percentage;
+                download::Progress::Advanced(new_stats) => {
+                    *stats = new_stats;
                 }
                 download::Progress::Finished => {
-                    self.state = State::Finished;
+                    job.state = State::Finished;
+                    job.instance = None;
+                    let then = job.then.take();
+                    self.start_next();
+
+                    return then;
                 }
                 download::Progress::Errored => {
-                    self.state = State::Errored;
+                    job.state = State::Errored;
+                    self.start_next();
                 }
             }
         }
+
+        None
     }
 }
@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub struct Feedback {
+    pub title: String,
+    pub description: String,
+    pub attach_summary: bool,
+}
+
+impl Default for Feedback {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            description: String::new(),
+            attach_summary: true,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::VecDeque;
+
+use lib::accounts::Account;
+
+// heads are fetched one at a time to stay below the rate limit
+#[derive(Default)]
+pub struct HeadQueue {
+    pending: VecDeque<Account>,
+    busy: bool,
+}
+
+impl HeadQueue {
+    pub fn push(&mut self, account: Account) {
+        if !self.pending.iter().any(|a| a.mc_id == account.mc_id) {
+            self.pending.push_back(account);
+        }
+    }
+
+    // the next account to fetch, unless a fetch is still running
+    pub fn next(&mut self) -> Option<Account> {
+        if self.busy {
+            return None;
+        }
+
+        let account = self.pending.pop_front()?;
+        self.busy = true;
+
+        Some(account)
+    }
+
+    pub fn finish(&mut self) {
+        self.busy = false;
+    }
+}
@@ -0,0 +1,16 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use lib::instances::InheritedSetting;
+
+// inputs on the instance page that aren't saved until submitted
+#[derive(Default)]
+pub struct InstanceEditor {
+    pub new_tag: String,
+    pub env_key: String,
+    pub env_value: String,
+    // typed values that can't be saved yet, shown with their error
+    pub drafts: HashMap<InheritedSetting, String>,
+}
@@ -0,0 +1,12 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use lib::cache::CachedDocument;
+
+#[derive(Default)]
+pub struct JsonViewer {
+    pub documents: Vec<CachedDocument>,
+    pub selected: Option<CachedDocument>,
+    pub content: String,
+    pub search: String,
+}
@@ -1,19 +1,59 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use iced::futures::TryFutureExt;
-use iced::{clipboard, Command, Subscription};
-use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::process::Child;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fs, thread};
 
+use iced::futures::channel::oneshot;
+use iced::futures::{Future, TryFutureExt};
+use iced::keyboard::{self, KeyCode};
+use iced::widget::{image, scrollable, text_input};
+use iced::{clipboard, event, subscription, window, Command, Event, Subscription};
+use rfd::{FileDialog, MessageButtons, MessageDialog, MessageLevel};
+
+use crate::cli::Args;
+use crate::format::{format_bytes, format_error};
 use crate::pages::Page;
+use crate::subscriptions::{ipc, server_console};
 use crate::types::download::Download;
-use crate::types::login::Login;
+use crate::types::feedback::Feedback;
+use crate::types::head_queue::HeadQueue;
+use crate::types::instance_editor::InstanceEditor;
+use crate::types::json_viewer::JsonViewer;
+use crate::types::login::{AuthlibLogin, Login};
 use crate::types::messages::Message;
+use crate::types::mod_browser::ModBrowser;
+use crate::types::mod_suggestions::ModSuggestions;
 use crate::types::modrinth_modpacks::ModrinthModpacks;
+use crate::types::modrinth_project::ModrinthProject;
+use crate::types::notifications::{Notifications, Severity};
+use crate::types::pack_import::PackImport;
+use crate::types::palette::{self, Palette};
+use crate::types::server_console::ServerConsole;
+use crate::types::server_list::ServerListEditor;
+use crate::types::tasks::Tasks;
 use crate::types::vanilla_installer::VanillaInstaller;
-use lib::accounts::{Account, Accounts};
-use lib::instances::Instances;
+use lib::accounts::{Account, Accounts, HeadFetch};
+use lib::backups::Backup;
+use lib::deep_link::DeepLink;
+use lib::diagnostics::GcSummary;
+use lib::gpu::Gpu;
+use lib::import::FileKind;
+use lib::instance_export::{self, ExportPart};
+use lib::instances::{Instance, InstanceKind, Instances, LaunchOptions, Loader};
+use lib::running::RunningGames;
+use lib::runtime_manager::system::JavaInstallation;
+use lib::server::ServerProperties;
 use lib::settings::Settings;
+use lib::skin::SkinPreview;
+use lib::store::DiskUsage;
+use lib::vanilla_installer::{LatestRelease, PatchNotes};
+use lib::world::LevelDat;
+use lib::DownloadQueue;
 
 pub struct Launcher {
     pub name: &'static str,
@@ -21,13 +61,89 @@ pub struct Launcher {
     pub instances: Instances,
     pub settings: Settings,
     pub accounts: Accounts,
+    pub running: RunningGames,
+    // games started by this session, with their pid
+    pub running_instances: HashMap<String, u32>,
+    // nothing that needs the network is done at startup, games use the cached tokens
+    pub offline: bool,
+    pub server_consoles: HashMap<String, ServerConsole>,
+    pub server_properties: ServerProperties,
+    // of the world being edited
+    pub level_dat: Option<LevelDat>,
+    pub server_list: ServerListEditor,
+    // None until the version manifest is fetched
+    pub latest_release: Option<LatestRelease>,
+    // deep links opened while the launcher is running arrive here
+    pub ipc_listener: Option<Arc<TcpListener>>,
+    pub java_installations: Vec<JavaInstallation>,
+    // empty unless the game can be sent to another gpu
+    pub gpus: Vec<Gpu>,
+    // from the last profiled session of each instance
+    pub gc_summaries: HashMap<String, GcSummary>,
+    // of the instances opened this session, newest first
+    pub backups: HashMap<String, Vec<Backup>>,
+    // instances page shows only instances with all of these
+    pub tag_filter: Vec<String>,
+    pub instance_editor: InstanceEditor,
+    // what goes in the next instance zip
+    pub export_parts: Vec<ExportPart>,
     pub login: Login,
     pub offline_account_username: String,
+    pub authlib_login: AuthlibLogin,
+    pub predownload_versions: String,
     pub vanilla_installer: VanillaInstaller,
+    // by version, they don't change during a session
+    pub patch_notes: HashMap<String, Option<PatchNotes>>,
     pub modrinth_modpacks: ModrinthModpacks,
+    pub mod_suggestions: ModSuggestions,
+    pub mod_browser: ModBrowser,
+    // of the active account, shown on the accounts page
+    pub skin_preview: Option<SkinPreview>,
+    pub modrinth_project: Option<ModrinthProject>,
+    pub pack_import: Option<PackImport>,
+    pub json_viewer: JsonViewer,
+    pub disk_usage: Option<DiskUsage>,
+    pub feedback: Feedback,
     pub download: Download,
+    pub tasks: Tasks,
+    pub head_queue: HeadQueue,
+    pub notifications: Notifications,
+    pub palette: Palette,
+}
+
+// waiting blocks, so it's done on its own thread
+fn wait_for_exit(mut child: Child) -> impl Future<Output = Result<(), String>> {
+    let (sender, receiver) = oneshot::channel();
+
+    thread::spawn(move || {
+        let result = child.wait().map(|_| ()).map_err(|error| error.to_string());
+        let _ = sender.send(result);
+    });
+
+    async move {
+        receiver
+            .await
+            .unwrap_or_else(|_| Err("Lost track of the game process".to_string()))
+    }
+}
+
+// how long notifications that don't ask for anything stay around
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    let (sender, receiver) = oneshot::channel();
+
+    thread::spawn(move || {
+        thread::sleep(duration);
+        let _ = sender.send(());
+    });
+
+    async move {
+        let _ = receiver.await;
+    }
 }
 
+// only for startup failures, there's no window to show a notification in yet
 fn error_dialog(error: &str) {
     MessageDialog::new()
         .set_level(MessageLevel::Error)
@@ -39,18 +155,19 @@ fn error_dialog(error: &str) {
 
 impl Default for Launcher {
     fn default() -> Self {
-        let instances = match Instances::load() {
-            Ok(instances) => instances,
+        let settings = match Settings::load() {
+            Ok(settings) => settings,
             Err(error) => {
-                error_dialog(&error.to_string());
+                error_dialog(&format_error(&error));
                 panic!();
             }
         };
+        lib::i18n::set_language(settings.language);
 
-        let settings = match Settings::load() {
-            Ok(settings) => settings,
+        let instances = match Instances::load(settings.instances_dir()) {
+            Ok(instances) => instances,
             Err(error) => {
-                error_dialog(&error.to_string());
+                error_dialog(&format_error(&error));
                 panic!();
             }
         };
@@ -58,7 +175,23 @@ impl Default for Launcher {
         let accounts = match Accounts::load() {
             Ok(accounts) => accounts,
             Err(error) => {
-                error_dialog(&error.to_string());
+                error_dialog(&format_error(&error));
+                panic!();
+            }
+        };
+
+        let ipc_listener = match lib::ipc::listen() {
+            Ok(listener) => Some(Arc::new(listener)),
+            Err(error) => {
+                tracing::warn!("not listening for deep links: {error}");
+                None
+            }
+        };
+
+        let running = match RunningGames::load() {
+            Ok(running) => running,
+            Err(error) => {
+                error_dialog(&format_error(&error));
                 panic!();
             }
         };
@@ -69,37 +202,182 @@ impl Default for Launcher {
             instances,
             settings,
             accounts,
+            running,
+            running_instances: HashMap::new(),
+            offline: false,
+            server_consoles: HashMap::new(),
+            server_properties: ServerProperties::default(),
+            level_dat: None,
+            server_list: ServerListEditor::default(),
+            latest_release: None,
+            ipc_listener,
+            java_installations: Vec::new(),
+            gpus: Vec::new(),
+            gc_summaries: HashMap::new(),
+            backups: HashMap::new(),
+            tag_filter: Vec::new(),
+            instance_editor: InstanceEditor::default(),
+            export_parts: vec![
+                ExportPart::Saves,
+                ExportPart::Mods,
+                ExportPart::Config,
+                ExportPart::ResourcePacks,
+            ],
             login: Login::default(),
             offline_account_username: String::new(),
+            authlib_login: AuthlibLogin::default(),
+            predownload_versions: String::new(),
             vanilla_installer: VanillaInstaller::default(),
+            patch_notes: HashMap::new(),
             modrinth_modpacks: ModrinthModpacks::default(),
+            mod_suggestions: ModSuggestions::default(),
+            mod_browser: ModBrowser::default(),
+            skin_preview: None,
+            modrinth_project: None,
+            pack_import: None,
+            json_viewer: JsonViewer::default(),
+            disk_usage: None,
+            feedback: Feedback::default(),
             download: Download::default(),
+            tasks: Tasks::default(),
+            head_queue: HeadQueue::default(),
+            notifications: Notifications::default(),
+            palette: Palette::default(),
         }
     }
 }
 
 impl Launcher {
-    pub fn new() -> (Self, Command<Message>) {
-        let launcher = Self::default();
+    pub fn new(args: Args) -> (Self, Command<Message>) {
+        let mut launcher = Self::default();
+        let mut commands = Vec::new();
+
+        // anything still running was started by a previous session
+        if !launcher.running.list.is_empty() {
+            let games = launcher
+                .running
+                .list
+                .iter()
+                .map(|game| format!("{} (pid {})", game.instance, game.pid))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            commands.push(launcher.notify(
+                Severity::Warning,
+                "Games still running",
+                format!(
+                    "These games were started by a previous session and are still running: {games}"
+                ),
+                vec![("Terminate".to_string(), Message::TerminatePreviousGames)],
+            ));
+        }
+
+        if let Err(error) = launcher
+            .instances
+            .purge_trash(launcher.settings.trash_retention_days)
+        {
+            tracing::warn!("failed to empty the trash: {error:#}");
+        }
+
+        // always taken, so opting in later doesn't bring up old crashes
+        if let Some(report) = lib::crash_reporter::take_last() {
+            if launcher.settings.crash_reports {
+                commands.push(launcher.notify(
+                    Severity::Warning,
+                    "The launcher crashed",
+                    "A crash report was saved, you can review it and open an issue with it",
+                    vec![
+                        (
+                            "Show".to_string(),
+                            Message::OpenCrashReport(report.path.clone()),
+                        ),
+                        ("Report".to_string(), Message::ReportCrash(report)),
+                    ],
+                ));
+            }
+        }
+
+        if let Err(error) = lib::deep_link::register() {
+            tracing::warn!(
+                "failed to register {} links: {error}",
+                lib::deep_link::SCHEME
+            );
+        }
+
+        if let Some(name) = args.instance {
+            launcher.page = if launcher.instances.list.contains_key(&name) {
+                commands.push(launcher.get_backups(&name));
+                Page::Instance(name)
+            } else {
+                Page::Error(format!("Instance not found: {name}"))
+            };
+        } else if let Some(page) = args.view {
+            commands.push(launcher.update(Message::ChangePage(page)));
+        }
+
+        if let Some(link) = args.deep_link {
+            commands.push(launcher.update(Message::OpenDeepLink(link)));
+        }
+
+        if cfg!(feature = "updater") {
+            lib::updater::remove_old_exe();
+        }
+
+        // the size chips of the instances page
+        if launcher.page == Page::Instances {
+            commands.push(launcher.get_disk_usage());
+        }
+
+        // look for java installations to offer alongside the managed runtimes
+        commands.push(Command::perform(
+            lib::runtime_manager::system::detect(),
+            Message::GotJavaInstallations,
+        ));
+
+        commands.push(Command::perform(lib::gpu::detect(), Message::GotGpus));
+
+        // nothing else is fetched until we know there's a network
+        commands.push(Command::perform(
+            lib::network::check_connection(),
+            Message::GotConnection,
+        ));
+
+        (launcher, Command::batch(commands))
+    }
+
+    // everything fetched in the background once the launcher is known to be online
+    fn start_online(&mut self) -> Command<Message> {
         let mut commands = Vec::new();
 
         // check for updates
-        if cfg!(feature = "updater") && launcher.settings.check_for_updates {
+        if cfg!(feature = "updater") && self.settings.check_for_updates {
             commands.push(Command::perform(
-                lib::updater::check_for_updates().map_err(|e| e.to_string()),
+                lib::updater::check_for_updates().map_err(|e| format_error(&e)),
                 Message::GotUpdate,
             ));
         }
 
-        // fetch account head
-        if let Some(account) = &launcher.accounts.active {
-            commands.push(Command::perform(
-                lib::accounts::get_head(account.to_owned()).map_err(|e| e.to_string()),
-                Message::GotAccountHead,
-            ));
+        // to tell which instances can be updated
+        commands.push(Command::perform(
+            lib::vanilla_installer::get_latest_release().map_err(|e| format_error(&e)),
+            Message::GotLatestRelease,
+        ));
+
+        // fetch account heads, the active one first
+        let accounts = self
+            .accounts
+            .active
+            .iter()
+            .chain(&self.accounts.others)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for account in accounts {
+            self.head_queue.push(account);
         }
+        commands.push(self.fetch_next_head());
 
-        (launcher, Command::batch(commands))
+        Command::batch(commands)
     }
 
     pub fn update(&mut self, message: Message) -> Command<Message> {
@@ -109,11 +387,56 @@ impl Launcher {
                     self.vanilla_installer = VanillaInstaller::default();
                     self.page = page;
                     return Command::perform(
-                        lib::vanilla_installer::get_versions().map_err(|e| e.to_string()),
+                        lib::vanilla_installer::get_versions().map_err(|e| format_error(&e)),
                         Message::GotVersions,
                     );
                 }
 
+                if page == Page::JsonViewer {
+                    self.json_viewer = JsonViewer::default();
+
+                    match lib::cache::list_documents() {
+                        Ok(documents) => self.json_viewer.documents = documents,
+                        Err(error) => {
+                            return self.update(Message::Error(format_error(&error), false))
+                        }
+                    }
+                }
+
+                // for the size chips
+                if page == Page::Instances && self.disk_usage.is_none() {
+                    self.page = page;
+
+                    return self.get_disk_usage();
+                }
+
+                if page == Page::ModrinthModpacks {
+                    self.page = page;
+
+                    return self.update(Message::GetModpacks);
+                }
+
+                if page == Page::Accounts {
+                    self.page = page;
+
+                    return self.get_skin_preview();
+                }
+
+                if page == Page::Storage {
+                    self.disk_usage = None;
+                    self.page = page;
+
+                    return self.get_disk_usage();
+                }
+
+                if let Page::Instance(name) = &page {
+                    self.instance_editor.drafts.clear();
+                    let command = self.get_backups(name);
+                    self.page = page;
+
+                    return command;
+                }
+
                 self.page = page;
             }
             Message::Error(error, fatal) => {
@@ -125,7 +448,63 @@ impl Launcher {
                 if fatal {
                     self.page = Page::Error(error.to_string());
                 } else {
-                    error_dialog(&error);
+                    return self.notify(Severity::Error, "Error", error, Vec::new());
+                }
+            }
+            Message::DismissNotification(id) => {
+                self.notifications.dismiss(id);
+            }
+            Message::ToggleNotificationDetails(id) => {
+                self.notifications.toggle_details(id);
+            }
+            Message::CopyToClipboard(content) => {
+                return clipboard::write(content);
+            }
+            Message::NotificationAction(id, message) => {
+                self.notifications.dismiss(id);
+                return self.update(*message);
+            }
+            Message::OpenPalette => {
+                self.palette.open = true;
+                self.palette.query.clear();
+
+                return text_input::focus(palette::input_id());
+            }
+            Message::ClosePalette => {
+                self.palette.open = false;
+            }
+            Message::PaletteQueryChanged(query) => {
+                self.palette.query = query;
+            }
+            Message::PaletteSubmit => {
+                let Some((_, message)) = self.palette.entries(&self.instances).into_iter().next()
+                else {
+                    return Command::none();
+                };
+
+                return self.update(Message::PaletteSelect(Box::new(message)));
+            }
+            Message::PaletteSelect(message) => {
+                self.palette.open = false;
+                return self.update(*message);
+            }
+            Message::LaunchOpenInstance => {
+                if let Some(name) = self.open_instance() {
+                    return self.update(Message::LaunchInstance(name));
+                }
+            }
+            Message::TerminatePreviousGames => {
+                let pids = self
+                    .running
+                    .list
+                    .iter()
+                    .map(|game| game.pid)
+                    .collect::<Vec<_>>();
+
+                for pid in pids {
+                    if let Err(error) = self.running.terminate(pid) {
+                        return self.update(Message::Error(format_error(&error), false));
+                    }
                 }
             }
             Message::OpenURL(url) => {
@@ -133,34 +512,90 @@ impl Launcher {
                     return self.update(Message::Error(error.to_string(), false));
                 }
             }
-            Message::GotUpdate(Ok(Some((version, url)))) => {
-                let result = MessageDialog::new()
-                    .set_level(MessageLevel::Info)
-                    .set_title("Update available")
-                    .set_description(format!("Version {} is available", version))
-                    .set_buttons(MessageButtons::OkCancelCustom(
-                        "Update".to_string(),
-                        "Cancel".to_string(),
-                    ))
-                    .show();
+            Message::GotUpdate(Ok(Some(update))) => {
+                return self.notify(
+                    Severity::Info,
+                    "Update available",
+                    format!(
+                        "Version {} is available, it will be checked against its signature before installing",
+                        update.version
+                    ),
+                    vec![("Update".to_string(), Message::InstallUpdate(update))],
+                );
+            }
+            Message::InstallUpdate(update) => {
+                return self.tasks.perform(
+                    format!("Downloading version {}", update.version),
+                    lib::updater::download_update(update).map_err(|e| format_error(&e)),
+                    Message::DownloadedUpdate,
+                );
+            }
+            Message::DownloadedUpdate(Ok(artifact)) => {
+                // apps can't replace themselves inside a mounted dmg
+                if cfg!(target_os = "macos") {
+                    if let Err(error) = open::that(&artifact) {
+                        return self.update(Message::Error(error.to_string(), false));
+                    }
+
+                    return Command::none();
+                }
 
-                if result == MessageDialogResult::Ok {
-                    return self.update(Message::OpenURL(url));
+                if let Err(error) =
+                    lib::updater::install(&artifact).and_then(|_| lib::updater::restart())
+                {
+                    return self.update(Message::Error(format_error(&error), false));
                 }
+
+                return window::close();
+            }
+            Message::DownloadedUpdate(Err(error)) => {
+                return self.update(Message::Error(error, false));
             }
             Message::GotUpdate(Ok(None)) => {
-                println!("No updates available");
+                tracing::info!("No updates available");
             }
             Message::GotUpdate(Err(error)) => {
                 return self.update(Message::Error(error, false));
             }
-            Message::GotAccountHead(Ok(account)) => {
+            Message::GotAccountHead(Ok(HeadFetch::RateLimited {
+                account,
+                attempt,
+                wait,
+            })) => {
+                // the queue stays busy until the retry is done
+                return Command::perform(sleep(wait), move |_| {
+                    Message::RetryAccountHead(account, attempt)
+                });
+            }
+            Message::RetryAccountHead(account, attempt) => {
+                return Command::perform(
+                    lib::accounts::get_head(account, attempt).map_err(|e| format_error(&e)),
+                    Message::GotAccountHead,
+                );
+            }
+            Message::GotAccountHead(Ok(HeadFetch::Fetched(account))) => {
+                self.head_queue.finish();
+
                 if let Err(error) = self.accounts.update_account(&account) {
-                    return self.update(Message::Error(error.to_string(), false));
+                    return self.update(Message::Error(format_error(&error), false));
                 }
+
+                return self.fetch_next_head();
+            }
+            Message::GotSkinPreview(Ok(preview)) => {
+                self.skin_preview = Some(preview);
+            }
+            Message::GotSkinPreview(Err(error)) => {
+                // the page works without it
+                tracing::warn!("failed to fetch skin preview: {error}");
             }
             Message::GotAccountHead(Err(error)) => {
-                return self.update(Message::Error(error, false));
+                self.head_queue.finish();
+
+                // the placeholder is good enough, no need for a dialog
+                tracing::warn!("failed to fetch account head: {error}");
+
+                return self.fetch_next_head();
             }
             Message::CreatedInstance(Ok(())) => {
                 self.page = Page::Instances;
@@ -169,180 +604,2439 @@ impl Launcher {
                 return self.update(Message::Error(error, true));
             }
             Message::LaunchInstance(name) => {
-                if let Some(account) = &self.accounts.active {
-                    if let Err(error) = self.instances.launch(&name, account) {
-                        return self.update(Message::Error(error.to_string(), true));
-                    }
-                } else {
-                    return self.update(Message::Error("No account selected".to_string(), false));
-                }
+                return self.launch(name, LaunchOptions::default());
             }
-            Message::OpenInstanceFolder(name) => {
-                let path = self.instances.get_dir(&name);
-
-                if let Err(error) = open::that(path) {
-                    return self.update(Message::Error(error.to_string(), false));
-                }
-            }
-            Message::OpenInstanceConfig(name) => {
-                let path = self.instances.get_config_path(&name);
+            Message::LaunchDemo(name) => {
+                let options = LaunchOptions {
+                    demo: true,
+                    ..Default::default()
+                };
 
-                if let Err(error) = open::that(path) {
-                    return self.update(Message::Error(error.to_string(), false));
-                }
+                return self.launch(name, options);
             }
-            Message::DeleteInstance(name) => {
-                let result = MessageDialog::new()
-                    .set_title("Delete instance")
-                    .set_description(format!("Are you sure you want to delete {name}?"))
-                    .set_buttons(MessageButtons::YesNo)
-                    .show();
+            Message::QuickPlay(name, world) => {
+                let options = LaunchOptions {
+                    quick_play: Some(world),
+                    ..Default::default()
+                };
 
-                if result == MessageDialogResult::Yes {
-                    if let Err(error) = self.instances.delete(&name) {
-                        return self.update(Message::Error(error.to_string(), true));
-                    }
-                }
+                return self.launch(name, options);
             }
-            Message::GetVersions => {
-                return Command::perform(
-                    lib::vanilla_installer::get_versions().map_err(|e| e.to_string()),
-                    Message::GotVersions,
-                );
+            Message::CheckConnection => {
+                return Command::perform(lib::network::check_connection(), Message::GotConnection);
             }
-            Message::GotVersions(Ok(versions)) => {
-                self.vanilla_installer.versions = versions;
-            }
-            Message::GotVersions(Err(error)) => {
-                return self.update(Message::Error(error, false));
+            Message::GotConnection(true) => {
+                self.offline = false;
+                return self.start_online();
             }
-            Message::ChangeName(name) => {
-                self.vanilla_installer.name = name;
+            Message::GotConnection(false) => {
+                self.offline = true;
+                return self.notify(
+                    Severity::Warning,
+                    "Offline",
+                    "There's no network connection, installed instances can still be played",
+                    vec![("Retry".to_string(), Message::CheckConnection)],
+                );
             }
-            Message::SetOptimizeJvm(optimize_jvm) => {
-                self.vanilla_installer.optimize_jvm = optimize_jvm;
+            Message::GameExited(name, pid, Ok(())) => {
+                let command = self.game_exited(name, pid);
+                return Command::batch([command, self.restore_window()]);
             }
-            Message::SetMemory(memory) => {
-                self.vanilla_installer.memory = memory;
+            Message::GameExited(name, pid, Err(error)) => {
+                let command = self.game_exited(name, pid);
+                return Command::batch([
+                    command,
+                    self.restore_window(),
+                    self.update(Message::Error(error, false)),
+                ]);
             }
-            Message::SelectVersion(index) => {
-                self.vanilla_installer.selected_version = Some(index);
+            Message::StartExperiment(name) => {
+                if let Err(error) = lib::experiments::start(&self.instances.get_dir(&name)) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
             }
-            Message::CreateInstance => {
-                let name = self.vanilla_installer.name.clone();
-                let version = self.vanilla_installer.selected_version.unwrap();
-                let version = self.vanilla_installer.versions[version].clone();
-                let optimize_jvm = self.vanilla_installer.optimize_jvm;
-                let memory = self.vanilla_installer.memory.clone();
+            Message::EndExperiment(name, keep) => {
+                let dir = self.instances.get_dir(&name);
+                let result = if keep {
+                    lib::experiments::commit(&dir)
+                } else {
+                    lib::experiments::rollback(&dir)
+                };
 
-                if let Err(error) = self
-                    .instances
-                    .create(name, version, None, optimize_jvm, memory)
-                {
-                    return self.update(Message::Error(error.to_string(), true));
+                if let Err(error) = result {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::ToggleTagFilter(tag) => {
+                if self.tag_filter.contains(&tag) {
+                    self.tag_filter.retain(|t| *t != tag);
                 } else {
-                    self.page = Page::Instances;
-                    self.vanilla_installer = VanillaInstaller::default();
-                    //return self.update(Message::UpdateInstances);
+                    self.tag_filter.push(tag);
                 }
             }
-            Message::AddAccount => {
-                let client = Accounts::get_client().unwrap();
-                let details = Accounts::get_details(&client).unwrap();
+            Message::NewTagChanged(tag) => {
+                self.instance_editor.new_tag = tag;
+            }
+            Message::AddInstanceTag(name, tag) => {
+                if let Err(error) = self.instances.add_tag(&name, &tag) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
 
-                self.login.url = details.verification_uri().to_string();
-                self.login.code = details.user_code().secret().to_string();
-                self.page = Page::AddingAccount;
+                self.instance_editor.new_tag.clear();
+            }
+            Message::RemoveInstanceTag(name, tag) => {
+                if let Err(error) = self.instances.remove_tag(&name, &tag) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
 
-                return Command::perform(
-                    Accounts::get_account(client, details).map_err(|e| e.to_string()),
-                    Message::LoggedIn,
-                );
+                // a filter on a tag nobody has would hide everything
+                let tags = self.instances.get_tags();
+                self.tag_filter.retain(|t| tags.contains(t));
             }
-            Message::LoggedIn(Ok(account)) => {
-                self.login = Login::default();
+            Message::EnvKeyChanged(key) => {
+                self.instance_editor.env_key = key;
+            }
+            Message::EnvValueChanged(value) => {
+                self.instance_editor.env_value = value;
+            }
+            Message::AddInstanceEnv(name) => {
+                let key = self.instance_editor.env_key.clone();
+                let value = self.instance_editor.env_value.clone();
 
-                if let Err(error) = self.accounts.add_account(account) {
-                    return self.update(Message::Error(error.to_string(), false));
-                } else {
-                    self.page = Page::Accounts;
+                if let Err(error) = self.instances.set_env(&name, &key, Some(value)) {
+                    return self.update(Message::Error(format_error(&error), false));
                 }
-            }
-            Message::LoggedIn(Err(error)) => {
-                self.login = Login::default();
-                self.page = Page::Accounts;
 
-                return self.update(Message::Error(error, false));
+                self.instance_editor.env_key.clear();
+                self.instance_editor.env_value.clear();
             }
-            Message::OfflineAccountUsernameChanged(username) => {
-                self.offline_account_username = username;
+            Message::RemoveInstanceEnv(name, key) => {
+                if let Err(error) = self.instances.set_env(&name, &key, None) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
             }
-            Message::AddOfflineAccount => {
-                let account = Account::new_offline(self.offline_account_username.clone());
-
-                if let Err(error) = self.accounts.add_account(account) {
-                    return self.update(Message::Error(error.to_string(), false));
-                } else {
-                    self.page = Page::Accounts;
+            Message::SetInstanceInherited(name, setting, value) => {
+                match self.instances.set_inherited(&name, setting, value.clone()) {
+                    Ok(()) => {
+                        self.instance_editor.drafts.remove(&setting);
+                    }
+                    // half typed, the error is shown next to the field
+                    Err(_)
+                        if value
+                            .as_deref()
+                            .is_some_and(|v| setting.validate(v).is_err()) =>
+                    {
+                        self.instance_editor.drafts.insert(setting, value.unwrap());
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
                 }
             }
-            Message::SelectAccount(account) => {
-                if let Err(error) = self.accounts.set_active_account(account) {
-                    return self.update(Message::Error(error.to_string(), false));
+            Message::SetInstanceProfiling(name, profiling) => {
+                if let Err(error) = self.instances.set_profiling(&name, profiling) {
+                    return self.update(Message::Error(format_error(&error), false));
                 }
             }
-            Message::OpenLoginUrl => {
-                if let Err(error) = open::that(&self.login.url) {
-                    return self.update(Message::Error(error.to_string(), false));
-                } else {
-                    return clipboard::write(self.login.code.to_owned());
+            Message::SetBackupSchedule(name, schedule) => {
+                if let Err(error) = self.instances.set_backup_schedule(&name, schedule) {
+                    return self.update(Message::Error(format_error(&error), false));
                 }
             }
-            Message::RemoveAccount(account) => {
-                let result = MessageDialog::new()
-                    .set_title("Remove account")
-                    .set_description(format!(
-                        "Are you sure you want to remove {}?",
-                        account.mc_username
-                    ))
-                    .set_buttons(MessageButtons::YesNo)
-                    .show();
-
-                if result == MessageDialogResult::Yes {
-                    if let Err(error) = self.accounts.remove_account(&account.mc_id) {
-                        return self.update(Message::Error(error.to_string(), false));
-                    }
+            Message::SetBackupRetention(name, value) => {
+                let retention = match value.trim() {
+                    "" => None,
+                    value => match value.parse() {
+                        Ok(retention) => Some(retention),
+                        Err(_) => return Command::none(),
+                    },
+                };
+
+                if let Err(error) = self.instances.set_backup_retention(&name, retention) {
+                    return self.update(Message::Error(format_error(&error), false));
                 }
             }
-            Message::SetCheckForUpdates(check_for_updates) => {
-                self.settings.check_for_updates = check_for_updates;
+            Message::BackUpInstance(name) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                let keep = info
+                    .backup_retention
+                    .unwrap_or(lib::backups::DEFAULT_RETENTION);
+
+                return self.tasks.perform(
+                    format!("Backing up {name}"),
+                    lib::backups::back_up(name.clone(), self.instances.get_dir(&name), keep)
+                        .map_err(|e| format_error(&e)),
+                    move |result| Message::BackedUpInstance(name.clone(), result),
+                );
             }
-            Message::SaveSettings => {
-                if let Err(error) = self.settings.save() {
-                    return self.update(Message::Error(error.to_string(), false));
+            Message::BackedUpInstance(name, Ok(())) => {
+                return self.get_backups(&name);
+            }
+            Message::BackedUpInstance(name, Err(error)) => {
+                return Command::batch([
+                    self.get_backups(&name),
+                    self.update(Message::Error(error, false)),
+                ]);
+            }
+            Message::GotBackups(name, backups) => {
+                self.backups.insert(name, backups);
+            }
+            Message::BackedUpBeforeLaunch(name, options, result) => {
+                // a failed backup shouldn't keep the game from starting
+                if let Err(error) = result {
+                    tracing::warn!("failed to back up {name}: {error}");
                 }
+
+                return Command::batch([
+                    self.get_backups(&name),
+                    self.run_pre_launch_hook(name, options),
+                ]);
             }
-            Message::GetModpacks => {
-                return Command::perform(
-                    lib::modrinth::search_modpacks("").map_err(|e| e.to_string()),
-                    Message::GotModpacks,
+            Message::RanPreLaunchHook(name, options, Ok(())) => {
+                return self.start_game(name, options);
+            }
+            Message::RanPreLaunchHook(_, _, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::RestoreBackup(name, backup) => {
+                if self.running.get(&name).is_some() {
+                    return self.update(Message::Error(
+                        format!("{name} is running, close it before restoring a backup"),
+                        false,
+                    ));
+                }
+
+                return self.notify(
+                    Severity::Warning,
+                    "Restore backup",
+                    format!("The worlds, mods and configs of {name} will be replaced"),
+                    vec![(
+                        "Restore".to_string(),
+                        Message::ConfirmRestoreBackup(name, backup),
+                    )],
+                );
+            }
+            Message::ConfirmRestoreBackup(name, backup) => {
+                return self.tasks.perform_uncancellable(
+                    format!("Restoring {name}"),
+                    lib::backups::restore(self.instances.get_dir(&name), backup)
+                        .map_err(|e| format_error(&e)),
+                    Message::RestoredBackup,
                 );
             }
-            Message::GotModpacks(Ok(projects)) => {
-                self.modrinth_modpacks.projects = projects.hits;
+            Message::RestoredBackup(Ok(())) => {
+                return self.notify(
+                    Severity::Info,
+                    "Backup restored",
+                    "The instance is back to the state of the backup",
+                    Vec::new(),
+                );
             }
-            Message::GotModpacks(Err(error)) => {
+            Message::RestoredBackup(Err(error)) => {
                 return self.update(Message::Error(error, false));
             }
-            Message::DownloadProgressed(progress) => {
-                self.download.update(progress);
+            Message::DeleteBackup(name, backup) => {
+                if let Err(error) = lib::backups::delete(&backup) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                return self.get_backups(&name);
+            }
+            Message::SetInstanceJvmPreset(name, jvm_preset) => {
+                if let Err(error) = self.instances.set_jvm_preset(&name, jvm_preset) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::LinkForwarded(link) => {
+                return Command::batch([
+                    window::minimize(false),
+                    window::gain_focus(),
+                    self.update(Message::OpenDeepLink(link)),
+                ]);
             }
-        }
+            Message::OpenDeepLink(link) => {
+                let link = match lib::deep_link::parse(&link) {
+                    Ok(link) => link,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
 
-        Command::none()
-    }
+                match link {
+                    DeepLink::OpenInstance(name) => {
+                        if !self.instances.list.contains_key(&name) {
+                            return self.update(Message::Error(
+                                format!("Instance not found: {name}"),
+                                false,
+                            ));
+                        }
 
-    pub fn subscription(&self) -> Subscription<Message> {
-        self.download.subscription()
+                        return self.update(Message::ChangePage(Page::Instance(name)));
+                    }
+                    DeepLink::OpenView(name) => match Page::from_name(&name) {
+                        Some(page) => return self.update(Message::ChangePage(page)),
+                        None => {
+                            return self
+                                .update(Message::Error(format!("Unknown page: {name}"), false))
+                        }
+                    },
+                    DeepLink::Open => {}
+                    DeepLink::InstallModrinth(project) => {
+                        let name = match self.open_instance() {
+                            Some(name) => name,
+                            None => {
+                                return self.update(Message::Error(
+                                    format!("Open the instance to install {project} into, then click the link again"),
+                                    false,
+                                ));
+                            }
+                        };
+
+                        return self.notify(
+                            Severity::Info,
+                            "Install mod",
+                            format!(
+                                "Do you want to install the Modrinth project {project} into {name}?"
+                            ),
+                            vec![(
+                                "Install".to_string(),
+                                Message::ConfirmInstallProject(name, project),
+                            )],
+                        );
+                    }
+                }
+            }
+            Message::ConfirmInstallProject(name, project) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                let minecraft = info.minecraft.clone();
+                let loader = info.loader;
+
+                return Command::perform(
+                    lib::modrinth::get_mod_version(project.clone(), minecraft, loader)
+                        .map_err(|e| format_error(&e)),
+                    move |result| Message::GotModVersion(name.clone(), project.clone(), result),
+                );
+            }
+            Message::FileDropped(path) => match lib::import::detect(&path) {
+                Some(FileKind::Mod) => {
+                    let Some(name) = self.open_instance() else {
+                        return self.update(Message::Error(
+                            "Open the instance to install the mod into, then drop it again"
+                                .to_string(),
+                            false,
+                        ));
+                    };
+
+                    if let Err(error) =
+                        lib::import::install_mod_file(&path, &self.instances.get_dir(&name))
+                    {
+                        return self.update(Message::Error(format_error(&error), false));
+                    }
+                }
+                Some(_) => match lib::import::read_pack(&path) {
+                    Ok(info) => {
+                        self.pack_import = Some(PackImport {
+                            path,
+                            name: info.name.clone(),
+                            info,
+                            temporary: false,
+                        });
+                        self.page = Page::PackImport;
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                },
+                None => {
+                    return self.update(Message::Error(
+                        format!("Can't import {}", path.display()),
+                        false,
+                    ));
+                }
+            },
+            Message::ImportNameChanged(name) => {
+                if let Some(pack_import) = &mut self.pack_import {
+                    pack_import.name = name;
+                }
+            }
+            Message::ImportPack => {
+                let Some(mut pack_import) = self.pack_import.take() else {
+                    return Command::none();
+                };
+
+                let name = pack_import.name.clone();
+                let info = pack_import.info.clone();
+                let path = pack_import.path.clone();
+
+                let instance = Instance::new(
+                    info.minecraft.clone(),
+                    info.loader,
+                    info.loader_version.clone(),
+                    None,
+                    None,
+                );
+
+                if let Err(error) = self.instances.create(name.clone(), instance.clone()) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                self.page = Page::Download;
+
+                // a downloaded pack is deleted by the task, once it's been read
+                let temporary = std::mem::take(&mut pack_import.temporary);
+                let install = lib::import::install_pack(
+                    path.clone(),
+                    instance,
+                    self.instances.get_dir(&name),
+                    self.settings.java_vendor,
+                    self.settings.download_threads,
+                );
+
+                return self.tasks.perform(
+                    format!("Preparing {name}"),
+                    async move {
+                        let result = install.await;
+                        if temporary {
+                            let _ = fs::remove_file(&path);
+                        }
+
+                        result.map_err(|e| format_error(&e))
+                    },
+                    move |result| Message::ImportedPack(name.clone(), result),
+                );
+            }
+            Message::TerminateInstance(name) => {
+                // servers save the world when asked to stop
+                if let Some(console) = self.server_consoles.get_mut(&name) {
+                    if console.stdin.is_some() {
+                        if let Err(error) = console.send("stop") {
+                            return self.update(Message::Error(error.to_string(), false));
+                        }
+
+                        return Command::none();
+                    }
+                }
+
+                if let Some(pid) = self.running.get(&name).map(|game| game.pid) {
+                    if let Err(error) = self.running.terminate(pid) {
+                        return self.update(Message::Error(format_error(&error), false));
+                    }
+                }
+            }
+            Message::AcceptEula(name) => {
+                if let Err(error) = lib::server::accept_eula(&self.instances.get_dir(&name)) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                return self.start_server(name);
+            }
+            Message::ServerOutput(name, line) => {
+                if let Some(console) = self.server_consoles.get_mut(&name) {
+                    console.push(line);
+
+                    return scrollable::snap_to(
+                        scrollable::Id::new("server-console"),
+                        scrollable::RelativeOffset::END,
+                    );
+                }
+            }
+            Message::ServerInputChanged(name, input) => {
+                if let Some(console) = self.server_consoles.get_mut(&name) {
+                    console.input = input;
+                }
+            }
+            Message::SendServerCommand(name) => {
+                if let Some(console) = self.server_consoles.get_mut(&name) {
+                    let command = std::mem::take(&mut console.input);
+
+                    if let Err(error) = console.send(&command) {
+                        return self.update(Message::Error(error.to_string(), false));
+                    }
+                }
+            }
+            Message::EditServerProperties(name) => {
+                match ServerProperties::load(&self.instances.get_dir(&name)) {
+                    Ok(properties) => {
+                        self.server_properties = properties;
+                        self.page = Page::ServerProperties(name);
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                }
+            }
+            Message::SetServerProperty(key, value) => {
+                self.server_properties.set(key, &value);
+            }
+            Message::SaveServerProperties(name) => {
+                if let Err(error) = self.server_properties.save(&self.instances.get_dir(&name)) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                self.page = Page::Instance(name);
+            }
+            Message::EditWorld(name, world) => {
+                let world_dir = self.instances.get_dir(&name).join("saves").join(&world);
+
+                match lib::world::load(&world_dir) {
+                    Ok(level_dat) => {
+                        self.level_dat = Some(level_dat);
+                        self.page = Page::World(name, world);
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                }
+            }
+            Message::SetWorldName(name) => {
+                if let Some(level_dat) = &mut self.level_dat {
+                    level_dat.name = name;
+                }
+            }
+            Message::SetWorldGameMode(game_mode) => {
+                if let Some(level_dat) = &mut self.level_dat {
+                    level_dat.game_mode = game_mode;
+                }
+            }
+            Message::SetWorldDifficulty(difficulty) => {
+                if let Some(level_dat) = &mut self.level_dat {
+                    level_dat.difficulty = difficulty;
+                }
+            }
+            Message::SetWorldCheats(cheats) => {
+                if let Some(level_dat) = &mut self.level_dat {
+                    level_dat.cheats = cheats;
+                }
+            }
+            Message::SaveWorld(name, world) => {
+                // the game would overwrite the changes when it saves
+                if self.running.get(&name).is_some() {
+                    return self.update(Message::Error(
+                        format!("Close {name} before editing its worlds"),
+                        false,
+                    ));
+                }
+
+                let Some(level_dat) = &self.level_dat else {
+                    return Command::none();
+                };
+
+                let world_dir = self.instances.get_dir(&name).join("saves").join(&world);
+                if let Err(error) = lib::world::save(&world_dir, level_dat) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                self.page = Page::Instance(name);
+            }
+            Message::EditServerList(name) => {
+                match lib::server_list::load(&self.instances.get_dir(&name)) {
+                    Ok(list) => {
+                        self.server_list = ServerListEditor {
+                            list,
+                            ..Default::default()
+                        };
+                        self.page = Page::ServerList(name.clone());
+                        return self.update(Message::PingServers(name));
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                }
+            }
+            Message::PingServers(name) => {
+                self.server_list.status.clear();
+
+                // every server answers on its own, a slow one doesn't hold the others
+                let mut addresses = self
+                    .server_list
+                    .list
+                    .entries()
+                    .into_iter()
+                    .map(|entry| entry.address)
+                    .collect::<Vec<_>>();
+                addresses.sort();
+                addresses.dedup();
+
+                return Command::batch(addresses.into_iter().map(|address| {
+                    let name = name.clone();
+
+                    Command::perform(
+                        lib::server_list::ping(address.clone()).map_err(|e| format_error(&e)),
+                        move |status| Message::GotServerStatus(name, address, status),
+                    )
+                }));
+            }
+            Message::GotServerStatus(name, address, status) => {
+                // a ping can outlive the page it was sent from
+                if self.page == Page::ServerList(name) {
+                    self.server_list.status.insert(address, status);
+                }
+            }
+            Message::ServerNameChanged(name) => {
+                self.server_list.new_name = name;
+            }
+            Message::ServerAddressChanged(address) => {
+                self.server_list.new_address = address;
+            }
+            Message::AddServer(name) => {
+                // the game rewrites servers.dat when it closes
+                if self.running.get(&name).is_some() {
+                    return self.update(Message::Error(
+                        format!("Close {name} before editing its servers"),
+                        false,
+                    ));
+                }
+
+                let server_name = std::mem::take(&mut self.server_list.new_name);
+                let address = std::mem::take(&mut self.server_list.new_address);
+
+                if let Err(error) = self.server_list.list.add(server_name, address.clone()) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+                if let Err(error) =
+                    lib::server_list::save(&self.instances.get_dir(&name), &self.server_list.list)
+                {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                let address = address.trim().to_string();
+                return Command::perform(
+                    lib::server_list::ping(address.clone()).map_err(|e| format_error(&e)),
+                    move |status| Message::GotServerStatus(name, address, status),
+                );
+            }
+            Message::RemoveServer(name, index) => {
+                if self.running.get(&name).is_some() {
+                    return self.update(Message::Error(
+                        format!("Close {name} before editing its servers"),
+                        false,
+                    ));
+                }
+
+                self.server_list.list.remove(index);
+
+                if let Err(error) =
+                    lib::server_list::save(&self.instances.get_dir(&name), &self.server_list.list)
+                {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::SetInstanceAccount(name, account) => {
+                if let Err(error) = self.instances.set_account(&name, Some(&account.mc_id)) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::OpenInstanceFolder(name) => {
+                let path = self.instances.get_dir(&name);
+
+                if let Err(error) = open::that(path) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+            }
+            Message::OpenInstanceConfig(name) => {
+                let path = self.instances.get_config_path(&name);
+
+                if let Err(error) = open::that(path) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+            }
+            Message::DeleteInstance(name) => {
+                return self.notify(
+                    Severity::Warning,
+                    "Delete instance",
+                    format!("Are you sure you want to delete {name}?"),
+                    vec![("Delete".to_string(), Message::ConfirmDeleteInstance(name))],
+                );
+            }
+            Message::ConfirmDeleteInstance(name) => {
+                if let Err(error) = self.instances.trash(&name) {
+                    return self.update(Message::Error(format_error(&error), true));
+                }
+
+                self.page = Page::Instances;
+            }
+            Message::RestoreTrashed(trashed) => match self.instances.restore(&trashed) {
+                Ok(name) => self.page = Page::Instance(name),
+                Err(error) => return self.update(Message::Error(format_error(&error), false)),
+            },
+            Message::DeleteTrashed(trashed) => {
+                if let Err(error) = self.instances.delete_trashed(&trashed) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::VerifyInstance(name) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                self.page = Page::Status(format!("Verifying {name}..."));
+
+                // the loader's files are checked too
+                let verify = lib::vanilla_installer::verify_instance(
+                    info.clone(),
+                    self.settings.java_vendor,
+                );
+
+                return self.tasks.perform_or(
+                    format!("Verifying {name}"),
+                    verify.map_err(|e| format_error(&e)),
+                    {
+                        let name = name.clone();
+                        move |result| Message::VerifiedInstance(name, result)
+                    },
+                    Message::ChangePage(Page::Instance(name)),
+                );
+            }
+            Message::UpdateInstance(name) => {
+                let (Some(info), Some(latest_release)) =
+                    (self.instances.list.get(&name), &self.latest_release)
+                else {
+                    return Command::none();
+                };
+
+                let text = format!(
+                    "Update {name} from Minecraft {} to {}? Worlds opened in the new version can't go back, consider backing them up first.",
+                    info.minecraft, latest_release.id
+                );
+
+                return self.notify(
+                    Severity::Warning,
+                    "Update instance",
+                    text,
+                    vec![("Update".to_string(), Message::ConfirmUpdateInstance(name))],
+                );
+            }
+            Message::ConfirmUpdateInstance(name) => {
+                let (Some(info), Some(latest_release)) =
+                    (self.instances.list.get(&name), &self.latest_release)
+                else {
+                    return Command::none();
+                };
+
+                let kind = info.kind;
+                let minecraft = latest_release.id.clone();
+
+                self.page = Page::Status(format!("Verifying {name}..."));
+
+                let vendor = self.settings.java_vendor;
+                let verify = {
+                    let minecraft = minecraft.clone();
+                    async move {
+                        match kind {
+                            InstanceKind::Client => {
+                                lib::vanilla_installer::verify_version(minecraft, vendor).await
+                            }
+                            InstanceKind::Server => {
+                                lib::vanilla_installer::verify_server(minecraft, vendor).await
+                            }
+                        }
+                    }
+                };
+
+                return Command::perform(verify.map_err(|e| format_error(&e)), move |result| {
+                    Message::VerifiedUpdate(name.clone(), minecraft.clone(), result)
+                });
+            }
+            // the instance keeps its version until the new files are all there
+            Message::VerifiedUpdate(name, minecraft, Ok(queue)) => {
+                if queue.is_empty() {
+                    return self.update(Message::UpdatedInstance(name, minecraft));
+                }
+
+                let job = self.enqueue_download(format!("Updating {name}"), queue, None);
+                self.download
+                    .then(job, Message::UpdatedInstance(name, minecraft));
+                self.page = Page::Download;
+            }
+            Message::VerifiedUpdate(name, _, Err(error)) => {
+                self.page = Page::Instance(name);
+                return self.update(Message::Error(error, false));
+            }
+            Message::UpdatedInstance(name, minecraft) => {
+                if let Err(error) = self.instances.set_minecraft(&name, minecraft.clone()) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                if self.page == Page::Status(format!("Verifying {name}...")) {
+                    self.page = Page::Instance(name.clone());
+                }
+
+                let text = format!("{name} is now on Minecraft {minecraft}");
+                return self.notify(Severity::Success, "Update", text, Vec::new());
+            }
+            Message::VerifiedInstance(name, Ok(queue)) => {
+                if queue.is_empty() {
+                    let text = format!("All files of {name} are fine");
+                    self.page = Page::Instance(name);
+
+                    return self.notify(Severity::Success, "Verify files", text, Vec::new());
+                } else {
+                    self.enqueue_download(format!("Repairing {name}"), queue, None);
+                    self.page = Page::Download;
+                }
+            }
+            Message::VerifiedInstance(name, Err(error)) => {
+                self.page = Page::Instance(name);
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotJavaInstallations(installations) => {
+                self.java_installations = installations;
+            }
+            Message::GotGpus(gpus) => {
+                self.gpus = gpus;
+            }
+            Message::SetInstanceGpu(name, gpu) => {
+                if let Err(error) = self.instances.set_gpu(&name, gpu) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::GotLatestRelease(Ok(latest_release)) => {
+                self.latest_release = Some(latest_release);
+            }
+            Message::GotLatestRelease(Err(error)) => {
+                tracing::warn!("failed to get the latest release: {error}");
+            }
+            Message::SetInstanceJava(name, java) => {
+                let result = if java.managed {
+                    self.instances.set_java(&name, Some(java.version), None)
+                } else {
+                    self.instances.set_java(&name, None, Some(java.path))
+                };
+
+                if let Err(error) = result {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::ChooseJavaBinary(name) => {
+                let Some(path) = FileDialog::new()
+                    .set_title("Choose java binary")
+                    .pick_file()
+                else {
+                    return Command::none();
+                };
+
+                return Command::perform(
+                    lib::runtime_manager::system::check_java(path).map_err(|e| format_error(&e)),
+                    move |result| Message::CheckedJavaBinary(name.clone(), result),
+                );
+            }
+            Message::CheckedJavaBinary(name, Ok(java)) => {
+                if !self.java_installations.contains(&java) {
+                    self.java_installations.push(java.clone());
+                }
+
+                return self.update(Message::SetInstanceJava(name, java));
+            }
+            Message::CheckedJavaBinary(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ExportCredits(name) => {
+                let mods_dir = self.instances.get_dir(&name).join("mods");
+
+                return Command::perform(
+                    lib::modrinth::get_credits(mods_dir).map_err(|e| format_error(&e)),
+                    Message::GotCredits,
+                );
+            }
+            Message::GotCredits(Ok(credits)) => {
+                let path = FileDialog::new()
+                    .set_file_name("CREDITS.md")
+                    .add_filter("Markdown", &["md"])
+                    .save_file();
+
+                if let Some(path) = path {
+                    let markdown = lib::modrinth::credits_to_markdown(&credits);
+
+                    if let Err(error) = fs::write(path, markdown) {
+                        return self.update(Message::Error(error.to_string(), false));
+                    }
+                }
+            }
+            Message::GotCredits(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ExportClasspathGraph(name) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                let path = FileDialog::new()
+                    .set_file_name(format!("{name}.dot"))
+                    .add_filter("Graphviz", &["dot"])
+                    .add_filter("JSON", &["json"])
+                    .save_file();
+
+                if let Some(path) = path {
+                    let result =
+                        lib::classpath::ClasspathGraph::new(&name, info).and_then(|graph| {
+                            let content = if path.extension().is_some_and(|ext| ext == "json") {
+                                graph.to_json()?
+                            } else {
+                                graph.to_dot()
+                            };
+
+                            fs::write(path, content)?;
+                            Ok(())
+                        });
+
+                    if let Err(error) = result {
+                        return self.update(Message::Error(format_error(&error), false));
+                    }
+                }
+            }
+            Message::OpenInstanceExport(name) => {
+                self.page = Page::InstanceExport(name);
+            }
+            Message::ToggleExportPart(part) => {
+                if self.export_parts.contains(&part) {
+                    self.export_parts.retain(|p| *p != part);
+                } else {
+                    self.export_parts.push(part);
+                }
+            }
+            Message::ExportInstance(name) => {
+                let Some(info) = self.instances.list.get(&name).cloned() else {
+                    return Command::none();
+                };
+
+                let Some(path) = FileDialog::new()
+                    .set_file_name(format!("{name}.zip"))
+                    .add_filter("Zip", &["zip"])
+                    .save_file()
+                else {
+                    return Command::none();
+                };
+
+                self.page = Page::Instance(name.clone());
+
+                return self.tasks.perform(
+                    format!("Exporting {name}"),
+                    instance_export::export(
+                        name.clone(),
+                        info,
+                        self.instances.get_dir(&name),
+                        self.export_parts.clone(),
+                        path,
+                    )
+                    .map_err(|e| format_error(&e)),
+                    Message::ExportedInstance,
+                );
+            }
+            Message::ExportedInstance(Ok(())) => {
+                return self.notify(
+                    Severity::Info,
+                    "Instance exported",
+                    "The zip can be imported by dropping it on the launcher",
+                    Vec::new(),
+                );
+            }
+            Message::ExportedInstance(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GetVersions => {
+                return Command::perform(
+                    lib::vanilla_installer::get_versions().map_err(|e| format_error(&e)),
+                    Message::GotVersions,
+                );
+            }
+            Message::GotVersions(Ok(versions)) => {
+                self.vanilla_installer.versions = versions;
+            }
+            Message::GotVersions(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ChangeName(name) => {
+                self.vanilla_installer.name = name;
+            }
+            Message::SetInstallerJvmPreset(jvm_preset) => {
+                self.vanilla_installer.jvm_preset = Some(jvm_preset);
+            }
+            Message::SetInstallerServer(server) => {
+                self.vanilla_installer.server = server;
+            }
+            Message::SetInstallerFabric(fabric) => {
+                self.vanilla_installer.fabric = fabric;
+            }
+            Message::SetMemory(memory) => {
+                self.vanilla_installer.memory = memory;
+            }
+            Message::ToggleReleaseType(release_type) => {
+                let release_types = &mut self.vanilla_installer.release_types;
+
+                if release_types.contains(&release_type) {
+                    release_types.retain(|t| *t != release_type);
+                } else {
+                    release_types.push(release_type);
+                }
+
+                self.vanilla_installer.deselect_hidden();
+            }
+            Message::VersionFilterChanged(filter) => {
+                self.vanilla_installer.version_filter = filter;
+                self.vanilla_installer.deselect_hidden();
+            }
+            Message::SelectVersion(index) => {
+                self.vanilla_installer.selected_version = Some(index);
+                self.vanilla_installer.patch_notes = None;
+
+                let id = self.vanilla_installer.versions[index].id.clone();
+
+                if let Some(patch_notes) = self.patch_notes.get(&id) {
+                    self.vanilla_installer.patch_notes = patch_notes.clone();
+                    return Command::none();
+                }
+
+                return Command::perform(
+                    lib::vanilla_installer::get_patch_notes(id.clone())
+                        .map_err(|e| format_error(&e)),
+                    move |result| Message::GotPatchNotes(id.clone(), result),
+                );
+            }
+            Message::GotPatchNotes(id, Ok(patch_notes)) => {
+                self.patch_notes.insert(id.clone(), patch_notes.clone());
+
+                // another version could have been selected in the meantime
+                let selected = self
+                    .vanilla_installer
+                    .selected_version
+                    .map(|index| &self.vanilla_installer.versions[index].id);
+
+                if selected == Some(&id) {
+                    self.vanilla_installer.patch_notes = patch_notes;
+                }
+            }
+            Message::GotPatchNotes(_, Err(error)) => {
+                tracing::warn!("failed to get patch notes: {error}");
+            }
+            Message::CreateInstance => {
+                if self.vanilla_installer.fabric && !self.vanilla_installer.server {
+                    let version = self.vanilla_installer.selected_version.unwrap();
+                    let version = self.vanilla_installer.versions[version].id.clone();
+
+                    return Command::perform(
+                        lib::fabric::get_latest_loader(version).map_err(|e| format_error(&e)),
+                        Message::GotInstallerLoader,
+                    );
+                }
+
+                return self.create_instance(Loader::Vanilla, None);
+            }
+            Message::GotInstallerLoader(Ok(loader_version)) => {
+                return self.create_instance(Loader::Fabric, Some(loader_version));
+            }
+            Message::GotInstallerLoader(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::AddAccount => {
+                let client = Accounts::get_client().unwrap();
+                let details = Accounts::get_details(&client).unwrap();
+
+                self.login.url = details.verification_uri().to_string();
+                self.login.code = details.user_code().secret().to_string();
+                self.page = Page::AddingAccount;
+
+                return Command::perform(
+                    Accounts::get_account(client, details).map_err(|e| format_error(&e)),
+                    Message::LoggedIn,
+                );
+            }
+            Message::LoggedIn(Ok(account)) => {
+                self.login = Login::default();
+
+                if let Err(error) = self.add_account(account) {
+                    return self.update(Message::Error(format_error(&error), false));
+                } else {
+                    self.page = Page::Accounts;
+                    return self.fetch_next_head();
+                }
+            }
+            Message::LoggedIn(Err(error)) => {
+                self.login = Login::default();
+                self.page = Page::Accounts;
+
+                return self.update(Message::Error(error, false));
+            }
+            Message::OfflineAccountUsernameChanged(username) => {
+                self.offline_account_username = username;
+            }
+            Message::AddOfflineAccount => {
+                let account = Account::new_offline(self.offline_account_username.clone());
+
+                if let Err(error) = self.add_account(account) {
+                    return self.update(Message::Error(format_error(&error), false));
+                } else {
+                    self.page = Page::Accounts;
+                    return self.fetch_next_head();
+                }
+            }
+            Message::AuthlibServerChanged(server) => {
+                self.authlib_login.server = server;
+            }
+            Message::AuthlibUsernameChanged(username) => {
+                self.authlib_login.username = username;
+            }
+            Message::AuthlibPasswordChanged(password) => {
+                self.authlib_login.password = password;
+            }
+            Message::AddAuthlibAccount => {
+                let login = std::mem::take(&mut self.authlib_login);
+                self.page = Page::Status(format!("Logging in to {}...", login.server));
+
+                return Command::perform(
+                    lib::authlib_injector::login(login.server, login.username, login.password)
+                        .map_err(|e| format_error(&e)),
+                    Message::LoggedIn,
+                );
+            }
+            Message::AddElyByAccount => {
+                let login = match lib::accounts::ElyByLogin::start() {
+                    Ok(login) => login,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                if let Err(error) = open::that(&login.url) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+                self.page = Page::Status(lib::i18n::t("authlib-account-waiting"));
+
+                return Command::perform(
+                    login.finish().map_err(|e| format_error(&e)),
+                    Message::LoggedIn,
+                );
+            }
+            Message::SelectAccount(account) => {
+                if let Err(error) = self.accounts.set_active_account(account) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                return self.get_skin_preview();
+            }
+            Message::OpenLoginUrl => {
+                if let Err(error) = open::that(&self.login.url) {
+                    return self.update(Message::Error(error.to_string(), false));
+                } else {
+                    return clipboard::write(self.login.code.to_owned());
+                }
+            }
+            Message::RemoveAccount(account) => {
+                return self.notify(
+                    Severity::Warning,
+                    "Remove account",
+                    format!("Are you sure you want to remove {}?", account.mc_username),
+                    vec![("Remove".to_string(), Message::ConfirmRemoveAccount(account))],
+                );
+            }
+            Message::ConfirmRemoveAccount(account) => {
+                if let Err(error) = self.remove_account(&account.mc_id) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::SetCheckForUpdates(check_for_updates) => {
+                self.settings.check_for_updates = check_for_updates;
+            }
+            Message::SetTheme(theme) => {
+                self.settings.theme = theme;
+            }
+            Message::SetInstancesView(instances_view) => {
+                self.settings.instances_view = instances_view;
+
+                // toggled outside of the settings page, so it's saved right away
+                if let Err(error) = self.settings.save() {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::SetLanguage(language) => {
+                self.settings.language = language;
+                lib::i18n::set_language(language);
+            }
+            Message::SetJvmPreset(jvm_preset) => {
+                self.settings.jvm_preset = jvm_preset;
+            }
+            Message::SetGarbageCollector(garbage_collector) => {
+                self.settings.garbage_collector = garbage_collector;
+            }
+            Message::SetCrashReports(crash_reports) => {
+                self.settings.crash_reports = crash_reports;
+            }
+            Message::SetMinimizeOnLaunch(minimize_on_launch) => {
+                self.settings.minimize_on_launch = minimize_on_launch;
+            }
+            Message::ReportCrash(report) => {
+                match lib::feedback::get_issue_url(
+                    env!("CARGO_PKG_REPOSITORY"),
+                    "Crash report",
+                    &report.issue_body(),
+                    None,
+                ) {
+                    Ok(url) => return self.update(Message::OpenURL(url)),
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                }
+            }
+            Message::OpenCrashReport(path) => {
+                if let Err(error) = open::that(path) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+            }
+            Message::SetLogLevel(log_level) => {
+                self.settings.log_level = log_level;
+            }
+            Message::OpenLogsFolder => {
+                if let Err(error) = open::that(&*lib::paths::LOGS_DIR) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+            }
+            Message::SetJavaVendor(vendor) => {
+                self.settings.java_vendor = vendor;
+            }
+            Message::SetDownloadThreads(threads) => {
+                if let Ok(threads) = threads.parse() {
+                    self.settings.download_threads = threads;
+                }
+            }
+            Message::SaveSettings => {
+                if let Err(error) = self.settings.save() {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::SetDownloadRetries(retries) => {
+                if let Ok(retries) = retries.parse() {
+                    self.settings.download_retries = retries;
+                }
+            }
+            Message::SetTrashRetention(days) => {
+                if let Ok(days) = days.parse() {
+                    self.settings.trash_retention_days = days;
+                }
+            }
+            Message::SetUseMirrors(use_mirrors) => {
+                self.settings.use_mirrors = use_mirrors;
+            }
+            Message::SetDefault(setting, value) => {
+                // half typed values are ignored, like the other numeric settings
+                let _ = setting.set_default(&mut self.settings, value);
+            }
+            Message::SetDownloadSpeedLimit(limit) => {
+                if let Ok(limit) = limit.parse() {
+                    self.settings.download_speed_limit = limit;
+                }
+            }
+            Message::PredownloadVersionsChanged(versions) => {
+                self.predownload_versions = versions;
+            }
+            Message::Predownload => {
+                let versions = self
+                    .predownload_versions
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+
+                return self.tasks.perform(
+                    "Resolving the versions to pre-download",
+                    lib::vanilla_installer::predownload_versions(
+                        versions,
+                        self.settings.java_vendor,
+                    )
+                    .map_err(|e| format_error(&e)),
+                    Message::GotDownloadQueue,
+                );
+            }
+            Message::GotDownloadQueue(Ok(queue)) => {
+                self.enqueue_download("Pre-downloaded versions".to_string(), queue, None);
+                self.page = Page::Download;
+            }
+            Message::GotDownloadQueue(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ChooseInstancesDir => {
+                if !self.running_instances.is_empty() {
+                    return self.update(Message::Error(
+                        "Close the running games before moving the instances".to_string(),
+                        false,
+                    ));
+                }
+
+                // the downloads write into the instances
+                if self.download.jobs.iter().any(|job| !job.is_done()) {
+                    return self.update(Message::Error(
+                        "Wait for the downloads to finish before moving the instances".to_string(),
+                        false,
+                    ));
+                }
+
+                let Some(dir) = FileDialog::new()
+                    .set_title("Choose the new instances folder")
+                    .pick_folder()
+                else {
+                    return Command::none();
+                };
+
+                return self.notify(
+                    Severity::Warning,
+                    "Move instances",
+                    format!("All instances will be moved to {}", dir.display()),
+                    vec![("Move".to_string(), Message::ConfirmMoveInstances(dir))],
+                );
+            }
+            Message::ConfirmMoveInstances(dir) => {
+                return self.tasks.perform_uncancellable(
+                    "Moving the instances",
+                    lib::store::move_instances(self.settings.instances_dir(), dir.clone())
+                        .map_err(|e| format_error(&e)),
+                    move |result| Message::MovedInstances(dir.clone(), result),
+                );
+            }
+            Message::MovedInstances(dir, Ok(())) => {
+                self.settings.instances_dir = Some(dir.clone());
+                if let Err(error) = self.settings.save() {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                match Instances::load(dir) {
+                    Ok(instances) => self.instances = instances,
+                    Err(error) => return self.update(Message::Error(format_error(&error), true)),
+                }
+            }
+            Message::MovedInstances(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ExportConfig => {
+                let Some(path) = FileDialog::new()
+                    .set_file_name("crab-launcher-config.zip")
+                    .add_filter("Zip", &["zip"])
+                    .save_file()
+                else {
+                    return Command::none();
+                };
+
+                if let Err(error) = lib::config_transfer::export(
+                    &path,
+                    &self.settings,
+                    &self.accounts,
+                    &self.instances,
+                ) {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+            }
+            Message::ImportConfig => {
+                let Some(path) = FileDialog::new().add_filter("Zip", &["zip"]).pick_file() else {
+                    return Command::none();
+                };
+
+                let archive = match lib::config_transfer::read(&path) {
+                    Ok(archive) => archive,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                let conflicts = archive
+                    .instances
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .filter(|name| self.instances.list.contains_key(*name))
+                    .collect::<Vec<_>>();
+
+                if conflicts.is_empty() {
+                    return self.update(Message::ImportConfigArchive(path, false));
+                }
+
+                return self.notify(
+                    Severity::Warning,
+                    "Instances already exist",
+                    format!(
+                        "These instances already exist: {}. Replace their configuration or keep both?",
+                        conflicts.join(", ")
+                    ),
+                    vec![
+                        (
+                            "Replace".to_string(),
+                            Message::ImportConfigArchive(path.clone(), true),
+                        ),
+                        (
+                            "Keep both".to_string(),
+                            Message::ImportConfigArchive(path, false),
+                        ),
+                    ],
+                );
+            }
+            Message::ImportConfigArchive(path, replace) => {
+                let mut archive = match lib::config_transfer::read(&path) {
+                    Ok(archive) => archive,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                archive.settings.instances_dir = self.settings.instances_dir.clone();
+                self.settings = archive.settings;
+                lib::i18n::set_language(self.settings.language);
+                if let Err(error) = self.settings.save() {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                let sign_in = match lib::config_transfer::import_accounts(
+                    &mut self.accounts,
+                    archive.accounts,
+                ) {
+                    Ok(sign_in) => sign_in,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                let names = match lib::config_transfer::import_instances(
+                    &mut self.instances,
+                    archive.instances,
+                    replace,
+                ) {
+                    Ok(names) => names,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                // the game files aren't part of the archive
+                let mut commands = names
+                    .into_iter()
+                    .map(|name| {
+                        let info = self.instances.list[&name].clone();
+
+                        self.tasks.perform(
+                            format!("Preparing {name}"),
+                            lib::vanilla_installer::download_instance(
+                                info,
+                                self.settings.java_vendor,
+                            )
+                            .map_err(|e| format_error(&e)),
+                            move |result| {
+                                Message::GotInstanceQueue(format!("Importing {name}"), name, result)
+                            },
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                self.page = Page::Download;
+
+                if !sign_in.is_empty() {
+                    commands.push(self.notify(
+                        Severity::Info,
+                        "Sign in again",
+                        format!(
+                            "Online accounts can't be imported, sign in to them again: {}",
+                            sign_in.join(", ")
+                        ),
+                        vec![("Accounts".to_string(), Message::ChangePage(Page::Accounts))],
+                    ));
+                }
+
+                return Command::batch(commands);
+            }
+            Message::GotInstanceQueue(task, name, Ok(queue)) => {
+                self.enqueue_download(task, queue, Some(name));
+            }
+            Message::GotInstanceQueue(_, _, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::ImportedPack(name, Ok(pack)) => {
+                self.enqueue_download(format!("Importing {name}"), pack.queue, Some(name.clone()));
+
+                if !pack.skipped.is_empty() {
+                    return self.notify(
+                        Severity::Warning,
+                        "Missing mods",
+                        format!(
+                            "These mods of {name} couldn't be downloaded, add them by hand: {}",
+                            pack.skipped.join(", ")
+                        ),
+                        Vec::new(),
+                    );
+                }
+            }
+            Message::ImportedPack(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::CleanUnusedFiles(areas) => {
+                return self.notify(
+                    Severity::Warning,
+                    "Clean unused files",
+                    "Files no instance uses anymore will be deleted, including pre-downloaded versions",
+                    vec![("Clean".to_string(), Message::ConfirmCleanUnusedFiles(areas))],
+                );
+            }
+            Message::ConfirmCleanUnusedFiles(areas) => {
+                let instances = self.instances.list.values().cloned().collect();
+
+                return self.tasks.perform_uncancellable(
+                    "Cleaning unused files",
+                    lib::store::clean_unused(instances, areas).map_err(|e| format_error(&e)),
+                    Message::CleanedUnusedFiles,
+                );
+            }
+            Message::CleanedUnusedFiles(Ok(report)) => {
+                let notification = self.notify(
+                    Severity::Success,
+                    "Clean unused files",
+                    format!(
+                        "Removed {} files, {} reclaimed",
+                        report.files,
+                        format_bytes(report.bytes as f64)
+                    ),
+                    Vec::new(),
+                );
+
+                if self.page == Page::Storage {
+                    return Command::batch([notification, self.get_disk_usage()]);
+                }
+
+                return notification;
+            }
+            Message::CleanedUnusedFiles(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotDiskUsage(Ok(disk_usage)) => {
+                self.disk_usage = Some(disk_usage);
+            }
+            Message::GotDiskUsage(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::OpenStorageArea(area) => {
+                if let Err(error) = open::that(area.get_dir()) {
+                    return self.update(Message::Error(error.to_string(), false));
+                }
+            }
+            Message::SelectJsonDocument(document) => {
+                let path = document.path.clone();
+                self.json_viewer.selected = Some(document);
+                self.json_viewer.content = String::new();
+
+                return Command::perform(
+                    lib::cache::read_pretty(path).map_err(|e| format_error(&e)),
+                    Message::GotJsonDocument,
+                );
+            }
+            Message::GotJsonDocument(Ok(content)) => {
+                self.json_viewer.content = content;
+            }
+            Message::GotJsonDocument(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::JsonSearchChanged(search) => {
+                self.json_viewer.search = search;
+            }
+            Message::FeedbackTitleChanged(title) => {
+                self.feedback.title = title;
+            }
+            Message::FeedbackDescriptionChanged(description) => {
+                self.feedback.description = description;
+            }
+            Message::SetAttachFeedbackSummary(attach_summary) => {
+                self.feedback.attach_summary = attach_summary;
+            }
+            Message::SubmitFeedback => {
+                let summary = self
+                    .feedback
+                    .attach_summary
+                    .then(|| self.feedback_summary());
+
+                match lib::feedback::get_issue_url(
+                    env!("CARGO_PKG_REPOSITORY"),
+                    &self.feedback.title,
+                    &self.feedback.description,
+                    summary.as_deref(),
+                ) {
+                    Ok(url) => {
+                        self.feedback = Feedback::default();
+                        return self.update(Message::OpenURL(url));
+                    }
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                }
+            }
+            Message::GetModpacks => {
+                return self.search_modpacks(0);
+            }
+            Message::LoadMoreModpacks => {
+                return self.search_modpacks(self.modrinth_modpacks.projects.len());
+            }
+            Message::ModpackQueryChanged(query) => {
+                self.modrinth_modpacks.query = query;
+            }
+            Message::ModpackFiltersChanged(filters) => {
+                self.modrinth_modpacks.filters = filters;
+            }
+            Message::SetModpackFilters(filters) => {
+                self.modrinth_modpacks.filters = filters;
+
+                return self.update(Message::GetModpacks);
+            }
+            Message::GotModpacks(generation, _)
+                if generation != self.modrinth_modpacks.generation => {}
+            Message::GotModpacks(_, Ok(projects)) => {
+                let total_hits = projects.total_hits;
+                if projects.add_to(&mut self.modrinth_modpacks.projects) {
+                    self.modrinth_modpacks.total_hits = total_hits;
+                }
+            }
+            Message::GotModpacks(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotSuggestions(Ok(suggestions)) => {
+                if !suggestions.is_empty() {
+                    self.mod_suggestions.suggestions = suggestions;
+                    self.page = Page::ModSuggestions;
+                }
+            }
+            Message::GotSuggestions(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::InstallSuggestion(suggestion) => {
+                let dir = self.instances.get_dir(&self.mod_suggestions.instance);
+                let item = match lib::modrinth::install_mod(&suggestion.version, &dir) {
+                    Ok(item) => item,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+
+                self.mod_suggestions
+                    .suggestions
+                    .retain(|s| s.version != suggestion.version);
+
+                self.enqueue_download(
+                    format!("{} for {}", suggestion.title, self.mod_suggestions.instance),
+                    DownloadQueue::new(vec![item]),
+                    None,
+                );
+            }
+            Message::InstallAllSuggestions => {
+                let name = self.mod_suggestions.instance.clone();
+                let dir = self.instances.get_dir(&name);
+
+                let items = self
+                    .mod_suggestions
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| lib::modrinth::install_mod(&suggestion.version, &dir))
+                    .collect::<Result<Vec<_>, _>>();
+
+                let items = match items {
+                    Ok(items) => items,
+                    Err(error) => return self.update(Message::Error(format_error(&error), false)),
+                };
+                self.mod_suggestions.suggestions.clear();
+
+                self.enqueue_download(
+                    format!("{} mods for {name}", items.len()),
+                    DownloadQueue::new(items),
+                    None,
+                );
+                self.page = Page::Download;
+            }
+            Message::OptimizeInstance(name) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                let minecraft_version = info.minecraft.clone();
+
+                if info.loader != Loader::Vanilla {
+                    return Command::perform(
+                        lib::modrinth::get_performance_mods(minecraft_version, info.loader)
+                            .map_err(|e| format_error(&e)),
+                        move |result| Message::GotPerformanceMods(name.clone(), result),
+                    );
+                }
+
+                // vanilla instances are never modified in place
+                return self.notify(
+                    Severity::Info,
+                    "Optimize instance",
+                    format!(
+                        "Performance mods need Fabric, so a copy of {name} called \"{name} (Fabric)\" will be created with Fabric installed. {name} itself won't be changed."
+                    ),
+                    vec![(
+                        "Create copy".to_string(),
+                        Message::ConfirmOptimizeInstance(name),
+                    )],
+                );
+            }
+            Message::ConfirmOptimizeInstance(name) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                return Command::perform(
+                    lib::fabric::get_latest_loader(info.minecraft.clone())
+                        .map_err(|e| format_error(&e)),
+                    move |result| Message::GotFabricLoader(name.clone(), result),
+                );
+            }
+            Message::GotFabricLoader(name, Ok(loader_version)) => {
+                let Some(info) = self.instances.list.get(&name) else {
+                    return Command::none();
+                };
+
+                let minecraft_version = info.minecraft.clone();
+                let new_name = format!("{name} (Fabric)");
+
+                let result = self
+                    .instances
+                    .duplicate(&name, new_name.clone())
+                    .and_then(|_| {
+                        self.instances.set_loader(
+                            &new_name,
+                            Loader::Fabric,
+                            Some(loader_version.clone()),
+                        )
+                    });
+
+                if let Err(error) = result {
+                    return self.update(Message::Error(format_error(&error), false));
+                }
+
+                let install = {
+                    let minecraft_version = minecraft_version.clone();
+                    let loader_version = loader_version.clone();
+
+                    async move {
+                        lib::fabric::install(&minecraft_version, &loader_version)
+                            .map(DownloadQueue::new)
+                    }
+                };
+
+                return Command::batch([
+                    self.tasks.perform(
+                        format!("Preparing {new_name}"),
+                        install.map_err(|e| format_error(&e)),
+                        {
+                            let new_name = new_name.clone();
+                            let task = format!("Fabric {loader_version} for {new_name}");
+                            move |result| {
+                                Message::GotInstanceQueue(task.clone(), new_name.clone(), result)
+                            }
+                        },
+                    ),
+                    Command::perform(
+                        lib::modrinth::get_performance_mods(minecraft_version, Loader::Fabric)
+                            .map_err(|e| format_error(&e)),
+                        move |result| Message::GotPerformanceMods(new_name.clone(), result),
+                    ),
+                ]);
+            }
+            Message::GotFabricLoader(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotPerformanceMods(name, Ok(suggestions)) => {
+                self.mod_suggestions = ModSuggestions {
+                    description: format!("These mods make {name} faster without changing gameplay. They are only added to its mods folder, delete them from there to undo."),
+                    instance: name,
+                    suggestions,
+                };
+                self.page = Page::ModSuggestions;
+            }
+            Message::GotPerformanceMods(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::OpenModBrowser(name) => {
+                self.mod_browser = ModBrowser {
+                    instance: name,
+                    // answers meant for the previous browser are still dropped
+                    generation: self.mod_browser.generation,
+                    ..Default::default()
+                };
+                self.page = Page::ModBrowser;
+
+                return self.update(Message::BrowseMods);
+            }
+            Message::SelectModList(list) => {
+                self.mod_browser.list = list;
+                list.apply(&mut self.mod_browser.filters);
+
+                return self.update(Message::BrowseMods);
+            }
+            Message::ModQueryChanged(query) => {
+                self.mod_browser.query = query;
+            }
+            Message::ModFiltersChanged(filters) => {
+                self.mod_browser.filters = filters;
+            }
+            Message::SetModFilters(filters) => {
+                self.mod_browser.filters = filters;
+
+                return self.update(Message::BrowseMods);
+            }
+            Message::BrowseMods => {
+                return self.browse_mods(0);
+            }
+            Message::LoadMoreMods => {
+                return self.browse_mods(self.mod_browser.projects.len());
+            }
+            Message::GotMods(generation, _) if generation != self.mod_browser.generation => {}
+            Message::GotMods(_, Ok(projects)) => {
+                let (offset, total_hits) = (projects.offset, projects.total_hits);
+                if !projects.add_to(&mut self.mod_browser.projects) {
+                    return Command::none();
+                }
+                self.mod_browser.total_hits = total_hits;
+
+                // covers are fetched in the background, the gallery is preferred
+                let commands = self.mod_browser.projects[offset..]
+                    .iter()
+                    .filter(|project| !self.mod_browser.images.contains_key(&project.project_id))
+                    .filter_map(|project| {
+                        let url = project
+                            .featured_gallery
+                            .clone()
+                            .or_else(|| project.icon_url.clone())
+                            .filter(|url| !url.is_empty())?;
+                        let id = project.project_id.clone();
+
+                        Some(Command::perform(
+                            lib::modrinth::get_image(url).map_err(|e| format_error(&e)),
+                            move |result| Message::GotModImage(id.clone(), result),
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+
+                return Command::batch(commands);
+            }
+            Message::GotMods(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotModImage(id, Ok(bytes)) => {
+                self.mod_browser
+                    .images
+                    .insert(id, image::Handle::from_memory(bytes));
+            }
+            Message::GotModImage(_, Err(error)) => {
+                // not worth bothering the user
+                tracing::warn!("failed to fetch mod image: {error}");
+            }
+            Message::InstallMod(project) => {
+                let Some(info) = self.instances.list.get(&self.mod_browser.instance) else {
+                    return Command::none();
+                };
+
+                let name = self.mod_browser.instance.clone();
+                let title = project.title.clone();
+
+                return Command::perform(
+                    lib::modrinth::get_mod_version(
+                        project.project_id,
+                        info.minecraft.clone(),
+                        info.loader,
+                    )
+                    .map_err(|e| format_error(&e)),
+                    move |result| Message::GotModVersion(name.clone(), title.clone(), result),
+                );
+            }
+            Message::OpenModrinthProject(project) => {
+                let id = project.project_id.clone();
+
+                self.modrinth_project = Some(ModrinthProject {
+                    project,
+                    back: self.page.clone(),
+                    details: None,
+                    description: Vec::new(),
+                    images: HashMap::new(),
+                    lightbox: None,
+                });
+                self.page = Page::ModrinthProject;
+
+                return Command::perform(
+                    lib::modrinth::get_project(id.clone()).map_err(|e| format_error(&e)),
+                    move |result| Message::GotModrinthProject(id.clone(), result),
+                );
+            }
+            Message::GotModrinthProject(id, Ok(details)) => {
+                let Some(modrinth_project) = &mut self.modrinth_project else {
+                    return Command::none();
+                };
+                if modrinth_project.project.project_id != id {
+                    return Command::none();
+                }
+
+                // screenshots load in the background, the page is usable meanwhile
+                let commands = details
+                    .gallery
+                    .iter()
+                    .map(|image| {
+                        let id = id.clone();
+                        let url = image.url.clone();
+
+                        Command::perform(
+                            lib::modrinth::get_image(url.clone()).map_err(|e| format_error(&e)),
+                            move |result| Message::GotGalleryImage(id, url, result),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                modrinth_project.description = lib::markdown::parse(&details.body);
+                modrinth_project.details = Some(details);
+
+                return Command::batch(commands);
+            }
+            Message::GotGalleryImage(id, url, result) => {
+                // another project could have been opened in the meantime
+                let Some(modrinth_project) = &mut self.modrinth_project else {
+                    return Command::none();
+                };
+                if modrinth_project.project.project_id != id {
+                    return Command::none();
+                }
+
+                if let Err(error) = &result {
+                    tracing::warn!("failed to fetch gallery image: {error}");
+                }
+
+                modrinth_project
+                    .images
+                    .insert(url, result.map(image::Handle::from_memory));
+            }
+            Message::ShowGalleryImage(index) => {
+                if let Some(modrinth_project) = &mut self.modrinth_project {
+                    modrinth_project.lightbox = index;
+                }
+            }
+            Message::GotModrinthProject(_, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::InstallModpack(id) => {
+                let title = self
+                    .modrinth_project
+                    .as_ref()
+                    .map(|modrinth_project| modrinth_project.project.title.clone())
+                    .unwrap_or(id.clone());
+
+                return self.tasks.perform(
+                    format!("Downloading {title}"),
+                    lib::modrinth::download_modpack(id).map_err(|e| format_error(&e)),
+                    Message::DownloadedModpack,
+                );
+            }
+            Message::DownloadedModpack(Ok(path)) => {
+                let command = self.update(Message::FileDropped(path.clone()));
+
+                match &mut self.pack_import {
+                    Some(pack_import) if pack_import.path == path => pack_import.temporary = true,
+                    // it couldn't be read, there's nothing to import
+                    _ => {
+                        let _ = fs::remove_file(&path);
+                    }
+                }
+
+                return command;
+            }
+            Message::DownloadedModpack(Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::GotModVersion(name, title, Ok(Some(version))) => {
+                let item =
+                    match lib::modrinth::install_mod(&version, &self.instances.get_dir(&name)) {
+                        Ok(item) => item,
+                        Err(error) => {
+                            return self.update(Message::Error(format_error(&error), false))
+                        }
+                    };
+
+                self.enqueue_download(
+                    format!("{title} for {name}"),
+                    DownloadQueue::new(vec![item]),
+                    None,
+                );
+            }
+            Message::GotModVersion(_, title, Ok(None)) => {
+                return self.update(Message::Error(
+                    format!("No compatible version of {title} found"),
+                    false,
+                ));
+            }
+            Message::GotModVersion(_, _, Err(error)) => {
+                return self.update(Message::Error(error, false));
+            }
+            Message::DownloadProgressed(id, progress) => {
+                self.tasks.update_download(id, &progress);
+                if let Some(message) = self.download.update(id, progress) {
+                    return self.update(message);
+                }
+            }
+            Message::PauseDownload(id) => {
+                self.download.pause(id);
+            }
+            Message::ResumeDownload(id) => {
+                self.download.resume(id);
+            }
+            Message::CancelDownload(id) => {
+                self.tasks.remove_download(id);
+
+                // don't leave a half-created instance behind
+                if let Some(name) = self.download.cancel(id).and_then(|job| job.instance) {
+                    if let Err(error) = self.instances.delete(&name) {
+                        return self.update(Message::Error(format_error(&error), false));
+                    }
+                }
+            }
+            Message::ClearFinishedDownloads => {
+                self.download.clear_finished();
+            }
+            Message::PauseAllDownloads => {
+                self.download.pause_all();
+            }
+            Message::ResumeAllDownloads => {
+                self.download.resume_all();
+            }
+            Message::TaskFinished(id, message) => {
+                self.tasks.finish(id);
+
+                if let Some(message) = message {
+                    return self.update(*message);
+                }
+            }
+            Message::CancelTask(id) => {
+                if let Some(message) = self.tasks.cancel(id) {
+                    return self.update(message);
+                }
+            }
+        }
+
+        Command::none()
+    }
+
+    pub fn feedback_summary(&self) -> String {
+        lib::feedback::get_summary(env!("CARGO_PKG_VERSION"), &self.instances)
+    }
+
+    fn add_account(&mut self, account: Account) -> anyhow::Result<()> {
+        // the account was removed before, give it its instances back
+        if let Some(removed) = self.accounts.take_removed(&account.mc_id)? {
+            for name in removed.instances {
+                if self.instances.list.contains_key(&name) {
+                    self.instances.set_account(&name, Some(&account.mc_id))?;
+                }
+            }
+        }
+
+        self.head_queue.push(account.clone());
+        self.accounts.add_account(account)
+    }
+
+    // dropped mods and install links go into the instance that's open
+    fn open_instance(&self) -> Option<String> {
+        match &self.page {
+            Page::Instance(name) => Some(name.clone()),
+            Page::ModBrowser => Some(self.mod_browser.instance.clone()),
+            _ => None,
+        }
+    }
+
+    fn launch(&mut self, name: String, mut options: LaunchOptions) -> Command<Message> {
+        options.offline = self.offline;
+
+        // zipping the worlds takes a while, so it's done before the game starts instead of in it
+        if let Some(info) = self.instances.list.get(&name) {
+            if info.kind == InstanceKind::Client && lib::backups::is_scheduled(&name, info) {
+                return self.tasks.perform(
+                    format!("Backing up {name}"),
+                    lib::backups::run_scheduled(
+                        name.clone(),
+                        self.instances.get_dir(&name),
+                        info.clone(),
+                    )
+                    .map_err(|e| format_error(&e)),
+                    move |result| {
+                        Message::BackedUpBeforeLaunch(name.clone(), options.clone(), result)
+                    },
+                );
+            }
+        }
+
+        self.run_pre_launch_hook(name, options)
+    }
+
+    fn run_pre_launch_hook(&mut self, name: String, options: LaunchOptions) -> Command<Message> {
+        let Some(hook) = self.instances.get_pre_launch_hook(&name, &self.settings) else {
+            return self.start_game(name, options);
+        };
+
+        self.tasks.perform(
+            format!("Running the pre-launch hook of {name}"),
+            lib::hooks::run_pre_launch(hook, self.instances.get_dir(&name), name.clone())
+                .map_err(|e| format_error(&e)),
+            move |result| Message::RanPreLaunchHook(name.clone(), options.clone(), result),
+        )
+    }
+
+    fn start_game(&mut self, name: String, options: LaunchOptions) -> Command<Message> {
+        if self
+            .instances
+            .list
+            .get(&name)
+            .is_some_and(|info| info.kind == InstanceKind::Server)
+        {
+            return self.start_server(name);
+        }
+
+        // prefer the account this instance was last launched with
+        let account = self
+            .instances
+            .list
+            .get(&name)
+            .and_then(|info| info.account.as_ref())
+            .and_then(|id| self.accounts.find(id))
+            .or(self.accounts.active.as_ref())
+            .cloned();
+
+        // the demo doesn't check the account
+        let account = match account {
+            Some(account) => account,
+            None if options.demo => Account::new_offline("Player".to_string()),
+            None => {
+                return self.update(Message::Error("No account selected".to_string(), false));
+            }
+        };
+
+        let child = match self.instances.launch(
+            &name,
+            &account,
+            &self.settings,
+            &options,
+            &mut self.running,
+        ) {
+            Ok(child) => child,
+            Err(error) => return self.update(Message::Error(format_error(&error), true)),
+        };
+
+        let pid = child.id();
+        self.running_instances.insert(name.clone(), pid);
+
+        let wait = Command::perform(wait_for_exit(child), move |result| {
+            Message::GameExited(name.clone(), pid, result)
+        });
+
+        if self.settings.minimize_on_launch {
+            Command::batch([wait, window::minimize(true)])
+        } else {
+            wait
+        }
+    }
+
+    // once no game is left running
+    fn restore_window(&self) -> Command<Message> {
+        if !self.settings.minimize_on_launch || !self.running_instances.is_empty() {
+            return Command::none();
+        }
+
+        Command::batch([window::minimize(false), window::gain_focus()])
+    }
+
+    fn start_server(&mut self, name: String) -> Command<Message> {
+        let dir = self.instances.get_dir(&name);
+
+        if !lib::server::eula_accepted(&dir) {
+            return self.notify(
+                Severity::Warning,
+                "Minecraft EULA",
+                "Running a server requires accepting the Minecraft EULA",
+                vec![
+                    (
+                        "Read".to_string(),
+                        Message::OpenURL(lib::server::EULA_URL.to_string()),
+                    ),
+                    ("Accept".to_string(), Message::AcceptEula(name)),
+                ],
+            );
+        }
+
+        let mut child = match self
+            .instances
+            .start_server(&name, &self.settings, &mut self.running)
+        {
+            Ok(child) => child,
+            Err(error) => return self.update(Message::Error(format_error(&error), false)),
+        };
+
+        let pid = child.id();
+        self.running_instances.insert(name.clone(), pid);
+        self.server_consoles
+            .insert(name.clone(), ServerConsole::new(&mut child));
+
+        Command::perform(wait_for_exit(child), move |result| {
+            Message::GameExited(name.clone(), pid, result)
+        })
+    }
+
+    fn game_exited(&mut self, name: String, pid: u32) -> Command<Message> {
+        self.running_instances.remove(&name);
+
+        // the output is kept to see why it stopped
+        if let Some(console) = self.server_consoles.get_mut(&name) {
+            console.stdin = None;
+        }
+
+        if let Err(error) = self.running.remove(pid) {
+            return self.update(Message::Error(format_error(&error), false));
+        }
+
+        if let Err(error) = self.instances.add_playtime(&name) {
+            return self.update(Message::Error(format_error(&error), false));
+        }
+
+        if let Err(error) = self.instances.run_post_exit_hook(&name, &self.settings) {
+            return self.update(Message::Error(format_error(&error), false));
+        }
+
+        if self
+            .instances
+            .list
+            .get(&name)
+            .is_some_and(|info| info.profiling)
+        {
+            let dir = self.instances.get_dir(&name);
+
+            if let Some(summary) = lib::diagnostics::get_latest_summary(&dir) {
+                self.gc_summaries.insert(name.clone(), summary);
+            }
+        }
+
+        // the game has exited, decide what to do with the experiment
+        // it has to be answered, dismissing it would leave the experiment running
+        if lib::experiments::is_active(&self.instances.get_dir(&name)) {
+            self.notifications.push_required(
+                Severity::Info,
+                "Experiment",
+                format!(
+                    "Do you want to keep the changes made to {name}? Rolling back restores its configs and mods as they were when the experiment started."
+                ),
+                vec![
+                    (
+                        "Keep changes".to_string(),
+                        Message::EndExperiment(name.clone(), true),
+                    ),
+                    ("Roll back".to_string(), Message::EndExperiment(name, false)),
+                ],
+            );
+        }
+
+        Command::none()
+    }
+
+    fn notify(
+        &mut self,
+        severity: Severity,
+        title: impl Into<String>,
+        text: impl Into<String>,
+        actions: Vec<(String, Message)>,
+    ) -> Command<Message> {
+        let notification = self.notifications.push(severity, title, text, actions);
+
+        if !notification.is_transient() {
+            return Command::none();
+        }
+
+        let id = notification.id;
+        Command::perform(sleep(NOTIFICATION_TIMEOUT), move |_| {
+            Message::DismissNotification(id)
+        })
+    }
+
+    // returns the id of the job
+    fn enqueue_download(
+        &mut self,
+        name: String,
+        queue: DownloadQueue,
+        instance: Option<String>,
+    ) -> usize {
+        let job = self.download.enqueue(name.clone(), queue, instance);
+        self.tasks.add_download(name, job);
+
+        job
+    }
+
+    fn fetch_next_head(&mut self) -> Command<Message> {
+        match self.head_queue.next() {
+            Some(account) => Command::perform(
+                lib::accounts::get_head(account, 0).map_err(|e| format_error(&e)),
+                Message::GotAccountHead,
+            ),
+            None => Command::none(),
+        }
+    }
+
+    // skins change rarely, the preview is fetched again only for another account
+    fn get_skin_preview(&self) -> Command<Message> {
+        let Some(account) = &self.accounts.active else {
+            return Command::none();
+        };
+        if self
+            .skin_preview
+            .as_ref()
+            .is_some_and(|preview| preview.account_id == account.mc_id)
+        {
+            return Command::none();
+        }
+
+        Command::perform(
+            lib::skin::get_preview(account.clone()).map_err(|e| format_error(&e)),
+            Message::GotSkinPreview,
+        )
+    }
+
+    fn remove_account(&mut self, id: &str) -> anyhow::Result<()> {
+        let names = self
+            .instances
+            .list
+            .iter()
+            .filter(|(_, info)| info.account.as_deref() == Some(id))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+
+        for name in &names {
+            self.instances.set_account(name, None)?;
+        }
+
+        self.accounts.remove_account(id, names)
+    }
+
+    fn create_instance(
+        &mut self,
+        loader: Loader,
+        loader_version: Option<String>,
+    ) -> Command<Message> {
+        let name = self.vanilla_installer.name.clone();
+        let version = self.vanilla_installer.selected_version.unwrap();
+        let version = self.vanilla_installer.versions[version].id.clone();
+        let jvm_preset = self.vanilla_installer.jvm_preset;
+        let memory =
+            Some(self.vanilla_installer.memory.clone()).filter(|memory| !memory.is_empty());
+
+        let mut info = Instance::new(version, loader, loader_version, jvm_preset, memory);
+        if self.vanilla_installer.server {
+            info.kind = InstanceKind::Server;
+        }
+        let minecraft_version = info.minecraft.clone();
+
+        if let Err(error) = self.instances.create(name.clone(), info.clone()) {
+            return self.update(Message::Error(format_error(&error), true));
+        }
+
+        self.vanilla_installer = VanillaInstaller::default();
+        self.page = Page::Download;
+
+        let download = self.tasks.perform(
+            format!("Preparing {name}"),
+            lib::vanilla_installer::download_instance(info, self.settings.java_vendor)
+                .map_err(|e| format_error(&e)),
+            {
+                let name = name.clone();
+                let task = format!("Minecraft {minecraft_version} for {name}");
+                move |result| Message::GotInstanceQueue(task.clone(), name.clone(), result)
+            },
+        );
+
+        if loader == Loader::Vanilla {
+            return download;
+        }
+
+        self.mod_suggestions = ModSuggestions {
+            description: format!(
+                "Most mods need these libraries to load. Do you want to install them into {name}?"
+            ),
+            instance: name,
+            suggestions: Vec::new(),
+        };
+
+        Command::batch([
+            download,
+            Command::perform(
+                lib::modrinth::get_suggestions(minecraft_version, loader)
+                    .map_err(|e| format_error(&e)),
+                Message::GotSuggestions,
+            ),
+        ])
+    }
+
+    fn get_backups(&self, name: &str) -> Command<Message> {
+        let name = name.to_string();
+
+        Command::perform(lib::backups::get_backups(name.clone()), move |backups| {
+            Message::GotBackups(name.clone(), backups)
+        })
+    }
+
+    fn get_disk_usage(&self) -> Command<Message> {
+        let instance_dirs = self
+            .instances
+            .list
+            .keys()
+            .map(|name| (name.clone(), self.instances.get_dir(name)))
+            .collect();
+
+        Command::perform(
+            lib::store::get_disk_usage(instance_dirs).map_err(|e| format_error(&e)),
+            Message::GotDiskUsage,
+        )
+    }
+
+    // a first page starts a new search
+    fn search_modpacks(&mut self, offset: usize) -> Command<Message> {
+        if offset == 0 {
+            self.modrinth_modpacks.generation += 1;
+        }
+        let generation = self.modrinth_modpacks.generation;
+
+        Command::perform(
+            lib::modrinth::search_modpacks(
+                self.modrinth_modpacks.query.clone(),
+                self.modrinth_modpacks.filters.clone(),
+                offset,
+            )
+            .map_err(|e| format_error(&e)),
+            move |result| Message::GotModpacks(generation, result),
+        )
+    }
+
+    // a first page starts a new search
+    fn browse_mods(&mut self, offset: usize) -> Command<Message> {
+        if offset == 0 {
+            self.mod_browser.generation += 1;
+        }
+        let generation = self.mod_browser.generation;
+
+        let Some(info) = self.instances.list.get(&self.mod_browser.instance) else {
+            return Command::none();
+        };
+
+        Command::perform(
+            lib::modrinth::browse_mods(
+                self.mod_browser.query.clone(),
+                info.minecraft.clone(),
+                info.loader,
+                self.mod_browser.filters.clone(),
+                offset,
+            )
+            .map_err(|e| format_error(&e)),
+            move |result| Message::GotMods(generation, result),
+        )
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let mut subscriptions = vec![
+            self.download.subscription(self.settings.download_options()),
+            subscription::events_with(|event, status| match event {
+                Event::Window(window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code,
+                    modifiers,
+                }) => match key_code {
+                    // also when the palette's input captured it
+                    KeyCode::Escape => Some(Message::ClosePalette),
+                    // text inputs capture every key they get while focused
+                    _ if !modifiers.command() || status == event::Status::Captured => None,
+                    KeyCode::K => Some(Message::OpenPalette),
+                    KeyCode::N => Some(Message::ChangePage(Page::NewInstance)),
+                    KeyCode::L => Some(Message::LaunchOpenInstance),
+                    _ => None,
+                },
+                _ => None,
+            }),
+        ];
+
+        for (name, console) in &self.server_consoles {
+            subscriptions.push(server_console::output(
+                name.clone(),
+                console.pid,
+                Arc::clone(&console.output),
+            ));
+        }
+
+        if let Some(listener) = &self.ipc_listener {
+            subscriptions.push(ipc::links(Arc::clone(listener)).map(Message::LinkForwarded));
+        }
+
+        Subscription::batch(subscriptions)
     }
 }
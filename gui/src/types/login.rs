@@ -6,3 +6,11 @@ pub struct Login {
     pub url: String,
     pub code: String,
 }
+
+// for authlib-injector servers, which take a username and password
+#[derive(Default)]
+pub struct AuthlibLogin {
+    pub server: String,
+    pub username: String,
+    pub password: String,
+}
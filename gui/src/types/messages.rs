@@ -1,49 +1,290 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::path::PathBuf;
+
 use crate::pages::Page;
 use crate::subscriptions::download;
-use lib::accounts::Account;
-use lib::instances::Instance;
-use lib::modrinth::Projects;
+use lib::accounts::{Account, HeadFetch};
+use lib::backups::{Backup, BackupSchedule};
+use lib::cache::CachedDocument;
+use lib::crash_reporter::CrashReport;
+use lib::gpu::Gpu;
+use lib::i18n::Language;
+use lib::import::ImportedPack;
+use lib::instance_export::ExportPart;
+use lib::instances::{InheritedSetting, Instance, LaunchOptions, TrashedInstance};
+use lib::jvm::{GarbageCollector, JvmPreset};
+use lib::logging::LogLevel;
+use lib::modrinth::{
+    Credit, ModList, Project, ProjectDetails, Projects, SearchFilters, Suggestion, Version,
+};
+use lib::runtime_manager::{system::JavaInstallation, JavaVendor};
+use lib::server_list::ServerStatus;
+use lib::settings::{InstancesView, Theme};
+use lib::skin::SkinPreview;
+use lib::store::{CleanReport, DiskUsage, StorageArea};
+use lib::updater::Update;
+use lib::vanilla_installer::{self, LatestRelease, PatchNotes, ReleaseType};
+use lib::world::{Difficulty, GameMode};
+use lib::DownloadQueue;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ChangePage(Page),
     Error(String, bool),
+    DismissNotification(usize),
+    ToggleNotificationDetails(usize),
+    CopyToClipboard(String),
+    // notification id, what its button does
+    NotificationAction(usize, Box<Message>),
+    TerminatePreviousGames,
+    // keyboard shortcuts
+    OpenPalette,
+    ClosePalette,
+    PaletteQueryChanged(String),
+    // picks the best match
+    PaletteSubmit,
+    PaletteSelect(Box<Message>),
+    LaunchOpenInstance,
     OpenURL(String),
-    GotUpdate(Result<Option<(String, String)>, String>),
-    GotAccountHead(Result<Account, String>),
+    GotUpdate(Result<Option<Update>, String>),
+    InstallUpdate(Update),
+    DownloadedUpdate(Result<PathBuf, String>),
+    GotAccountHead(Result<HeadFetch, String>),
+    RetryAccountHead(Account, u32),
+    GotSkinPreview(Result<SkinPreview, String>),
     CreatedInstance(Result<(), String>),
     LaunchInstance(String),
+    LaunchDemo(String),
+    // instance name, world folder
+    QuickPlay(String, String),
+    // instance name, pid
+    GameExited(String, u32, Result<(), String>),
+    StartExperiment(String),
+    // instance name, whether to keep the changes
+    EndExperiment(String, bool),
+    TerminateInstance(String),
+    // instance name, line
+    ServerOutput(String, String),
+    ServerInputChanged(String, String),
+    SendServerCommand(String),
+    AcceptEula(String),
+    EditServerProperties(String),
+    SetServerProperty(&'static str, String),
+    SaveServerProperties(String),
+    // instance name, world folder
+    EditWorld(String, String),
+    SetWorldName(String),
+    SetWorldGameMode(GameMode),
+    SetWorldDifficulty(Difficulty),
+    SetWorldCheats(bool),
+    SaveWorld(String, String),
+    EditServerList(String),
+    PingServers(String),
+    // instance name, address
+    GotServerStatus(String, String, Result<ServerStatus, String>),
+    ServerNameChanged(String),
+    ServerAddressChanged(String),
+    AddServer(String),
+    // instance name, index in the list
+    RemoveServer(String, usize),
+    OpenDeepLink(String),
+    // from a launcher started later
+    LinkForwarded(String),
+    // instance name, modrinth project
+    ConfirmInstallProject(String, String),
+    FileDropped(PathBuf),
+    ImportNameChanged(String),
+    ImportPack,
+    SetInstanceJvmPreset(String, Option<JvmPreset>),
+    SetInstanceProfiling(String, bool),
+    SetBackupSchedule(String, BackupSchedule),
+    SetBackupRetention(String, String),
+    BackUpInstance(String),
+    BackedUpInstance(String, Result<(), String>),
+    GotBackups(String, Vec<Backup>),
+    // the scheduled backup is taken before the game starts
+    BackedUpBeforeLaunch(String, LaunchOptions, Result<(), String>),
+    RanPreLaunchHook(String, LaunchOptions, Result<(), String>),
+    // instance name, backup
+    RestoreBackup(String, PathBuf),
+    ConfirmRestoreBackup(String, PathBuf),
+    RestoredBackup(Result<(), String>),
+    DeleteBackup(String, PathBuf),
+    RestoreTrashed(TrashedInstance),
+    DeleteTrashed(TrashedInstance),
+    // None goes back to the default from the settings
+    SetInstanceInherited(String, InheritedSetting, Option<String>),
+    ToggleTagFilter(String),
+    NewTagChanged(String),
+    AddInstanceTag(String, String),
+    RemoveInstanceTag(String, String),
+    EnvKeyChanged(String),
+    EnvValueChanged(String),
+    AddInstanceEnv(String),
+    RemoveInstanceEnv(String, String),
+    SetInstanceAccount(String, Account),
     OpenInstanceFolder(String),
     OpenInstanceConfig(String),
     DeleteInstance(String),
-    DownloadProgressed(download::Progress),
+    ConfirmDeleteInstance(String),
+    VerifyInstance(String),
+    UpdateInstance(String),
+    ConfirmUpdateInstance(String),
+    VerifiedInstance(String, Result<DownloadQueue, String>),
+    // instance, new version
+    VerifiedUpdate(String, String, Result<DownloadQueue, String>),
+    UpdatedInstance(String, String),
+    GotJavaInstallations(Vec<JavaInstallation>),
+    GotGpus(Vec<Gpu>),
+    SetInstanceGpu(String, Option<String>),
+    GotLatestRelease(Result<LatestRelease, String>),
+    CheckConnection,
+    GotConnection(bool),
+    SetInstanceJava(String, JavaInstallation),
+    ChooseJavaBinary(String),
+    CheckedJavaBinary(String, Result<JavaInstallation, String>),
+    ExportCredits(String),
+    ExportClasspathGraph(String),
+    OpenInstanceExport(String),
+    ToggleExportPart(ExportPart),
+    ExportInstance(String),
+    ExportedInstance(Result<(), String>),
+    GotCredits(Result<Vec<Credit>, String>),
+    DownloadProgressed(usize, download::Progress),
+    PauseDownload(usize),
+    ResumeDownload(usize),
+    CancelDownload(usize),
+    ClearFinishedDownloads,
+    PauseAllDownloads,
+    ResumeAllDownloads,
+    // none if it was cancelled
+    TaskFinished(usize, Option<Box<Message>>),
+    CancelTask(usize),
 
     // Vanilla installer
     GetVersions,
-    GotVersions(Result<Vec<String>, String>),
+    GotVersions(Result<Vec<vanilla_installer::Version>, String>),
+    ToggleReleaseType(ReleaseType),
+    VersionFilterChanged(String),
+    // version id
+    GotPatchNotes(String, Result<Option<PatchNotes>, String>),
     ChangeName(String),
-    SetOptimizeJvm(bool),
+    SetInstallerJvmPreset(JvmPreset),
+    SetInstallerServer(bool),
+    SetInstallerFabric(bool),
     SetMemory(String),
     SelectVersion(usize),
     CreateInstance,
+    // the loader version for the new instance
+    GotInstallerLoader(Result<String, String>),
 
     // Accounts
     AddAccount,
     LoggedIn(Result<Account, String>),
     SelectAccount(Account),
     RemoveAccount(Account),
+    ConfirmRemoveAccount(Account),
     OpenLoginUrl,
     AddOfflineAccount,
     OfflineAccountUsernameChanged(String),
+    AuthlibServerChanged(String),
+    AuthlibUsernameChanged(String),
+    AuthlibPasswordChanged(String),
+    AddAuthlibAccount,
+    AddElyByAccount,
 
     // Settings
     SetCheckForUpdates(bool),
+    SetTheme(Theme),
+    SetInstancesView(InstancesView),
+    ChooseInstancesDir,
+    ConfirmMoveInstances(PathBuf),
+    ExportConfig,
+    ImportConfig,
+    // archive, whether to replace existing instances
+    ImportConfigArchive(PathBuf, bool),
+    MovedInstances(PathBuf, Result<(), String>),
+    SetLanguage(Language),
+    SetJavaVendor(JavaVendor),
+    SetJvmPreset(JvmPreset),
+    SetGarbageCollector(GarbageCollector),
+    SetLogLevel(LogLevel),
+    SetCrashReports(bool),
+    SetMinimizeOnLaunch(bool),
+    ReportCrash(CrashReport),
+    OpenCrashReport(PathBuf),
+    OpenLogsFolder,
+    SetDownloadThreads(String),
+    SetDownloadRetries(String),
+    SetTrashRetention(String),
+    SetUseMirrors(bool),
+    SetDownloadSpeedLimit(String),
+    SetDefault(InheritedSetting, String),
     SaveSettings,
+    PredownloadVersionsChanged(String),
+    Predownload,
+    GotDownloadQueue(Result<DownloadQueue, String>),
+    // task name, instance name
+    GotInstanceQueue(String, String, Result<DownloadQueue, String>),
+    // instance name
+    ImportedPack(String, Result<ImportedPack, String>),
+    CleanUnusedFiles(Vec<StorageArea>),
+    ConfirmCleanUnusedFiles(Vec<StorageArea>),
+    CleanedUnusedFiles(Result<CleanReport, String>),
+
+    // Storage
+    GotDiskUsage(Result<DiskUsage, String>),
+    OpenStorageArea(StorageArea),
+
+    // JSON viewer
+    SelectJsonDocument(CachedDocument),
+    GotJsonDocument(Result<String, String>),
+    JsonSearchChanged(String),
+
+    // Feedback
+    FeedbackTitleChanged(String),
+    FeedbackDescriptionChanged(String),
+    SetAttachFeedbackSummary(bool),
+    SubmitFeedback,
 
     // Modrinth
     GetModpacks,
-    GotModpacks(Result<Projects, String>),
+    ModpackQueryChanged(String),
+    // text fields, the search runs on submit
+    ModpackFiltersChanged(SearchFilters),
+    SetModpackFilters(SearchFilters),
+    LoadMoreModpacks,
+    // search generation, see ModrinthModpacks
+    GotModpacks(usize, Result<Projects, String>),
+    GotSuggestions(Result<Vec<Suggestion>, String>),
+    InstallSuggestion(Suggestion),
+    InstallAllSuggestions,
+    OptimizeInstance(String),
+    ConfirmOptimizeInstance(String),
+    GotFabricLoader(String, Result<String, String>),
+    GotPerformanceMods(String, Result<Vec<Suggestion>, String>),
+    OpenModBrowser(String),
+    SelectModList(ModList),
+    ModQueryChanged(String),
+    ModFiltersChanged(SearchFilters),
+    SetModFilters(SearchFilters),
+    BrowseMods,
+    LoadMoreMods,
+    // search generation, see ModBrowser
+    GotMods(usize, Result<Projects, String>),
+    GotModImage(String, Result<Vec<u8>, String>),
+    InstallMod(Project),
+    OpenModrinthProject(Project),
+    // project id
+    GotModrinthProject(String, Result<ProjectDetails, String>),
+    // image url
+    // project id and image url
+    GotGalleryImage(String, String, Result<Vec<u8>, String>),
+    ShowGalleryImage(Option<usize>),
+    InstallModpack(String),
+    DownloadedModpack(Result<PathBuf, String>),
+    // instance name, title
+    GotModVersion(String, String, Result<Option<Version>, String>),
 }
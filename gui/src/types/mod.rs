@@ -7,3 +7,16 @@ pub mod vanilla_installer;
 pub mod login;
 pub mod modrinth_modpacks;
 pub mod download;
+pub mod mod_suggestions;
+pub mod json_viewer;
+pub mod feedback;
+pub mod head_queue;
+pub mod instance_editor;
+pub mod mod_browser;
+pub mod pack_import;
+pub mod server_console;
+pub mod notifications;
+pub mod tasks;
+pub mod palette;
+pub mod modrinth_project;
+pub mod server_list;
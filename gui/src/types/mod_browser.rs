@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use iced::widget::image;
+use lib::modrinth::{ModList, Project, SearchFilters};
+
+pub struct ModBrowser {
+    pub instance: String,
+    pub list: ModList,
+    pub query: String,
+    // version and loader always come from the instance
+    pub filters: SearchFilters,
+    pub projects: Vec<Project>,
+    pub total_hits: usize,
+    // bumped by every new search, answers to older ones are dropped
+    pub generation: usize,
+    // by project id
+    pub images: HashMap<String, image::Handle>,
+}
+
+impl Default for ModBrowser {
+    fn default() -> Self {
+        let list = ModList::Popular;
+        let mut filters = SearchFilters::default();
+        list.apply(&mut filters);
+
+        Self {
+            instance: String::new(),
+            list,
+            query: String::new(),
+            filters,
+            projects: Vec::new(),
+            total_hits: 0,
+            generation: 0,
+            images: HashMap::new(),
+        }
+    }
+}
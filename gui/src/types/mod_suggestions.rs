@@ -0,0 +1,12 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use lib::modrinth::Suggestion;
+
+#[derive(Default)]
+pub struct ModSuggestions {
+    pub instance: String,
+    // why these mods are suggested
+    pub description: String,
+    pub suggestions: Vec<Suggestion>,
+}
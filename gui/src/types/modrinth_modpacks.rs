@@ -1,16 +1,36 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use lib::modrinth::Project;
+use lib::instances::Loader;
+use lib::modrinth::{Project, SearchFilters};
 
 pub struct ModrinthModpacks {
+    pub query: String,
+    pub filters: SearchFilters,
     pub projects: Vec<Project>,
+    pub total_hits: usize,
+    // bumped by every new search, answers to older ones are dropped
+    pub generation: usize,
+}
+
+impl ModrinthModpacks {
+    // fabric packs are the most common ones
+    pub fn default_filters() -> SearchFilters {
+        SearchFilters {
+            loader: Some(Loader::Fabric),
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for ModrinthModpacks {
     fn default() -> Self {
         Self {
+            query: String::new(),
+            filters: Self::default_filters(),
             projects: Vec::new(),
+            total_hits: 0,
+            generation: 0,
         }
     }
 }
@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use iced::widget::image;
+use lib::markdown::Block;
+use lib::modrinth::{Project, ProjectDetails};
+
+use crate::pages::Page;
+
+// a search hit, looked at before it's installed
+pub struct ModrinthProject {
+    pub project: Project,
+    // the modpacks page or the mod browser, it decides what installing means
+    pub back: Page,
+    pub details: Option<ProjectDetails>,
+    // parsed once, not on every view
+    pub description: Vec<Block>,
+    // gallery screenshots by url, they arrive one by one
+    pub images: HashMap<String, Result<image::Handle, String>>,
+    // the gallery index shown in the lightbox
+    pub lightbox: Option<usize>,
+}
@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::VecDeque;
+
+use crate::types::messages::Message;
+
+// only the first few are shown, the rest wait for them to be dismissed
+pub const VISIBLE: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: usize,
+    pub severity: Severity,
+    pub title: String,
+    pub text: String,
+    // what caused it, from the lines after the first one
+    pub details: Option<String>,
+    pub expanded: bool,
+    // label and what pressing it does, the notification is dismissed too
+    pub actions: Vec<(String, Message)>,
+    // only goes away through one of the actions
+    pub required: bool,
+}
+
+impl Notification {
+    // the ones that don't ask for anything go away on their own
+    pub fn is_transient(&self) -> bool {
+        matches!(self.severity, Severity::Info | Severity::Success) && self.actions.is_empty()
+    }
+
+    // for bug reports
+    pub fn full_text(&self) -> String {
+        match &self.details {
+            Some(details) => format!("{}\n{}\n{}", self.title, self.text, details),
+            None => format!("{}\n{}", self.title, self.text),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Notifications {
+    next_id: usize,
+    pub queue: VecDeque<Notification>,
+}
+
+impl Notifications {
+    pub fn push(
+        &mut self,
+        severity: Severity,
+        title: impl Into<String>,
+        text: impl Into<String>,
+        actions: Vec<(String, Message)>,
+    ) -> &Notification {
+        let text = text.into();
+        let (text, details) = match text.split_once('\n') {
+            Some((text, details)) => (text.to_string(), Some(details.to_string())),
+            None => (text, None),
+        };
+
+        self.queue.push_back(Notification {
+            id: self.next_id,
+            severity,
+            title: title.into(),
+            text,
+            details,
+            expanded: false,
+            actions,
+            required: false,
+        });
+        self.next_id += 1;
+
+        self.queue.back().unwrap()
+    }
+
+    // for questions that need an answer, an action has to be picked
+    pub fn push_required(
+        &mut self,
+        severity: Severity,
+        title: impl Into<String>,
+        text: impl Into<String>,
+        actions: Vec<(String, Message)>,
+    ) {
+        self.push(severity, title, text, actions);
+        self.queue.back_mut().unwrap().required = true;
+    }
+
+    pub fn dismiss(&mut self, id: usize) {
+        self.queue.retain(|notification| notification.id != id);
+    }
+
+    pub fn toggle_details(&mut self, id: usize) {
+        if let Some(notification) = self.queue.iter_mut().find(|n| n.id == id) {
+            notification.expanded = !notification.expanded;
+        }
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &Notification> {
+        self.queue.iter().take(VISIBLE)
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs;
+use std::path::PathBuf;
+
+use lib::import::PackInfo;
+
+// a pack dropped onto the window, waiting for a name
+pub struct PackImport {
+    pub path: PathBuf,
+    pub info: PackInfo,
+    pub name: String,
+    // downloaded into the temp dir, deleted when it's not needed anymore
+    pub temporary: bool,
+}
+
+impl Drop for PackImport {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::widget::text_input;
+use lib::instances::Instances;
+
+use crate::pages::Page;
+use crate::types::messages::Message;
+
+const MAX_ENTRIES: usize = 8;
+
+pub fn input_id() -> text_input::Id {
+    text_input::Id::new("command-palette")
+}
+
+// how far apart the matched characters are, lower is better; none when the
+// query isn't a subsequence of the candidate
+fn fuzzy_score(query: &str, candidate: &str) -> Option<usize> {
+    let candidate = candidate.to_lowercase();
+    let mut chars = candidate.char_indices();
+    let mut last = None;
+    let mut score = 0;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        score += last.map_or(i, |last| i - last - 1);
+        last = Some(i);
+    }
+
+    Some(score)
+}
+
+#[derive(Default)]
+pub struct Palette {
+    pub open: bool,
+    pub query: String,
+}
+
+impl Palette {
+    // instances and pages matching the query, best first
+    pub fn entries(&self, instances: &Instances) -> Vec<(String, Message)> {
+        let instances = instances.list.keys().map(|name| {
+            (
+                name.clone(),
+                Message::ChangePage(Page::Instance(name.clone())),
+            )
+        });
+
+        let pages = Page::NAMES.into_iter().filter_map(|name| {
+            Some((
+                name.to_string(),
+                Message::ChangePage(Page::from_name(name)?),
+            ))
+        });
+
+        let mut entries = instances
+            .chain(pages)
+            .filter_map(|(label, message)| {
+                fuzzy_score(&self.query, &label).map(|score| (score, label, message))
+            })
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        entries
+            .into_iter()
+            .take(MAX_ENTRIES)
+            .map(|(_, label, message)| (label, message))
+            .collect()
+    }
+}
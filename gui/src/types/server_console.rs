@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use iced::futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+// older lines are dropped
+const MAX_LINES: usize = 1000;
+
+pub struct ServerConsole {
+    pub pid: u32,
+    pub lines: Vec<String>,
+    pub input: String,
+    // gone once the server exits
+    pub stdin: Option<ChildStdin>,
+    // taken by the subscription
+    pub output: Arc<Mutex<Option<UnboundedReceiver<String>>>>,
+}
+
+// reading blocks, so each pipe gets its own thread
+fn forward_lines(pipe: impl Read + Send + 'static, sender: UnboundedSender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            if sender.unbounded_send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+impl ServerConsole {
+    pub fn new(child: &mut Child) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, sender);
+        }
+
+        Self {
+            pid: child.id(),
+            lines: Vec::new(),
+            input: String::new(),
+            stdin: child.stdin.take(),
+            output: Arc::new(Mutex::new(Some(receiver))),
+        }
+    }
+
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.remove(0);
+        }
+
+        self.lines.push(line);
+    }
+
+    pub fn send(&mut self, command: &str) -> io::Result<()> {
+        let Some(stdin) = &mut self.stdin else {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "The server isn't running",
+            ));
+        };
+
+        writeln!(stdin, "{command}")?;
+        stdin.flush()
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+use lib::server_list::{ServerList, ServerStatus};
+
+#[derive(Debug, Default)]
+pub struct ServerListEditor {
+    pub list: ServerList,
+    // by address, a missing one is still being pinged
+    pub status: HashMap<String, Result<ServerStatus, String>>,
+    pub new_name: String,
+    pub new_address: String,
+}
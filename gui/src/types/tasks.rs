@@ -0,0 +1,163 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::futures::future::{self, AbortHandle};
+use iced::futures::{Future, FutureExt};
+use iced::Command;
+
+use crate::subscriptions::download;
+use crate::types::messages::Message;
+
+enum Cancel {
+    // the work itself can't be interrupted, only its result is dropped,
+    // with a message to leave the page that waited for it
+    Abort(AbortHandle, Option<Box<Message>>),
+    // a job in the download queue
+    Download(usize),
+    // dropping the result would leave things half done, like moved files
+    Never,
+}
+
+pub struct Task {
+    pub id: usize,
+    pub name: String,
+    // none when there's no way to tell
+    pub progress: Option<f32>,
+    cancel: Cancel,
+}
+
+impl Task {
+    pub fn can_cancel(&self) -> bool {
+        !matches!(self.cancel, Cancel::Never)
+    }
+}
+
+// everything running in the background, downloads included
+#[derive(Default)]
+pub struct Tasks {
+    pub list: Vec<Task>,
+    next_id: usize,
+}
+
+impl Tasks {
+    fn add(&mut self, name: String, cancel: Cancel) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.list.push(Task {
+            id,
+            name,
+            progress: None,
+            cancel,
+        });
+
+        id
+    }
+
+    fn spawn<T: Send + 'static>(
+        &mut self,
+        name: String,
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(T) -> Message + Send + 'static,
+        cancellable: bool,
+        on_cancel: Option<Message>,
+    ) -> Command<Message> {
+        let (future, handle) = future::abortable(future);
+        let cancel = if cancellable {
+            Cancel::Abort(handle, on_cancel.map(Box::new))
+        } else {
+            Cancel::Never
+        };
+        let id = self.add(name, cancel);
+
+        Command::perform(
+            future.map(|result| result.ok().map(|output| Box::new(f(output)))),
+            move |message| Message::TaskFinished(id, message),
+        )
+    }
+
+    // like Command::perform, but the task is listed until it's done
+    pub fn perform<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(T) -> Message + Send + 'static,
+    ) -> Command<Message> {
+        self.spawn(name.into(), future, f, true, None)
+    }
+
+    // on_cancel is sent when the task is cancelled
+    pub fn perform_or<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(T) -> Message + Send + 'static,
+        on_cancel: Message,
+    ) -> Command<Message> {
+        self.spawn(name.into(), future, f, true, Some(on_cancel))
+    }
+
+    // for work that has to run to the end
+    pub fn perform_uncancellable<T: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(T) -> Message + Send + 'static,
+    ) -> Command<Message> {
+        self.spawn(name.into(), future, f, false, None)
+    }
+
+    pub fn add_download(&mut self, name: String, job: usize) {
+        self.add(name, Cancel::Download(job));
+    }
+
+    fn find_download(&mut self, job: usize) -> Option<&mut Task> {
+        self.list
+            .iter_mut()
+            .find(|task| matches!(task.cancel, Cancel::Download(id) if id == job))
+    }
+
+    pub fn update_download(&mut self, job: usize, progress: &download::Progress) {
+        match progress {
+            download::Progress::Started => {
+                if let Some(task) = self.find_download(job) {
+                    task.progress = Some(0.0);
+                }
+            }
+            download::Progress::Advanced(stats) => {
+                if let Some(task) = self.find_download(job) {
+                    task.progress = Some(stats.percentage());
+                }
+            }
+            download::Progress::Finished | download::Progress::Errored => {
+                self.remove_download(job);
+            }
+        }
+    }
+
+    pub fn remove_download(&mut self, job: usize) {
+        self.list
+            .retain(|task| !matches!(task.cancel, Cancel::Download(id) if id == job));
+    }
+
+    pub fn finish(&mut self, id: usize) {
+        self.list.retain(|task| task.id != id);
+    }
+
+    // returns what has to happen after the task is gone, like cancelling its download
+    pub fn cancel(&mut self, id: usize) -> Option<Message> {
+        let index = self
+            .list
+            .iter()
+            .position(|task| task.id == id && task.can_cancel())?;
+
+        match self.list.remove(index).cancel {
+            Cancel::Abort(handle, on_cancel) => {
+                handle.abort();
+                on_cancel.map(|message| *message)
+            }
+            Cancel::Download(job) => Some(Message::CancelDownload(job)),
+            Cancel::Never => None,
+        }
+    }
+}
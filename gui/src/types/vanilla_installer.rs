@@ -1,22 +1,58 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use lib::jvm::JvmPreset;
+use lib::vanilla_installer::{PatchNotes, ReleaseType, Version};
+
 pub struct VanillaInstaller {
-    pub versions: Vec<String>,
+    pub versions: Vec<Version>,
+    // only these are listed
+    pub release_types: Vec<ReleaseType>,
+    pub version_filter: String,
     pub selected_version: Option<usize>,
+    // of the selected version
+    pub patch_notes: Option<PatchNotes>,
     pub name: String,
-    pub optimize_jvm: bool,
+    pub jvm_preset: Option<JvmPreset>,
+    // empty to use the one from the settings
     pub memory: String,
+    pub server: bool,
+    // with the latest Fabric loader, ignored for servers
+    pub fabric: bool,
 }
 
 impl Default for VanillaInstaller {
     fn default() -> Self {
         Self {
             versions: Vec::new(),
+            release_types: vec![ReleaseType::Release],
+            version_filter: String::new(),
             selected_version: None,
+            patch_notes: None,
             name: "My Instance".to_string(),
-            optimize_jvm: true,
-            memory: "4G".to_string(),
+            jvm_preset: None,
+            memory: String::new(),
+            server: false,
+            fabric: false,
+        }
+    }
+}
+
+impl VanillaInstaller {
+    pub fn is_shown(&self, version: &Version) -> bool {
+        self.release_types.contains(&version.release_type)
+            && version.id.contains(self.version_filter.trim())
+    }
+
+    // selected_version indexes all the versions, a hidden one can't be created
+    pub fn deselect_hidden(&mut self) {
+        let hidden = self
+            .selected_version
+            .is_some_and(|index| !self.is_shown(&self.versions[index]));
+
+        if hidden {
+            self.selected_version = None;
+            self.patch_notes = None;
         }
     }
 }
@@ -1,22 +1,29 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{fs, io, thread};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::time::Instant;
+use std::{fmt, io, thread};
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use image::{imageops, ImageFormat};
 use oauth2::ureq::http_client;
 use oauth2::{
-    basic::BasicClient, devicecode::StandardDeviceAuthorizationResponse, url, AuthUrl, ClientId,
-    DeviceAuthorizationUrl, ExtraTokenFields, RefreshToken, Scope, StandardTokenResponse,
-    TokenResponse, TokenType, TokenUrl,
+    basic::BasicClient, devicecode::StandardDeviceAuthorizationResponse, url, AuthType, AuthUrl,
+    AuthorizationCode, ClientId, ClientSecret, CsrfToken, DeviceAuthorizationUrl, ExtraTokenFields,
+    RedirectUrl, RefreshToken, Scope, StandardTokenResponse, TokenResponse, TokenType, TokenUrl,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use serde_with::{base64::Base64, serde_as};
+use sha2::{Digest, Sha256};
 use time::{Duration, OffsetDateTime};
 
+use crate::authlib_injector::{self, ELY_BY_SERVER};
 use crate::paths::ACCOUNTS_PATH;
 use crate::AGENT;
+use crate::{deep_link, file_lock};
 
 pub const MSA_DEVICE_AUTH_ENDPOINT: &str =
     "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
@@ -29,8 +36,23 @@ const XSTS_AUTHORIZATION_ENDPOINT: &str = "https://xsts.auth.xboxlive.com/xsts/a
 const MINECRAFT_AUTH_ENDPOINT: &str =
     "https://api.minecraftservices.com/authentication/login_with_xbox";
 const MINECRAFT_PROFILE_ENDPOINT: &str = "https://api.minecraftservices.com/minecraft/profile";
+// skins rarely change
+const HEAD_CACHE_TIME: Duration = Duration::days(1);
+const HEAD_RETRIES: u32 = 3;
 pub const CLIENT_ID: &str = "543a897a-0694-435b-a147-11de17aacd1f";
 pub const SCOPES: &[&str] = &["XboxLive.signin"];
+const ELY_BY_AUTHORIZATION_ENDPOINT: &str = "https://account.ely.by/oauth2/v1";
+const ELY_BY_TOKEN_ENDPOINT: &str = "https://account.ely.by/api/oauth2/v1/token";
+const ELY_BY_INFO_ENDPOINT: &str = "https://account.ely.by/api/account/v1/info";
+const ELY_BY_CLIENT_ID: &str = "icy-launcher";
+// the secret comes with the client registration, builds without it only have the
+// password login
+pub const ELY_BY_CLIENT_SECRET: Option<&str> = option_env!("ELY_BY_CLIENT_SECRET");
+// the redirect registered with the client
+const ELY_BY_REDIRECT_PORT: u16 = 25585;
+const ELY_BY_SCOPES: &[&str] = &["account_info", "minecraft_server_session", "offline_access"];
+// how long the browser has to send the code back
+const ELY_BY_LOGIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5 * 60);
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -45,11 +67,17 @@ pub struct Account {
     pub cached_head: Option<Vec<u8>>,
 
     cached_head_time: Option<OffsetDateTime>,
+
+    // yggdrasil server of authlib-injector accounts
+    pub auth_server: Option<String>,
+    pub client_token: Option<String>,
+    // ely.by accounts added through the browser, they have no client token
+    pub oauth_refresh_token: Option<String>,
 }
 
 impl Account {
     pub fn new_offline(username: String) -> Self {
-        use md5::{Digest, Md5};
+        use md5::Md5;
 
         let mc_id = {
             let text = format!("OfflinePlayer:{}", username);
@@ -65,43 +93,134 @@ impl Account {
             token_time: None,
             cached_head: None,
             cached_head_time: None,
+            auth_server: None,
+            client_token: None,
+            oauth_refresh_token: None,
         }
     }
+
+    pub fn is_offline(&self) -> bool {
+        self.ms_refresh_token.is_none() && self.auth_server.is_none()
+    }
+}
+
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mc_username)
+    }
+}
+
+// the face is the 8x8 square at (8, 8), scaled up without blurring it
+fn crop_head(skin: &[u8]) -> Result<Vec<u8>> {
+    let skin = image::load_from_memory_with_format(skin, ImageFormat::Png)?;
+    let head = skin
+        .crop_imm(8, 8, 8, 8)
+        .resize(64, 64, imageops::FilterType::Nearest);
+
+    let mut bytes = Vec::new();
+    head.write_to(&mut io::Cursor::new(&mut bytes), ImageFormat::Png)?;
+
+    Ok(bytes)
+}
+
+pub(crate) fn ely_by_skin_url(username: &str) -> String {
+    format!(
+        "https://skinsystem.ely.by/skins/{}.png",
+        deep_link::encode(username)
+    )
 }
 
-pub async fn get_head(mut account: Account) -> Result<Account> {
+#[derive(Debug, Clone)]
+pub enum HeadFetch {
+    Fetched(Account),
+    // rate limited, the caller fetches again after the wait
+    RateLimited {
+        account: Account,
+        attempt: u32,
+        wait: std::time::Duration,
+    },
+}
+
+// attempt counts the fetches already rate limited
+pub async fn get_head(mut account: Account, attempt: u32) -> Result<HeadFetch> {
     let now = OffsetDateTime::now_utc();
 
     if let Some(time) = &account.cached_head_time {
-        if now < *time + Duration::minutes(5) {
-            return Ok(account);
+        if now < *time + HEAD_CACHE_TIME {
+            return Ok(HeadFetch::Fetched(account));
         }
     }
 
-    let resp = AGENT
-        .get(&format!("https://crafatar.com/avatars/{}", account.mc_id))
-        .call()?;
+    let url = match account.auth_server.as_deref() {
+        None => format!("https://crafatar.com/avatars/{}", account.mc_id),
+        Some(ELY_BY_SERVER) => ely_by_skin_url(&account.mc_username),
+        // other servers don't have a common skin api
+        Some(_) => return Ok(HeadFetch::Fetched(account)),
+    };
+
+    let resp = match AGENT.get(&url).call() {
+        Ok(resp) => resp,
+        // rate limited, wait as long as the server asks
+        Err(ureq::Error::Status(429, resp)) if attempt < HEAD_RETRIES => {
+            let seconds = resp
+                .header("Retry-After")
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(5)
+                .min(60);
+
+            return Ok(HeadFetch::RateLimited {
+                account,
+                attempt: attempt + 1,
+                wait: std::time::Duration::from_secs(seconds),
+            });
+        }
+        // an outdated head is better than none
+        Err(_) if account.cached_head.is_some() => return Ok(HeadFetch::Fetched(account)),
+        Err(error) => return Err(error.into()),
+    };
+
+    let mut bytes = Vec::new();
+    io::copy(&mut resp.into_reader(), &mut bytes)?;
 
-    let mut bytes = Vec::with_capacity(resp.header("Content-Length").unwrap().parse::<usize>()?);
-    io::copy(&mut resp.into_reader(), &mut bytes).unwrap();
+    // ely.by only serves whole skins
+    if account.auth_server.is_some() {
+        bytes = crop_head(&bytes)?;
+    }
 
     account.cached_head = Some(bytes);
     account.cached_head_time = Some(now);
 
-    Ok(account)
+    Ok(HeadFetch::Fetched(account))
+}
+
+// Left behind when an account is removed, so adding it back restores its instances
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RemovedAccount {
+    // sha256 of the profile id, tokens are never kept
+    pub id_hash: String,
+    pub name: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub removed: OffsetDateTime,
+    pub instances: Vec<String>,
+}
+
+fn hash_id(id: &str) -> String {
+    hex::encode(Sha256::digest(id.as_bytes()))
 }
 
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Accounts {
     pub active: Option<Account>,
     pub others: Vec<Account>,
+    // sealed, the names and instances shouldn't be readable from a shared file
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "crate::sealed")]
+    pub removed: Vec<RemovedAccount>,
 }
 
 impl Accounts {
     pub fn load() -> Result<Self> {
         if ACCOUNTS_PATH.exists() {
-            let content = fs::read_to_string(&*ACCOUNTS_PATH)?;
-            let doc = toml::from_str(&content)?;
+            let doc = file_lock::read(&ACCOUNTS_PATH, |content| Ok(toml::from_str(content)?))?;
 
             Ok(doc)
         } else {
@@ -114,50 +233,106 @@ impl Accounts {
 
     fn save(&self) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
-        fs::write(&*ACCOUNTS_PATH, content)?;
+        file_lock::write(&ACCOUNTS_PATH, content)?;
+
+        Ok(())
+    }
+
+    // applied to accounts.toml as it is on disk, another launcher process could
+    // have changed it since it was loaded
+    fn update(&mut self, modify: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        *self = file_lock::update(
+            &ACCOUNTS_PATH,
+            |content| Ok(toml::from_str(content)?),
+            |accounts| Ok(toml::to_string_pretty(accounts)?),
+            modify,
+        )?;
 
         Ok(())
     }
 
-    pub fn remove_account(&mut self, id: &str) -> Result<()> {
-        if let Some(account) = &self.active {
-            if account.mc_id == id {
-                self.active = None;
+    pub fn list(&self) -> Vec<Account> {
+        self.active.iter().chain(&self.others).cloned().collect()
+    }
+
+    pub fn find(&self, id: &str) -> Option<&Account> {
+        self.active
+            .iter()
+            .chain(&self.others)
+            .find(|a| a.mc_id == id)
+    }
+
+    // instances are the ones that were set to launch with this account
+    pub fn remove_account(&mut self, id: &str, instances: Vec<String>) -> Result<()> {
+        self.update(|accounts| {
+            let Some(account) = accounts.find(id).cloned() else {
+                return Ok(());
+            };
+
+            if accounts.active.as_ref().is_some_and(|a| a.mc_id == id) {
+                accounts.active = None;
+            } else {
+                accounts.others.retain(|a| a.mc_id != id);
             }
-        } else {
-            self.others.retain(|a| a.mc_id != id);
+
+            let id_hash = hash_id(id);
+            accounts.removed.retain(|r| r.id_hash != id_hash);
+            accounts.removed.push(RemovedAccount {
+                id_hash,
+                name: account.mc_username,
+                removed: OffsetDateTime::now_utc(),
+                instances,
+            });
+
+            Ok(())
+        })
+    }
+
+    // forgets the tombstone of a re-added account and returns it
+    pub fn take_removed(&mut self, id: &str) -> Result<Option<RemovedAccount>> {
+        let id_hash = hash_id(id);
+
+        if !self.removed.iter().any(|r| r.id_hash == id_hash) {
+            return Ok(None);
         }
 
-        self.save()?;
+        let mut removed = None;
+        self.update(|accounts| {
+            if let Some(index) = accounts.removed.iter().position(|r| r.id_hash == id_hash) {
+                removed = Some(accounts.removed.remove(index));
+            }
+
+            Ok(())
+        })?;
 
-        Ok(())
+        Ok(removed)
     }
 
     pub fn add_account(&mut self, account: Account) -> Result<()> {
-        if self.active.is_none() {
-            self.active = Some(account);
-        } else {
-            self.others.push(account);
-        }
-
-        self.save()?;
+        self.update(|accounts| {
+            if accounts.active.is_none() {
+                accounts.active = Some(account);
+            } else {
+                accounts.others.push(account);
+            }
 
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn set_active_account(&mut self, account: Account) -> Result<()> {
-        if let Some(account) = &self.active {
-            self.others.push(account.clone());
-        }
-
-        // Remove the account from the others list
-        self.others.retain(|a| a.mc_id != account.mc_id);
+        self.update(|accounts| {
+            if let Some(account) = &accounts.active {
+                accounts.others.push(account.clone());
+            }
 
-        self.active = Some(account);
+            // Remove the account from the others list
+            accounts.others.retain(|a| a.mc_id != account.mc_id);
 
-        self.save()?;
+            accounts.active = Some(account);
 
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn get_client() -> Result<BasicClient, url::ParseError> {
@@ -204,26 +379,35 @@ impl Accounts {
     }
 
     pub fn update_account(&mut self, account: &Account) -> Result<()> {
-        if let Some(active) = &mut self.active {
-            if active.mc_id == account.mc_id {
-                *active = account.to_owned();
-                self.save()?;
-                return Ok(());
+        self.update(|accounts| {
+            if let Some(existing) = accounts
+                .active
+                .iter_mut()
+                .chain(&mut accounts.others)
+                .find(|a| a.mc_id == account.mc_id)
+            {
+                *existing = account.to_owned();
             }
-        }
 
-        for other in &mut self.others {
-            if other.mc_id == account.mc_id {
-                *other = account.to_owned();
-                self.save()?;
-                return Ok(());
-            }
-        }
-
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn refresh_account(&mut self, account: Account) -> Result<Account> {
+        if account.oauth_refresh_token.is_some() {
+            let account = refresh_ely_by(account)?;
+            self.update_account(&account)?;
+
+            return Ok(account);
+        }
+
+        if let Some(server) = account.auth_server.clone() {
+            let account = authlib_injector::refresh(account, &server)?;
+            self.update_account(&account)?;
+
+            return Ok(account);
+        }
+
         let now = OffsetDateTime::now_utc();
 
         if let Some(token_time) = account.token_time {
@@ -250,6 +434,205 @@ impl Accounts {
     }
 }
 
+fn ely_by_client() -> Result<BasicClient> {
+    let secret = ELY_BY_CLIENT_SECRET
+        .ok_or_else(|| anyhow!("This build can't log in to ely.by through the browser"))?;
+
+    let client = BasicClient::new(
+        ClientId::new(ELY_BY_CLIENT_ID.to_owned()),
+        Some(ClientSecret::new(secret.to_owned())),
+        AuthUrl::new(ELY_BY_AUTHORIZATION_ENDPOINT.to_owned())?,
+        Some(TokenUrl::new(ELY_BY_TOKEN_ENDPOINT.to_owned())?),
+    )
+    .set_auth_type(AuthType::RequestBody)
+    .set_redirect_uri(RedirectUrl::new(format!(
+        "http://localhost:{ELY_BY_REDIRECT_PORT}"
+    ))?);
+
+    Ok(client)
+}
+
+#[derive(Deserialize)]
+struct ElyByInfo {
+    uuid: String,
+    username: String,
+}
+
+fn get_ely_by_account<A: ExtraTokenFields, B: TokenType>(
+    token: &StandardTokenResponse<A, B>,
+    refresh_token: Option<String>,
+) -> Result<Account> {
+    let access_token = token.access_token().secret();
+
+    let info = AGENT
+        .get(ELY_BY_INFO_ENDPOINT)
+        .set("Authorization", &format!("Bearer {access_token}"))
+        .call()?
+        .into_json::<ElyByInfo>()?;
+
+    Ok(Account {
+        mc_id: info.uuid.replace('-', ""),
+        mc_access_token: access_token.to_owned(),
+        mc_username: info.username,
+        token_time: Some(OffsetDateTime::now_utc()),
+        auth_server: Some(ELY_BY_SERVER.to_string()),
+        oauth_refresh_token: token
+            .refresh_token()
+            .map(|token| token.secret().to_owned())
+            .or(refresh_token),
+        ..Default::default()
+    })
+}
+
+// the refresh token isn't always rotated, the old one stays valid then
+fn refresh_ely_by(mut account: Account) -> Result<Account> {
+    let now = OffsetDateTime::now_utc();
+
+    if account
+        .token_time
+        .is_some_and(|time| now < time + Duration::minutes(30))
+    {
+        return Ok(account);
+    }
+
+    let Some(refresh_token) = account.oauth_refresh_token.clone() else {
+        return Ok(account);
+    };
+
+    let token = ely_by_client()?
+        .exchange_refresh_token(&RefreshToken::new(refresh_token.clone()))
+        .request(http_client)?;
+
+    let refreshed = get_ely_by_account(&token, Some(refresh_token))?;
+    account.mc_access_token = refreshed.mc_access_token;
+    account.mc_username = refreshed.mc_username;
+    account.token_time = refreshed.token_time;
+    account.oauth_refresh_token = refreshed.oauth_refresh_token;
+
+    Ok(account)
+}
+
+// "GET /?code=...&state=... HTTP/1.1"
+fn parse_query(request_line: &str) -> Result<Vec<(String, String)>> {
+    let query = request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| path.split_once('?'))
+        .map(|(_, query)| query)
+        .unwrap_or_default();
+
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| {
+            Ok((
+                deep_link::decode(key)?,
+                deep_link::decode(&value.replace('+', " "))?,
+            ))
+        })
+        .collect()
+}
+
+// the query of the request the browser is redirected with
+fn read_redirect(listener: &TcpListener) -> Result<Vec<(String, String)>> {
+    let deadline = Instant::now() + ELY_BY_LOGIN_TIMEOUT;
+
+    loop {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                if Instant::now() > deadline {
+                    bail!("The browser didn't come back in time");
+                }
+                thread::sleep(std::time::Duration::from_millis(100));
+                continue;
+            }
+            Err(error) => return Err(error.into()),
+        };
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(std::time::Duration::from_secs(10)))?;
+
+        let mut request = String::new();
+        BufReader::new(&stream).read_line(&mut request)?;
+        let params = parse_query(&request)?;
+
+        // browsers also ask for a favicon
+        if !params
+            .iter()
+            .any(|(key, _)| key == "code" || key == "error")
+        {
+            stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")?;
+            continue;
+        }
+
+        let body = "You can close this page and go back to the launcher.";
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )?;
+
+        return Ok(params);
+    }
+}
+
+// the authorization code flow, the code comes back to a local redirect
+pub struct ElyByLogin {
+    pub url: String,
+    client: BasicClient,
+    state: CsrfToken,
+    listener: TcpListener,
+}
+
+impl ElyByLogin {
+    pub fn start() -> Result<Self> {
+        let client = ely_by_client()?;
+
+        let listener = TcpListener::bind(("127.0.0.1", ELY_BY_REDIRECT_PORT))?;
+        listener.set_nonblocking(true)?;
+
+        let (url, state) = client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(ELY_BY_SCOPES.iter().map(|s| Scope::new(s.to_string())))
+            .url();
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+            state,
+            listener,
+        })
+    }
+
+    pub async fn finish(self) -> Result<Account> {
+        let params = read_redirect(&self.listener)?;
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        if let Some(error) = get("error") {
+            bail!(
+                "ely.by refused the login: {}",
+                get("error_description").unwrap_or(error)
+            );
+        }
+        if get("state") != Some(self.state.secret().as_str()) {
+            bail!("The login answer doesn't match the request");
+        }
+        let code = get("code").ok_or_else(|| anyhow!("ely.by didn't send a code"))?;
+
+        let token = self
+            .client
+            .exchange_code(AuthorizationCode::new(code.to_owned()))
+            .request(http_client)?;
+
+        get_ely_by_account(&token, None)
+    }
+}
+
 pub fn get_minecraft_account_data<A: ExtraTokenFields, B: TokenType>(
     token: &StandardTokenResponse<A, B>,
     now: OffsetDateTime,
@@ -284,13 +667,13 @@ pub fn get_minecraft_account_data<A: ExtraTokenFields, B: TokenType>(
         "TokenType": "JWT",
     });
 
-    println!("Authenticating with Xbox Live...");
+    tracing::debug!("Authenticating with Xbox Live...");
     let xbl_response = AGENT
         .post(XBOXLIVE_AUTH_ENDPOINT)
         .set("Accept", "application/json")
         .send_json(params)?
         .into_json::<XBLResponse>()?;
-    println!("Authenticated with Xbox Live!");
+    tracing::debug!("Authenticated with Xbox Live!");
 
     // Authenticate with XSTS
 
@@ -309,13 +692,13 @@ pub fn get_minecraft_account_data<A: ExtraTokenFields, B: TokenType>(
         "TokenType": "JWT",
     });
 
-    println!("Authenticating with XSTS...");
+    tracing::debug!("Authenticating with XSTS...");
     let xsts_response = AGENT
         .post(XSTS_AUTHORIZATION_ENDPOINT)
         .set("Accept", "application/json")
         .send_json(params)?
         .into_json::<XSTSResponse>()?;
-    println!("Authenticated with XSTS!");
+    tracing::debug!("Authenticated with XSTS!");
 
     // Authenticate with Minecraft
 
@@ -332,13 +715,13 @@ pub fn get_minecraft_account_data<A: ExtraTokenFields, B: TokenType>(
             )
     });
 
-    println!("Authenticating with Minecraft...");
+    tracing::debug!("Authenticating with Minecraft...");
     let minecraft_response = AGENT
         .post(MINECRAFT_AUTH_ENDPOINT)
         .set("Accept", "application/json")
         .send_json(params)?
         .into_json::<MinecraftResponse>()?;
-    println!("Authenticated with Minecraft!");
+    tracing::debug!("Authenticated with Minecraft!");
 
     // Get Minecraft profile
 
@@ -365,7 +748,31 @@ pub fn get_minecraft_account_data<A: ExtraTokenFields, B: TokenType>(
         token_time: Some(now),
         cached_head: None,
         cached_head_time: None,
+        auth_server: None,
+        client_token: None,
+        oauth_refresh_token: None,
     };
 
     Ok(account)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_redirect_query() {
+        let params = parse_query("GET /?code=a%2Fb&state=x+y HTTP/1.1\r\n").unwrap();
+        assert_eq!(
+            params,
+            vec![
+                ("code".to_string(), "a/b".to_string()),
+                ("state".to_string(), "x y".to_string()),
+            ]
+        );
+
+        assert!(parse_query("GET /favicon.ico HTTP/1.1\r\n")
+            .unwrap()
+            .is_empty());
+    }
+}
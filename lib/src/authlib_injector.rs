@@ -0,0 +1,250 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use time::{Duration, OffsetDateTime};
+use version_compare::Version;
+
+use crate::accounts::Account;
+use crate::paths::{LIBRARIES_DIR, META_DIR};
+use crate::{DownloadItem, Hash, HashAlgorithm, AGENT};
+
+const LATEST_AGENT_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+// how long the last answer of LATEST_AGENT_URL is trusted
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+// ely.by's oauth needs a registered client secret, but it also speaks yggdrasil
+pub const ELY_BY_SERVER: &str = "https://authserver.ely.by/api/authlib-injector";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthResponse {
+    access_token: String,
+    client_token: String,
+    selected_profile: Option<Profile>,
+}
+
+// servers are often given without the trailing slash, or with the api path missing it
+fn endpoint(server: &str, path: &str) -> String {
+    format!("{}/authserver/{}", server.trim_end_matches('/'), path)
+}
+
+pub async fn login(server: String, username: String, password: String) -> Result<Account> {
+    let resp = AGENT
+        .post(&endpoint(&server, "authenticate"))
+        .send_json(json!({
+            "agent": { "name": "Minecraft", "version": 1 },
+            "username": username,
+            "password": password,
+        }))?
+        .into_json::<AuthResponse>()?;
+
+    let profile = resp
+        .selected_profile
+        .ok_or_else(|| anyhow!("This account doesn't have a Minecraft profile"))?;
+
+    let mut account = Account::default();
+    account.mc_id = profile.id;
+    account.mc_access_token = resp.access_token;
+    account.mc_username = profile.name;
+    account.token_time = Some(OffsetDateTime::now_utc());
+    account.auth_server = Some(server);
+    account.client_token = Some(resp.client_token);
+
+    Ok(account)
+}
+
+// the token is only replaced once the server stops accepting it
+pub fn refresh(mut account: Account, server: &str) -> Result<Account> {
+    let now = OffsetDateTime::now_utc();
+
+    if account
+        .token_time
+        .is_some_and(|time| now < time + Duration::minutes(30))
+    {
+        return Ok(account);
+    }
+
+    let tokens = json!({
+        "accessToken": account.mc_access_token,
+        "clientToken": account.client_token,
+    });
+
+    if AGENT
+        .post(&endpoint(server, "validate"))
+        .send_json(tokens.clone())
+        .is_err()
+    {
+        let resp = AGENT
+            .post(&endpoint(server, "refresh"))
+            .send_json(tokens)?
+            .into_json::<AuthResponse>()?;
+
+        account.mc_access_token = resp.access_token;
+        account.client_token = Some(resp.client_token);
+    }
+
+    account.token_time = Some(now);
+
+    Ok(account)
+}
+
+#[derive(Serialize, Deserialize)]
+struct Checksums {
+    sha256: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Artifact {
+    version: String,
+    download_url: String,
+    checksums: Checksums,
+}
+
+fn get_dir() -> PathBuf {
+    LIBRARIES_DIR.join("authlib-injector")
+}
+
+fn get_artifact_path() -> PathBuf {
+    META_DIR.join("authlib-injector.json")
+}
+
+// authlib-injector-<version>.jar, partial downloads aren't
+fn parse_version(path: &Path) -> Option<&str> {
+    if path.extension()? != "jar" {
+        return None;
+    }
+
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix("authlib-injector-")
+}
+
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (Version::from(a), Version::from(b)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+// the newest complete jar
+fn get_installed_agent(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| parse_version(path).is_some())
+        .max_by(|a, b| {
+            compare_versions(
+                parse_version(a).unwrap_or_default(),
+                parse_version(b).unwrap_or_default(),
+            )
+        })
+}
+
+// the jar games are started with, for store::clean_unused
+pub(crate) fn get_agent_path() -> Option<PathBuf> {
+    get_installed_agent(&get_dir())
+}
+
+// the answer of the last check, if it's recent enough
+fn read_recent_artifact() -> Option<Artifact> {
+    let path = get_artifact_path();
+    let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+
+    if age > CHECK_INTERVAL {
+        return None;
+    }
+
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn get_latest_artifact() -> Result<Artifact> {
+    if let Some(artifact) = read_recent_artifact() {
+        return Ok(artifact);
+    }
+
+    let artifact = AGENT
+        .get(LATEST_AGENT_URL)
+        .call()?
+        .into_json::<Artifact>()?;
+
+    fs::create_dir_all(&*META_DIR)?;
+    fs::write(get_artifact_path(), serde_json::to_string(&artifact)?)?;
+
+    Ok(artifact)
+}
+
+// downloads the latest injector, checked once a day; falls back to the last one when offline
+fn get_agent(offline: bool) -> Result<PathBuf> {
+    let dir = get_dir();
+
+    if offline {
+        return get_installed_agent(&dir)
+            .ok_or_else(|| anyhow!("authlib-injector has to be downloaded first"));
+    }
+
+    let artifact = match get_latest_artifact() {
+        Ok(artifact) => artifact,
+        Err(error) => return get_installed_agent(&dir).ok_or(error),
+    };
+
+    let item = DownloadItem {
+        url: artifact.download_url,
+        path: dir.join(format!("authlib-injector-{}.jar", artifact.version)),
+        hash: Some(Hash {
+            hash: artifact.checksums.sha256,
+            function: HashAlgorithm::Sha256,
+        }),
+        size: None,
+        extract: false,
+    };
+
+    if !item.path.exists() {
+        item.download_file()?;
+    }
+
+    Ok(item.path)
+}
+
+// makes the game talk to the account's server instead of mojang's
+pub fn get_flags(server: &str, offline: bool) -> Result<Vec<String>> {
+    let agent = get_agent(offline)?;
+
+    Ok(vec![format!("-javaagent:{}={}", agent.display(), server)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_newest_complete_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "authlib-injector-1.2.9.jar",
+            "authlib-injector-1.2.10.jar",
+            "authlib-injector-1.2.11.part",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        assert_eq!(
+            get_installed_agent(dir.path()),
+            Some(dir.path().join("authlib-injector-1.2.10.jar"))
+        );
+    }
+}
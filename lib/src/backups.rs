@@ -0,0 +1,329 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use time::macros::format_description;
+use time::{Duration, OffsetDateTime};
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::instance_export::{self, ExportPart};
+use crate::instances::Instance;
+use crate::paths::BACKUPS_DIR;
+
+pub const DEFAULT_RETENTION: usize = 5;
+
+// what can't be downloaded again
+const BACKED_UP: [ExportPart; 4] = [
+    ExportPart::Saves,
+    ExportPart::Mods,
+    ExportPart::Config,
+    ExportPart::ResourcePacks,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackupSchedule {
+    #[default]
+    Manual,
+    OnLaunch,
+    // checked on launch too, an instance that isn't played doesn't change
+    Daily,
+}
+
+impl BackupSchedule {
+    pub const ALL: [BackupSchedule; 3] = [
+        BackupSchedule::Manual,
+        BackupSchedule::OnLaunch,
+        BackupSchedule::Daily,
+    ];
+}
+
+impl fmt::Display for BackupSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupSchedule::Manual => write!(f, "Manual"),
+            BackupSchedule::OnLaunch => write!(f, "On launch"),
+            BackupSchedule::Daily => write!(f, "Daily"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub created: OffsetDateTime,
+    pub size: u64,
+}
+
+impl Backup {
+    pub fn label(&self) -> String {
+        let format = format_description!("[year]-[month]-[day] [hour]:[minute] UTC");
+
+        self.created
+            .format(&format)
+            .unwrap_or_else(|_| self.created.to_string())
+    }
+}
+
+fn get_dir(name: &str) -> PathBuf {
+    BACKUPS_DIR.join(name)
+}
+
+// backup-<unix time>.zip, the .part ones are still being written
+fn parse_backup(path: PathBuf) -> Option<Backup> {
+    if path.extension()? != "zip" {
+        return None;
+    }
+
+    let timestamp = path
+        .file_stem()?
+        .to_str()?
+        .strip_prefix("backup-")?
+        .parse()
+        .ok()?;
+
+    Some(Backup {
+        created: OffsetDateTime::from_unix_timestamp(timestamp).ok()?,
+        size: fs::metadata(&path).ok()?.len(),
+        path,
+    })
+}
+
+// newest first
+fn list(name: &str) -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(get_dir(name)) else {
+        return Vec::new();
+    };
+
+    let mut backups = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| parse_backup(entry.path()))
+        .collect::<Vec<_>>();
+
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    backups
+}
+
+pub async fn get_backups(name: String) -> Vec<Backup> {
+    list(&name)
+}
+
+fn create(name: &str, instance_dir: &Path) -> Result<()> {
+    let dir = get_dir(name);
+    fs::create_dir_all(&dir)?;
+
+    let now = OffsetDateTime::now_utc().unix_timestamp();
+    let path = dir.join(format!("backup-{now}.zip"));
+    let part_path = path.with_extension("part");
+
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(&part_path)?));
+    let options = FileOptions::default();
+
+    for part in BACKED_UP {
+        for path in part.paths() {
+            instance_export::add_path(&mut zip, &instance_dir.join(path), path, options)?;
+        }
+    }
+
+    zip.finish()?;
+    fs::rename(part_path, path)?;
+
+    Ok(())
+}
+
+// only the newest ones are kept
+fn prune(name: &str, keep: usize) -> Result<()> {
+    for backup in list(name).into_iter().skip(keep.max(1)) {
+        fs::remove_file(backup.path)?;
+    }
+
+    Ok(())
+}
+
+pub async fn back_up(name: String, instance_dir: PathBuf, keep: usize) -> Result<()> {
+    create(&name, &instance_dir)?;
+    prune(&name, keep)
+}
+
+fn is_due(schedule: BackupSchedule, last: Option<&Backup>, now: OffsetDateTime) -> bool {
+    match schedule {
+        BackupSchedule::Manual => false,
+        BackupSchedule::OnLaunch => true,
+        BackupSchedule::Daily => last.is_none_or(|last| now - last.created >= Duration::DAY),
+    }
+}
+
+pub fn is_scheduled(name: &str, info: &Instance) -> bool {
+    let last = list(name).into_iter().next();
+
+    is_due(
+        info.backup_schedule,
+        last.as_ref(),
+        OffsetDateTime::now_utc(),
+    )
+}
+
+// called before the game starts
+pub async fn run_scheduled(name: String, instance_dir: PathBuf, info: Instance) -> Result<()> {
+    if is_scheduled(&name, &info) {
+        create(&name, &instance_dir)?;
+        prune(&name, info.backup_retention.unwrap_or(DEFAULT_RETENTION))?;
+    }
+
+    Ok(())
+}
+
+fn extract(backup: &Path, dest_dir: &Path) -> Result<()> {
+    let mut zip = ZipArchive::new(BufReader::new(File::open(backup)?))?;
+
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        let name = entry
+            .enclosed_name()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Invalid entry in {}", backup.display()))?;
+        let dest = dest_dir.join(name);
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        io::copy(&mut entry, &mut BufWriter::new(File::create(dest)?))?;
+    }
+
+    Ok(())
+}
+
+// moves `paths` from `from` to `to`, keeping track of what was moved
+fn swap(from: &Path, to: &Path, paths: &[&str], moved: &mut Vec<PathBuf>) -> Result<()> {
+    for path in paths {
+        if from.join(path).exists() {
+            fs::rename(from.join(path), to.join(path))?;
+            moved.push(PathBuf::from(path));
+        }
+    }
+
+    Ok(())
+}
+
+// replaces `paths` in `dir` with the ones in `source`, the ones missing there are
+// removed; if a move fails, what was in `dir` is put back
+pub(crate) fn swap_in(dir: &Path, source: &Path, paths: &[&str]) -> Result<()> {
+    let replaced = tempfile::tempdir_in(dir)?;
+    let mut moved = Vec::new();
+
+    let result = swap(dir, replaced.path(), paths, &mut moved)
+        .and_then(|_| swap(source, dir, paths, &mut Vec::new()));
+
+    if result.is_err() {
+        // whatever made it in from `source` goes
+        for path in moved {
+            let dest = dir.join(&path);
+            if dest.is_dir() {
+                fs::remove_dir_all(&dest)?;
+            } else if dest.is_file() {
+                fs::remove_file(&dest)?;
+            }
+            fs::rename(replaced.path().join(&path), dest)?;
+        }
+    }
+
+    result
+}
+
+// the backed up folders are replaced as a whole, the rest is left alone;
+// the backup is extracted next to the instance first, so a broken one changes nothing
+pub async fn restore(instance_dir: PathBuf, backup: PathBuf) -> Result<()> {
+    replace(&instance_dir, &backup)
+}
+
+fn replace(instance_dir: &Path, backup: &Path) -> Result<()> {
+    let extracted = tempfile::tempdir_in(instance_dir)?;
+    extract(backup, extracted.path())?;
+
+    let paths = BACKED_UP
+        .iter()
+        .flat_map(|part| part.paths())
+        .copied()
+        .collect::<Vec<_>>();
+
+    swap_in(instance_dir, extracted.path(), &paths)
+}
+
+pub fn delete(backup: &Path) -> Result<()> {
+    fs::remove_file(backup)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restores_only_complete_backups() {
+        let instance_dir = tempfile::tempdir().unwrap();
+        let saves = instance_dir.path().join("saves");
+        fs::create_dir_all(saves.join("World")).unwrap();
+        fs::write(instance_dir.path().join("options.txt"), "old").unwrap();
+
+        let backup = instance_dir.path().join("backup.zip");
+        let mut zip = ZipWriter::new(File::create(&backup).unwrap());
+        zip.start_file("options.txt", FileOptions::default())
+            .unwrap();
+        io::Write::write_all(&mut zip, b"new").unwrap();
+        zip.finish().unwrap();
+
+        replace(instance_dir.path(), &backup).unwrap();
+        assert_eq!(
+            fs::read_to_string(instance_dir.path().join("options.txt")).unwrap(),
+            "new"
+        );
+        assert!(!saves.exists());
+
+        // a broken backup leaves the instance as it was
+        let broken = instance_dir.path().join("broken.zip");
+        fs::write(&broken, "not a zip").unwrap();
+        assert!(replace(instance_dir.path(), &broken).is_err());
+        assert_eq!(
+            fs::read_to_string(instance_dir.path().join("options.txt")).unwrap(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn ignores_unfinished_backups() {
+        assert!(parse_backup(PathBuf::from("backup-0.part")).is_none());
+    }
+
+    #[test]
+    fn schedules_backups() {
+        let now = OffsetDateTime::now_utc();
+        let backup = |created| Backup {
+            path: PathBuf::new(),
+            created,
+            size: 0,
+        };
+
+        assert!(!is_due(BackupSchedule::Manual, None, now));
+        assert!(is_due(BackupSchedule::OnLaunch, Some(&backup(now)), now));
+        assert!(is_due(BackupSchedule::Daily, None, now));
+        assert!(!is_due(
+            BackupSchedule::Daily,
+            Some(&backup(now - Duration::HOUR)),
+            now
+        ));
+        assert!(is_due(
+            BackupSchedule::Daily,
+            Some(&backup(now - Duration::DAY)),
+            now
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    ffi::OsString,
+    fmt::{self, Display},
+    fs,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::paths::{ASSETS_DIR, META_DIR};
+use crate::AGENT;
+
+// what the server said about the cached copy, to ask whether it changed
+#[derive(Serialize, Deserialize, Default)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+fn get_validators_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".validators");
+
+    PathBuf::from(name)
+}
+
+// small documents that change now and then, like the version manifest; the
+// cached copy is used when the server says it's current, when offline or when
+// the server is failing
+pub fn fetch(url: &str, path: &Path) -> Result<String> {
+    let validators_path = get_validators_path(path);
+    let validators = if path.exists() {
+        fs::read_to_string(&validators_path)
+            .ok()
+            .and_then(|contents| toml::from_str::<Validators>(&contents).ok())
+            .unwrap_or_default()
+    } else {
+        Validators::default()
+    };
+
+    let mut request = AGENT.get(url);
+    if let Some(etag) = &validators.etag {
+        request = request.set("If-None-Match", etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.set("If-Modified-Since", last_modified);
+    }
+
+    let response = match request.call() {
+        Ok(response) => response,
+        Err(ureq::Error::Transport(error)) if path.exists() => {
+            tracing::warn!("using the cached {}: {}", path.display(), error);
+            return Ok(fs::read_to_string(path)?);
+        }
+        Err(ureq::Error::Status(status, _)) if status >= 500 && path.exists() => {
+            tracing::warn!("using the cached {}: status {}", path.display(), status);
+            return Ok(fs::read_to_string(path)?);
+        }
+        Err(error) => return Err(error).with_context(|| format!("Failed to download {url}")),
+    };
+
+    if response.status() == 304 {
+        tracing::debug!("{} is up to date", path.display());
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let validators = Validators {
+        etag: response.header("ETag").map(str::to_string),
+        last_modified: response.header("Last-Modified").map(str::to_string),
+    };
+    let contents = response.into_string()?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // the old copy stays valid until the new one is complete
+    let part_path = path.with_extension("part");
+    fs::write(&part_path, &contents)?;
+    fs::rename(&part_path, path)?;
+    fs::write(validators_path, toml::to_string(&validators)?)?;
+
+    Ok(contents)
+}
+
+// A JSON file the launcher downloaded and kept around
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedDocument {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Display for CachedDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+fn find_json(dir: &Path, prefix: &str, documents: &mut Vec<CachedDocument>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            find_json(&path, &format!("{prefix}{file_name}/"), documents)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            documents.push(CachedDocument {
+                name: format!("{prefix}{file_name}"),
+                path,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// version manifest and metas, asset indexes
+pub fn list_documents() -> Result<Vec<CachedDocument>> {
+    let mut documents = Vec::new();
+
+    find_json(&META_DIR, "meta/", &mut documents)?;
+    find_json(
+        &ASSETS_DIR.join("indexes"),
+        "assets/indexes/",
+        &mut documents,
+    )?;
+
+    documents.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(documents)
+}
+
+pub async fn read_pretty(path: PathBuf) -> Result<String> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let value = serde_json::from_reader::<_, serde_json::Value>(reader)?;
+    let pretty = serde_json::to_string_pretty(&value)?;
+
+    Ok(pretty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_validators_next_to_the_file() {
+        assert_eq!(
+            get_validators_path(Path::new("meta/version_manifest_v2.json")),
+            Path::new("meta/version_manifest_v2.json.validators")
+        );
+    }
+}
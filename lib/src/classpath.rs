@@ -0,0 +1,202 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{collections::HashMap, fmt::Write, path::PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::fabric::FabricMeta;
+use crate::instances::{Instance, Loader};
+use crate::vanilla_installer::VersionMeta;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    Instance,
+    Loader,
+    Version,
+    Library,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Node {
+    pub id: String,
+    pub kind: NodeKind,
+    pub path: Option<PathBuf>,
+    // order on the classpath, the first match of a class wins
+    pub position: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+}
+
+// the same artifact in different versions, only the first one is used
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    pub artifact: String,
+    pub versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClasspathGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl ClasspathGraph {
+    pub fn new(name: &str, instance: &Instance) -> Result<Self> {
+        let mut graph = Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            conflicts: Vec::new(),
+        };
+
+        graph.push_node(name, NodeKind::Instance, None);
+
+        let version_meta = VersionMeta::load(&instance.minecraft)?;
+        let version = format!("minecraft {}", instance.minecraft);
+        let mut libraries = Vec::new();
+
+        // loader libraries come first on the classpath and the loader inherits from the version
+        let parent = match (instance.loader, &instance.loader_version) {
+            (Loader::Fabric, Some(loader_version)) => {
+                let fabric_meta = FabricMeta::load(&instance.minecraft, loader_version)?;
+                let loader = format!("fabric {loader_version}");
+
+                graph.push_node(&loader, NodeKind::Loader, None);
+                graph.push_edge(name, &loader);
+
+                for library in fabric_meta.get_libraries() {
+                    libraries.push((loader.clone(), library));
+                }
+
+                loader
+            }
+            _ => name.to_string(),
+        };
+
+        graph.push_node(&version, NodeKind::Version, None);
+        graph.push_edge(&parent, &version);
+
+        libraries.push((version.clone(), version_meta.get_client()));
+        for library in version_meta.get_libraries() {
+            libraries.push((version.clone(), library));
+        }
+
+        for (position, (owner, (id, path))) in libraries.into_iter().enumerate() {
+            graph.push_node(&id, NodeKind::Library, Some((path, position)));
+            graph.push_edge(&owner, &id);
+        }
+
+        graph.find_conflicts();
+
+        Ok(graph)
+    }
+
+    fn push_node(&mut self, id: &str, kind: NodeKind, library: Option<(PathBuf, usize)>) {
+        let (path, position) = library.unzip();
+
+        self.nodes.push(Node {
+            id: id.to_string(),
+            kind,
+            path,
+            position,
+        });
+    }
+
+    fn push_edge(&mut self, from: &str, to: &str) {
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+
+    fn find_conflicts(&mut self) {
+        let mut artifacts = HashMap::<String, Vec<String>>::new();
+        let mut order = Vec::new();
+
+        for node in self.nodes.iter().filter(|n| n.kind == NodeKind::Library) {
+            // group:artifact:version[:classifier]
+            let parts = node.id.split(':').collect::<Vec<_>>();
+            if parts.len() < 3 {
+                continue;
+            }
+
+            let mut artifact = format!("{}:{}", parts[0], parts[1]);
+            if let Some(classifier) = parts.get(3) {
+                artifact.push(':');
+                artifact.push_str(classifier);
+            }
+
+            let versions = artifacts.entry(artifact.clone()).or_insert_with(|| {
+                order.push(artifact);
+                Vec::new()
+            });
+
+            if !versions.iter().any(|v| v == parts[2]) {
+                versions.push(parts[2].to_string());
+            }
+        }
+
+        for artifact in order {
+            let versions = artifacts.remove(&artifact).unwrap_or_default();
+
+            if versions.len() > 1 {
+                self.conflicts.push(Conflict { artifact, versions });
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let json = serde_json::to_string_pretty(self)?;
+        Ok(json)
+    }
+
+    // graphviz, e.g. `dot -Tsvg graph.dot -o graph.svg`
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph classpath {\n    rankdir=LR;\n");
+
+        let conflicting = self
+            .conflicts
+            .iter()
+            .map(|conflict| format!("{}:", conflict.artifact))
+            .collect::<Vec<_>>();
+
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Instance => "house",
+                NodeKind::Loader | NodeKind::Version => "box",
+                NodeKind::Library => "ellipse",
+            };
+
+            let label = match node.position {
+                Some(position) => format!("{} (#{})", node.id, position),
+                None => node.id.clone(),
+            };
+
+            let color = if conflicting.iter().any(|c| node.id.starts_with(c)) {
+                ", color=red"
+            } else {
+                ""
+            };
+
+            let _ = writeln!(
+                dot,
+                "    {:?} [label={:?}, shape={}{}];",
+                node.id, label, shape, color
+            );
+        }
+
+        for edge in &self.edges {
+            let _ = writeln!(dot, "    {:?} -> {:?};", edge.from, edge.to);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,186 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::accounts::{Account, Accounts};
+use crate::instances::{check_name, Instance, Instances};
+use crate::settings::Settings;
+
+const SETTINGS_ENTRY: &str = "settings.toml";
+const ACCOUNTS_ENTRY: &str = "accounts.toml";
+const INSTANCES_PREFIX: &str = "instances/";
+
+// tokens never leave the machine, online accounts have to sign in again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedAccount {
+    pub id: String,
+    pub name: String,
+    pub offline: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ExportedAccounts {
+    accounts: Vec<ExportedAccount>,
+}
+
+// only the configuration, game files are downloaded again
+pub struct ConfigArchive {
+    pub settings: Settings,
+    pub accounts: Vec<ExportedAccount>,
+    pub instances: Vec<(String, Instance)>,
+}
+
+pub fn export(
+    path: &Path,
+    settings: &Settings,
+    accounts: &Accounts,
+    instances: &Instances,
+) -> Result<()> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = FileOptions::default();
+
+    // the instances folder is specific to this machine
+    let mut settings = toml::Table::try_from(settings)?;
+    settings.remove("instances_dir");
+    zip.start_file(SETTINGS_ENTRY, options)?;
+    zip.write_all(toml::to_string_pretty(&settings)?.as_bytes())?;
+
+    let accounts = ExportedAccounts {
+        accounts: accounts
+            .list()
+            .into_iter()
+            .map(|account| ExportedAccount {
+                offline: account.is_offline(),
+                id: account.mc_id,
+                name: account.mc_username,
+            })
+            .collect(),
+    };
+    zip.start_file(ACCOUNTS_ENTRY, options)?;
+    zip.write_all(toml::to_string_pretty(&accounts)?.as_bytes())?;
+
+    for (name, info) in &instances.list {
+        zip.start_file(format!("{INSTANCES_PREFIX}{name}.toml"), options)?;
+        zip.write_all(info.to_toml()?.as_bytes())?;
+    }
+
+    zip.finish()?;
+
+    Ok(())
+}
+
+pub fn read(path: &Path) -> Result<ConfigArchive> {
+    let mut zip = ZipArchive::new(File::open(path)?)?;
+
+    let mut settings = String::new();
+    zip.by_name(SETTINGS_ENTRY)
+        .map_err(|_| anyhow!("Not a launcher configuration: {}", path.display()))?
+        .read_to_string(&mut settings)?;
+    let settings = toml::from_str(&settings)?;
+
+    let accounts = match zip.by_name(ACCOUNTS_ENTRY) {
+        Ok(mut entry) => {
+            let mut accounts = String::new();
+            entry.read_to_string(&mut accounts)?;
+            toml::from_str::<ExportedAccounts>(&accounts)?.accounts
+        }
+        Err(_) => Vec::new(),
+    };
+
+    let mut instances = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+
+        let Some(name) = entry
+            .name()
+            .strip_prefix(INSTANCES_PREFIX)
+            .and_then(|name| name.strip_suffix(".toml"))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+
+        check_name(&name)?;
+
+        let mut info = String::new();
+        entry.read_to_string(&mut info)?;
+        instances.push((name, Instance::from_toml(&info)?));
+    }
+
+    Ok(ConfigArchive {
+        settings,
+        accounts,
+        instances,
+    })
+}
+
+// returns the online accounts that have to sign in again
+pub fn import_accounts(
+    accounts: &mut Accounts,
+    imported: Vec<ExportedAccount>,
+) -> Result<Vec<String>> {
+    let mut sign_in = Vec::new();
+
+    for account in imported {
+        if accounts.find(&account.id).is_some() {
+            continue;
+        }
+
+        if account.offline {
+            accounts.add_account(Account::new_offline(account.name))?;
+        } else {
+            sign_in.push(account.name);
+        }
+    }
+
+    Ok(sign_in)
+}
+
+// existing instances keep their files, either their config is replaced or the
+// imported one is added next to them; returns the names that were written
+pub fn import_instances(
+    instances: &mut Instances,
+    imported: Vec<(String, Instance)>,
+    replace: bool,
+) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+
+    for (name, mut info) in imported {
+        check_name(&name)?;
+        if instances.get_dir(&name).parent() != Some(instances.get_base_dir()) {
+            bail!("Invalid instance name in the archive: {}", name);
+        }
+
+        // custom java binaries are specific to the old machine
+        info.java_path = None;
+
+        let name = if !instances.list.contains_key(&name) {
+            instances.create(name.clone(), info)?;
+            name
+        } else if replace {
+            instances.list.insert(name.clone(), info);
+            instances.save(&name)?;
+            name
+        } else {
+            let name = (2..)
+                .map(|i| format!("{name} ({i})"))
+                .find(|name| !instances.list.contains_key(name))
+                .unwrap();
+
+            instances.create(name.clone(), info)?;
+            name
+        };
+
+        names.push(name);
+    }
+
+    Ok(names)
+}
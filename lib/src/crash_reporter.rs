@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::backtrace::Backtrace;
+use std::path::PathBuf;
+use std::{env, fs, panic};
+
+use time::OffsetDateTime;
+
+use crate::paths::{CRASHES_DIR, LOGS_DIR};
+
+const RECENT_LOG_LINES: usize = 50;
+
+// github rejects longer urls, the full dump stays on disk
+const MAX_ISSUE_BODY: usize = 6000;
+
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+impl CrashReport {
+    pub fn issue_body(&self) -> String {
+        let contents = match self.contents.char_indices().nth(MAX_ISSUE_BODY) {
+            Some((end, _)) => &self.contents[..end],
+            None => &self.contents,
+        };
+
+        format!("The launcher crashed.\n\n```\n{contents}\n```")
+    }
+}
+
+fn last_lines(text: &str, count: usize) -> String {
+    let lines = text.lines().collect::<Vec<_>>();
+
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+// the most recently written log file, the buffered lines may not be in there yet
+fn recent_log_lines() -> String {
+    fs::read_dir(&*LOGS_DIR)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        })
+        .and_then(|entry| fs::read_to_string(entry.path()).ok())
+        .map(|text| last_lines(&text, RECENT_LOG_LINES))
+        .unwrap_or_default()
+}
+
+fn format_report(launcher_version: &str, panic: &str, backtrace: &str, logs: &str) -> String {
+    format!(
+        "Launcher: {}\nOS: {} ({})\n\n{}\n\nBacktrace:\n{}\n\nRecent log lines:\n{}\n",
+        launcher_version,
+        env::consts::OS,
+        env::consts::ARCH,
+        panic,
+        backtrace,
+        logs,
+    )
+}
+
+// writes a crash dump to the crashes folder before the default hook runs
+pub fn install_hook(launcher_version: &'static str) {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        tracing::error!("{info}");
+
+        let report = format_report(
+            launcher_version,
+            &info.to_string(),
+            &Backtrace::force_capture().to_string(),
+            &recent_log_lines(),
+        );
+
+        let path = CRASHES_DIR.join(format!(
+            "crash-{}.txt",
+            OffsetDateTime::now_utc().unix_timestamp()
+        ));
+
+        if fs::write(&path, report).is_ok() {
+            eprintln!("Crash report saved to {}", path.display());
+        }
+
+        default_hook(info);
+    }));
+}
+
+// the newest crash that wasn't offered yet; every pending one is marked as seen
+pub fn take_last() -> Option<CrashReport> {
+    let mut pending = fs::read_dir(&*CRASHES_DIR)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<_>>();
+
+    // the timestamps in the names sort chronologically
+    pending.sort();
+
+    for path in &pending {
+        if let Err(error) = fs::rename(path, path.with_extension("seen")) {
+            tracing::warn!("failed to mark {} as seen: {error}", path.display());
+        }
+    }
+
+    let path = pending.pop()?.with_extension("seen");
+    let contents = fs::read_to_string(&path).ok()?;
+
+    Some(CrashReport { path, contents })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_last_log_lines() {
+        assert_eq!(last_lines("a\nb\nc", 2), "b\nc");
+        assert_eq!(last_lines("a", 2), "a");
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anyhow::{anyhow, bail, Result};
+
+pub const SCHEME: &str = "icy-launcher";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    // icy-launcher://install/modrinth/<project>
+    InstallModrinth(String),
+    // icy-launcher://instance/<name>
+    OpenInstance(String),
+    // icy-launcher://view/<page>, the page names of --view
+    OpenView(String),
+    // icy-launcher://open, sent by a second launch to bring the window up
+    Open,
+}
+
+impl DeepLink {
+    pub fn to_url(&self) -> String {
+        let path = match self {
+            DeepLink::InstallModrinth(project) => format!("install/modrinth/{}", encode(project)),
+            DeepLink::OpenInstance(name) => format!("instance/{}", encode(name)),
+            DeepLink::OpenView(page) => format!("view/{}", encode(page)),
+            DeepLink::Open => "open".to_string(),
+        };
+
+        format!("{SCHEME}://{path}")
+    }
+}
+
+pub fn parse(url: &str) -> Result<DeepLink> {
+    let path = url
+        .strip_prefix(SCHEME)
+        .and_then(|rest| rest.strip_prefix("://"))
+        .ok_or_else(|| anyhow!("Not a {} link: {}", SCHEME, url))?;
+
+    let parts = path
+        .trim_end_matches('/')
+        .split('/')
+        .map(decode)
+        .collect::<Result<Vec<_>>>()?;
+
+    match parts.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["install", "modrinth", project] if !project.is_empty() => {
+            Ok(DeepLink::InstallModrinth(project.to_string()))
+        }
+        ["instance", name] if !name.is_empty() => Ok(DeepLink::OpenInstance(name.to_string())),
+        ["view", page] if !page.is_empty() => Ok(DeepLink::OpenView(page.to_string())),
+        ["open"] => Ok(DeepLink::Open),
+        _ => bail!("Unsupported link: {}", url),
+    }
+}
+
+// percent-decoding, browsers encode spaces in instance names
+pub(crate) fn decode(part: &str) -> Result<String> {
+    let mut bytes = Vec::new();
+    let mut iter = part.bytes();
+
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [
+                iter.next().unwrap_or_default(),
+                iter.next().unwrap_or_default(),
+            ];
+            let hex = std::str::from_utf8(&hex)?;
+            bytes.push(u8::from_str_radix(hex, 16)?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+// everything but the unreserved characters of RFC 3986
+pub(crate) fn encode(part: &str) -> String {
+    let mut encoded = String::new();
+
+    for byte in part.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+// makes the os open links with this executable, unless it already does
+#[cfg(target_os = "linux")]
+pub fn register() -> Result<()> {
+    use std::{fs, process};
+
+    let exe = std::env::current_exe()?;
+    let dir = directories::BaseDirs::new()
+        .ok_or_else(|| anyhow!("No home directory"))?
+        .data_dir()
+        .join("applications");
+
+    let file_name = format!("{SCHEME}-url.desktop");
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=CrabLauncher\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{SCHEME};\n",
+        exe.display()
+    );
+
+    let handler = process::Command::new("xdg-mime")
+        .args(["query", "default"])
+        .arg(format!("x-scheme-handler/{SCHEME}"))
+        .output()?;
+    let registered = String::from_utf8_lossy(&handler.stdout).trim() == file_name
+        && fs::read_to_string(dir.join(&file_name)).is_ok_and(|current| current == entry);
+    if registered {
+        return Ok(());
+    }
+
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(&file_name), entry)?;
+
+    let status = process::Command::new("xdg-mime")
+        .args(["default", &file_name])
+        .arg(format!("x-scheme-handler/{SCHEME}"))
+        .status()?;
+
+    if !status.success() {
+        bail!("xdg-mime failed: {}", status);
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub fn register() -> Result<()> {
+    use std::process;
+
+    let exe = std::env::current_exe()?;
+    let key = format!("HKCU\\Software\\Classes\\{SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+
+    let current = process::Command::new("reg")
+        .args(["query", &format!("{key}\\shell\\open\\command"), "/ve"])
+        .output()?;
+    if current.status.success() && String::from_utf8_lossy(&current.stdout).contains(&command) {
+        return Ok(());
+    }
+
+    let entries = [
+        (key.clone(), None, format!("URL:{SCHEME}")),
+        (key.clone(), Some("URL Protocol"), String::new()),
+        (format!("{key}\\shell\\open\\command"), None, command),
+    ];
+
+    for (key, value, data) in entries {
+        let mut reg = process::Command::new("reg");
+        reg.args(["add", &key]);
+
+        match value {
+            Some(value) => reg.args(["/v", value]),
+            None => reg.arg("/ve"),
+        };
+
+        let status = reg.args(["/d", &data, "/f"]).status()?;
+        if !status.success() {
+            bail!("Failed to register {}: {}", key, status);
+        }
+    }
+
+    Ok(())
+}
+
+// on macOS the app bundle would have to declare the scheme, and links arrive as apple events
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn register() -> Result<()> {
+    bail!("{} links aren't supported on this platform yet", SCHEME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_links() {
+        assert_eq!(
+            parse("icy-launcher://install/modrinth/AANobbMI").unwrap(),
+            DeepLink::InstallModrinth("AANobbMI".to_string())
+        );
+        assert_eq!(
+            parse("icy-launcher://instance/My%20Instance/").unwrap(),
+            DeepLink::OpenInstance("My Instance".to_string())
+        );
+        assert!(parse("icy-launcher://install/curseforge/123").is_err());
+        assert!(parse("crab-launcher://instance/Old").is_err());
+        assert!(parse("https://modrinth.com").is_err());
+    }
+
+    #[test]
+    fn builds_links() {
+        for link in [
+            DeepLink::OpenInstance("My Instance (1.20%)".to_string()),
+            DeepLink::OpenView("settings".to_string()),
+            DeepLink::Open,
+        ] {
+            assert_eq!(parse(&link.to_url()).unwrap(), link);
+        }
+    }
+}
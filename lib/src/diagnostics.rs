@@ -0,0 +1,177 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use time::OffsetDateTime;
+
+const GC_LOG: &str = "gc.log";
+const RECORDING: &str = "recording.jfr";
+
+// creates the directory a profiled session writes its logs to
+pub fn new_session_dir(instance_dir: &Path) -> Result<PathBuf> {
+    let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+    let dir = instance_dir.join("diagnostics").join(timestamp.to_string());
+    fs::create_dir_all(&dir)?;
+
+    Ok(dir)
+}
+
+pub fn get_flags(session_dir: &Path, java_major: u32) -> Vec<String> {
+    let gc_log = session_dir.join(GC_LOG);
+
+    // java 8 predates unified logging and open source JFR
+    if java_major < 11 {
+        return vec![
+            format!("-Xloggc:{}", gc_log.display()),
+            "-XX:+PrintGCDetails".to_string(),
+            "-XX:+PrintGCDateStamps".to_string(),
+        ];
+    }
+
+    vec![
+        format!("-Xlog:gc*:file={}:uptime,level,tags", gc_log.display()),
+        format!(
+            "-XX:StartFlightRecording=filename={},settings=profile",
+            session_dir.join(RECORDING).display()
+        ),
+    ]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcSummary {
+    pub pauses: usize,
+    pub total_pause_ms: f64,
+    pub max_pause_ms: f64,
+    // MiB/s, only known if the log has heap transitions
+    pub allocation_rate: Option<f64>,
+}
+
+impl GcSummary {
+    pub fn average_pause_ms(&self) -> f64 {
+        if self.pauses == 0 {
+            0.
+        } else {
+            self.total_pause_ms / self.pauses as f64
+        }
+    }
+}
+
+fn parse_size(size: &str) -> Option<f64> {
+    let (number, unit) = size.split_at(size.len().checked_sub(1)?);
+    let number = number.parse::<f64>().ok()?;
+
+    match unit {
+        "K" => Some(number / 1024.),
+        "M" => Some(number),
+        "G" => Some(number * 1024.),
+        _ => None,
+    }
+}
+
+// e.g. [0.512s][info][gc] GC(3) Pause Young (Normal) (G1 Evacuation Pause) 51M->10M(256M) 4.123ms
+pub fn parse_gc_log(content: &str) -> GcSummary {
+    let mut summary = GcSummary {
+        pauses: 0,
+        total_pause_ms: 0.,
+        max_pause_ms: 0.,
+        allocation_rate: None,
+    };
+
+    let mut allocated = 0.;
+    let mut first_uptime = None;
+    let mut last_uptime = None;
+    let mut last_after = None;
+
+    for line in content.lines() {
+        if !line.contains("Pause") {
+            continue;
+        }
+
+        let Some(pause) = line
+            .rsplit(' ')
+            .next()
+            .and_then(|token| token.strip_suffix("ms"))
+            .and_then(|ms| ms.parse::<f64>().ok())
+        else {
+            continue;
+        };
+
+        summary.pauses += 1;
+        summary.total_pause_ms += pause;
+        summary.max_pause_ms = summary.max_pause_ms.max(pause);
+
+        let uptime = line
+            .strip_prefix('[')
+            .and_then(|line| line.split(']').next())
+            .and_then(|uptime| uptime.strip_suffix('s'))
+            .and_then(|uptime| uptime.parse::<f64>().ok());
+
+        // e.g. 51M->10M(256M)
+        let transition = line.split(' ').find_map(|token| {
+            let (before, after) = token.split_once("->")?;
+            let after = after.split('(').next()?;
+            Some((parse_size(before)?, parse_size(after)?))
+        });
+
+        if let (Some(uptime), Some((before, after))) = (uptime, transition) {
+            if let Some(last_after) = last_after {
+                allocated += before - last_after;
+            }
+
+            first_uptime.get_or_insert(uptime);
+            last_uptime = Some(uptime);
+            last_after = Some(after);
+        }
+    }
+
+    if let (Some(first), Some(last)) = (first_uptime, last_uptime) {
+        if last > first {
+            summary.allocation_rate = Some(allocated / (last - first));
+        }
+    }
+
+    summary
+}
+
+// the summary of the most recent profiled session
+pub fn get_latest_summary(instance_dir: &Path) -> Option<GcSummary> {
+    let latest = fs::read_dir(instance_dir.join("diagnostics"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let timestamp = entry.file_name().to_string_lossy().parse::<i64>().ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .max_by_key(|(timestamp, _)| *timestamp)?;
+
+    let content = fs::read_to_string(latest.1.join(GC_LOG)).ok()?;
+
+    Some(parse_gc_log(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gc_log() {
+        let content = "\
+[0.010s][info][gc,init] Heap Region Size: 1M
+[1.000s][info][gc,start    ] GC(0) Pause Young (Normal) (G1 Evacuation Pause)
+[1.000s][info][gc          ] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 50M->10M(256M) 4.000ms
+[3.000s][info][gc          ] GC(1) Pause Young (Normal) (G1 Evacuation Pause) 110M->20M(256M) 6.000ms
+";
+
+        let summary = parse_gc_log(content);
+        assert_eq!(summary.pauses, 2);
+        assert_eq!(summary.total_pause_ms, 10.);
+        assert_eq!(summary.max_pause_ms, 6.);
+        assert_eq!(summary.average_pause_ms(), 5.);
+        assert_eq!(summary.allocation_rate, Some(50.));
+    }
+}
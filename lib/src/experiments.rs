@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+
+use crate::backups;
+use crate::instances::copy_dir;
+
+// what an experiment can change, relative to the instance directory
+const TRACKED: &[&str] = &["config", "mods", "options.txt"];
+
+fn get_snapshot_dir(instance_dir: &Path) -> PathBuf {
+    instance_dir.join(".experiment")
+}
+
+pub fn is_active(instance_dir: &Path) -> bool {
+    get_snapshot_dir(instance_dir).exists()
+}
+
+// snapshot the configs and mods, so they can be restored later
+pub fn start(instance_dir: &Path) -> Result<()> {
+    if is_active(instance_dir) {
+        bail!("An experiment is already in progress");
+    }
+
+    // written to a temporary dir first, so a failed copy isn't mistaken for a
+    // snapshot; it's removed if the copy fails
+    let tmp = tempfile::tempdir_in(instance_dir)?;
+
+    for entry in TRACKED {
+        let src = instance_dir.join(entry);
+
+        if src.is_dir() {
+            copy_dir(&src, &tmp.path().join(entry))?;
+        } else if src.is_file() {
+            fs::copy(&src, tmp.path().join(entry))?;
+        }
+    }
+
+    fs::rename(tmp.into_path(), get_snapshot_dir(instance_dir))?;
+
+    Ok(())
+}
+
+// keep the changes
+pub fn commit(instance_dir: &Path) -> Result<()> {
+    if !is_active(instance_dir) {
+        bail!("No experiment in progress");
+    }
+
+    fs::remove_dir_all(get_snapshot_dir(instance_dir))?;
+
+    Ok(())
+}
+
+// put back everything as it was when the experiment started
+pub fn rollback(instance_dir: &Path) -> Result<()> {
+    let snapshot_dir = get_snapshot_dir(instance_dir);
+    if !snapshot_dir.exists() {
+        bail!("No experiment in progress");
+    }
+
+    // the current files are only dropped once the snapshot is in place
+    backups::swap_in(instance_dir, &snapshot_dir, TRACKED)?;
+    fs::remove_dir_all(snapshot_dir)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_back_to_the_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let instance_dir = dir.path();
+        fs::create_dir(instance_dir.join("mods")).unwrap();
+        fs::write(instance_dir.join("mods").join("old.jar"), "").unwrap();
+
+        start(instance_dir).unwrap();
+        fs::write(instance_dir.join("mods").join("new.jar"), "").unwrap();
+        fs::write(instance_dir.join("options.txt"), "changed").unwrap();
+
+        rollback(instance_dir).unwrap();
+        assert!(!is_active(instance_dir));
+        assert!(instance_dir.join("mods").join("old.jar").exists());
+        assert!(!instance_dir.join("mods").join("new.jar").exists());
+        assert!(!instance_dir.join("options.txt").exists());
+    }
+}
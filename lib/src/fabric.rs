@@ -1,14 +1,16 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::fs::File;
+use std::io::BufReader;
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::Deserialize;
 
-use crate::instances::Instance;
-use crate::paths::LIBRARIES_DIR;
-use crate::{DownloadItem, AGENT};
+use crate::cache;
+use crate::paths::{LIBRARIES_DIR, META_DIR};
+use crate::DownloadItem;
 
 #[derive(Deserialize)]
 struct FabricLibrary {
@@ -39,38 +41,90 @@ impl FabricLibrary {
 }
 
 #[derive(Deserialize)]
-struct FabricMeta {
+pub struct FabricMeta {
     libraries: Vec<FabricLibrary>,
     #[serde(rename = "mainClass")]
-    main_class: String,
+    pub main_class: String,
 }
 
-fn download(minecraft_version: &str, fabric_version: &str) -> Result<Vec<DownloadItem>> {
-    let url = format!("https://meta.fabricmc.net/v2/versions/loader/{minecraft_version}/{fabric_version}/profile/json", );
-    let meta = AGENT.get(&url).call()?.into_json::<FabricMeta>()?;
+fn get_meta_path(minecraft_version: &str, fabric_version: &str) -> PathBuf {
+    META_DIR
+        .join("fabric")
+        .join(format!("{minecraft_version}-{fabric_version}.json"))
+}
 
-    meta.libraries
-        .into_iter()
-        .map(|lib| {
-            Ok(DownloadItem {
-                url: lib.get_download_url(),
-                path: lib.get_full_path(),
-                hash: None,
-                extract: false,
-            })
-        })
-        .collect()
+impl FabricMeta {
+    pub fn load(minecraft_version: &str, fabric_version: &str) -> Result<Self> {
+        let file = File::open(get_meta_path(minecraft_version, fabric_version))?;
+        let reader = BufReader::new(file);
+        let meta = serde_json::from_reader(reader)?;
+
+        Ok(meta)
+    }
+
+    // by maven coordinates
+    pub fn get_libraries(&self) -> Vec<(String, PathBuf)> {
+        self.libraries
+            .iter()
+            .map(|lib| (lib.name.clone(), lib.get_full_path()))
+            .collect()
+    }
+
+    // prepended to the vanilla classpath
+    pub fn get_paths(&self) -> Vec<PathBuf> {
+        self.libraries
+            .iter()
+            .map(FabricLibrary::get_full_path)
+            .collect()
+    }
 }
 
-/*
-pub fn install(instance: &mut Instance, fabric_version: &str) -> Result<Vec<DownloadItem>> {
-    let minecraft_version = &instance.info.minecraft;
+pub async fn get_latest_loader(minecraft_version: String) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Loader {
+        version: String,
+        stable: bool,
+    }
 
-    let downloads = download(minecraft_version, fabric_version)?;
+    #[derive(Deserialize)]
+    struct LoaderVersion {
+        loader: Loader,
+    }
+
+    let url = format!("https://meta.fabricmc.net/v2/versions/loader/{minecraft_version}");
+    let path = META_DIR
+        .join("fabric")
+        .join(format!("loaders-{minecraft_version}.json"));
+    let versions = serde_json::from_str::<Vec<LoaderVersion>>(&cache::fetch(&url, &path)?)?;
+
+    versions
+        .into_iter()
+        .find(|v| v.loader.stable)
+        .map(|v| v.loader.version)
+        .ok_or_else(|| anyhow!("Fabric doesn't support Minecraft {}", minecraft_version))
+}
 
-    instance.info.fabric = Some(fabric_version.to_string());
-    instance.save_info()?;
+pub fn install(minecraft_version: &str, fabric_version: &str) -> Result<Vec<DownloadItem>> {
+    let meta = DownloadItem {
+        url: format!("https://meta.fabricmc.net/v2/versions/loader/{minecraft_version}/{fabric_version}/profile/json"),
+        path: get_meta_path(minecraft_version, fabric_version),
+        hash: None,
+        size: None,
+        extract: false,
+    }
+    .download_json::<FabricMeta>()?;
+
+    let items = meta
+        .libraries
+        .into_iter()
+        .map(|lib| DownloadItem {
+            url: lib.get_download_url(),
+            path: lib.get_full_path(),
+            hash: None,
+            size: None,
+            extract: false,
+        })
+        .collect();
 
-    Ok(downloads)
+    Ok(items)
 }
-*/
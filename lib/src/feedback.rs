@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{collections::BTreeSet, env, fs};
+
+use anyhow::Result;
+use oauth2::url::Url;
+
+use crate::instances::Instances;
+use crate::paths::RUNTIMES_DIR;
+
+// Nothing personal in here: no instance names, usernames or paths
+pub fn get_summary(launcher_version: &str, instances: &Instances) -> String {
+    let versions = instances
+        .list
+        .values()
+        .map(|info| format!("{} ({:?})", info.minecraft, info.loader))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let runtimes = fs::read_dir(&*RUNTIMES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+
+    format!(
+        "- Launcher: {}\n- OS: {} ({})\n- Instances: {}\n- Versions: {}\n- Java runtimes: {}",
+        launcher_version,
+        env::consts::OS,
+        env::consts::ARCH,
+        instances.list.len(),
+        versions,
+        runtimes,
+    )
+}
+
+pub fn get_issue_url(
+    repository: &str,
+    title: &str,
+    description: &str,
+    summary: Option<&str>,
+) -> Result<String> {
+    let mut body = description.to_string();
+
+    if let Some(summary) = summary {
+        body.push_str("\n\n### System information\n\n");
+        body.push_str(summary);
+    }
+
+    let url = Url::parse_with_params(
+        &format!("{repository}/issues/new"),
+        &[("title", title), ("body", &body)],
+    )?;
+
+    Ok(url.to_string())
+}
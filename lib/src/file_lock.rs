@@ -0,0 +1,181 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+
+    PathBuf::from(name)
+}
+
+// the lock lives next to the file, so it survives the file being replaced
+fn get_lock_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".lock")
+}
+
+// the previous version, in case the current one can't be parsed
+fn get_backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+// advisory, it only keeps out other launcher processes; released on drop
+fn lock(path: &Path, exclusive: bool) -> Result<File> {
+    let lock_path = get_lock_path(path);
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+
+    if exclusive {
+        file.lock()?;
+    } else {
+        file.lock_shared()?;
+    }
+
+    Ok(file)
+}
+
+// the caller holds the exclusive lock
+fn restore_backup<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T>,
+    error: anyhow::Error,
+) -> Result<T> {
+    let backup_path = get_backup_path(path);
+    let Ok(value) = fs::read_to_string(&backup_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| parse(&contents))
+    else {
+        return Err(error);
+    };
+
+    tracing::warn!("restoring {} from its backup: {error:#}", path.display());
+    fs::copy(&backup_path, path)?;
+
+    Ok(value)
+}
+
+// the caller holds the exclusive lock
+fn replace(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let temp_path = with_suffix(path, ".tmp");
+    fs::write(&temp_path, contents)?;
+
+    if path.exists() {
+        fs::copy(path, get_backup_path(path))?;
+    }
+
+    fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+// for state shared between launcher processes (accounts, settings, instances);
+// when the file is damaged the backup is parsed and put back in its place
+pub fn read<T>(path: &Path, parse: impl Fn(&str) -> Result<T>) -> Result<T> {
+    let error = {
+        let _lock = lock(path, false)?;
+
+        match parse(&fs::read_to_string(path)?) {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        }
+    };
+
+    let _lock = lock(path, true)?;
+    restore_backup(path, parse, error)
+}
+
+// the new contents are written aside and moved in place, so the file is never
+// left half-written; the old one is kept as the backup
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let _lock = lock(path, true)?;
+
+    replace(path, contents)
+}
+
+// read-modify-write under a single exclusive lock: the change is applied to
+// what's on disk now, so changes another process made since this one read the
+// file aren't overwritten; returns the new value
+pub fn update<T>(
+    path: &Path,
+    parse: impl Fn(&str) -> Result<T>,
+    serialize: impl Fn(&T) -> Result<String>,
+    modify: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<T> {
+    let _lock = lock(path, true)?;
+
+    let mut value = match parse(&fs::read_to_string(path)?) {
+        Ok(value) => value,
+        Err(error) => restore_backup(path, &parse, error)?,
+    };
+
+    modify(&mut value)?;
+    replace(path, serialize(&value)?)?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Result<toml::Table> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    #[test]
+    fn keeps_the_lock_next_to_the_file() {
+        assert_eq!(
+            get_lock_path(Path::new("accounts.toml")),
+            Path::new("accounts.toml.lock")
+        );
+    }
+
+    #[test]
+    fn restores_a_damaged_file_from_its_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("accounts.toml");
+
+        write(&path, "a = 1").unwrap();
+        write(&path, "a = 2").unwrap();
+        fs::write(&path, "a = ").unwrap();
+
+        let table = read(&path, parse).unwrap();
+        assert_eq!(table["a"].as_integer(), Some(1));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1");
+    }
+
+    #[test]
+    fn updates_what_is_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("accounts.toml");
+
+        write(&path, "a = 1").unwrap();
+        let stale = read(&path, parse).unwrap();
+        // another process changes the file in the meantime
+        write(&path, "a = 1\nb = 2").unwrap();
+
+        let table = update(
+            &path,
+            parse,
+            |table| Ok(toml::to_string(table)?),
+            |table| {
+                table.insert("c".to_string(), 3.into());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert!(!stale.contains_key("b"));
+        assert_eq!(table["b"].as_integer(), Some(2));
+        assert_eq!(read(&path, parse).unwrap(), table);
+    }
+}
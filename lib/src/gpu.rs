@@ -0,0 +1,211 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Result;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gpu {
+    // what's stored in the instance config
+    pub id: String,
+    pub name: String,
+}
+
+impl fmt::Display for Gpu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+#[cfg(target_os = "linux")]
+const NVIDIA_VENDOR: &str = "0x10de";
+
+#[cfg(target_os = "linux")]
+fn get_vendor_name(vendor: &str) -> &'static str {
+    match vendor {
+        NVIDIA_VENDOR => "NVIDIA",
+        "0x1002" => "AMD",
+        "0x8086" => "Intel",
+        _ => "Unknown",
+    }
+}
+
+// the pci slot of every card, e.g. 0000:01:00.0
+#[cfg(target_os = "linux")]
+pub async fn detect() -> Vec<Gpu> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+
+    let mut gpus = entries
+        .filter_map(|entry| entry.ok())
+        // connectors are listed as card0-HDMI-A-1 and so on
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("card"))
+                .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|entry| {
+            let device = entry.path().join("device");
+            let slot = fs::read_link(&device)
+                .ok()?
+                .file_name()?
+                .to_str()?
+                .to_string();
+            let vendor = fs::read_to_string(device.join("vendor")).ok()?;
+            let primary = fs::read_to_string(device.join("boot_vga"))
+                .is_ok_and(|boot_vga| boot_vga.trim() == "1");
+
+            let mut name = format!("{} ({slot})", get_vendor_name(vendor.trim()));
+            if primary {
+                name.push_str(" *");
+            }
+
+            Some(Gpu { id: slot, name })
+        })
+        .collect::<Vec<_>>();
+
+    gpus.sort_by(|a, b| a.id.cmp(&b.id));
+    gpus
+}
+
+// windows only lets applications pick between the two classes of gpu
+#[cfg(target_os = "windows")]
+pub async fn detect() -> Vec<Gpu> {
+    let adapters = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            // remote desktop, streaming and other virtual adapters aren't on the pci bus
+            "(Get-CimInstance Win32_VideoController | Where-Object { $_.PNPDeviceID -like 'PCI\\*' }).Name",
+        ])
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count()
+        })
+        .unwrap_or(0);
+
+    if adapters < 2 {
+        return Vec::new();
+    }
+
+    vec![
+        Gpu {
+            id: "power-saving".to_string(),
+            name: "Power saving".to_string(),
+        },
+        Gpu {
+            id: "high-performance".to_string(),
+            name: "High performance".to_string(),
+        },
+    ]
+}
+
+// macs switch gpus on their own
+#[cfg(target_os = "macos")]
+pub async fn detect() -> Vec<Gpu> {
+    Vec::new()
+}
+
+// mesa takes the pci slot, nvidia's driver only knows render offload
+#[cfg(target_os = "linux")]
+fn get_prime_env(vendor: &str, id: &str) -> Vec<(String, String)> {
+    let env = if vendor == NVIDIA_VENDOR {
+        vec![
+            ("__NV_PRIME_RENDER_OFFLOAD", "1".to_string()),
+            ("__GLX_VENDOR_LIBRARY_NAME", "nvidia".to_string()),
+            ("__VK_LAYER_NV_optimus", "NVIDIA_only".to_string()),
+        ]
+    } else {
+        vec![("DRI_PRIME", format!("pci-{}", id.replace([':', '.'], "_")))]
+    };
+
+    env.into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+}
+
+// None is the system default
+#[cfg(target_os = "linux")]
+pub fn get_env(id: Option<&str>, _java_path: &Path) -> Result<Vec<(String, String)>> {
+    let Some(id) = id else {
+        return Ok(Vec::new());
+    };
+
+    let vendor =
+        std::fs::read_to_string(Path::new("/sys/bus/pci/devices").join(id).join("vendor"))?;
+
+    Ok(get_prime_env(vendor.trim(), id))
+}
+
+#[cfg(target_os = "windows")]
+const GPU_PREFERENCES_KEY: &str = "HKCU\\Software\\Microsoft\\DirectX\\UserGpuPreferences";
+
+// the preference is per executable, so it's set on the java binary; the runtime
+// is shared between instances, so it's removed again for the ones without one
+#[cfg(target_os = "windows")]
+pub fn get_env(id: Option<&str>, java_path: &Path) -> Result<Vec<(String, String)>> {
+    use anyhow::bail;
+
+    let preference = match id {
+        None => None,
+        Some("power-saving") => Some(1),
+        Some("high-performance") => Some(2),
+        Some(id) => bail!("Unknown GPU: {id}"),
+    };
+
+    // javaw is what actually opens the window when it's used
+    for exe in [
+        java_path.to_path_buf(),
+        java_path.with_file_name("javaw.exe"),
+    ] {
+        let mut command = std::process::Command::new("reg");
+        match preference {
+            Some(preference) => command
+                .args(["add", GPU_PREFERENCES_KEY, "/v"])
+                .arg(&exe)
+                .args(["/d", &format!("GpuPreference={preference};"), "/f"]),
+            None => command
+                .args(["delete", GPU_PREFERENCES_KEY, "/v"])
+                .arg(&exe)
+                .arg("/f"),
+        };
+
+        // deleting a value that isn't there fails too, that's fine
+        let output = command.output()?;
+        if preference.is_some() && !output.status.success() {
+            bail!("Failed to set the GPU preference of {}", exe.display());
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+#[cfg(target_os = "macos")]
+pub fn get_env(_id: Option<&str>, _java_path: &Path) -> Result<Vec<(String, String)>> {
+    Ok(Vec::new())
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offloads_to_the_chosen_gpu() {
+        assert_eq!(
+            get_prime_env("0x1002", "0000:03:00.0"),
+            [("DRI_PRIME".to_string(), "pci-0000_03_00_0".to_string())]
+        );
+        assert!(get_prime_env(NVIDIA_VENDOR, "0000:01:00.0")
+            .contains(&("__NV_PRIME_RENDER_OFFLOAD".to_string(), "1".to_string())));
+    }
+}
@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+// a hook that hangs would keep the game from starting
+const TIMEOUT: Duration = Duration::from_secs(300);
+
+// runs a user command through the system shell, inside the instance folder;
+// the variables are the same MultiMC sets, so existing scripts keep working
+pub fn run(command: &str, dir: &Path, name: &str) -> Result<()> {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let mut child = shell
+        .arg(command)
+        .current_dir(dir)
+        .env("INST_NAME", name)
+        .env("INST_DIR", dir)
+        .spawn()?;
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if start.elapsed() > TIMEOUT {
+            child.kill()?;
+            child.wait()?;
+            bail!(
+                "`{}` didn't finish in {} minutes and was stopped",
+                command,
+                TIMEOUT.as_secs() / 60
+            );
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    };
+
+    if !status.success() {
+        bail!("`{}` failed ({})", command, status);
+    }
+
+    Ok(())
+}
+
+pub async fn run_pre_launch(command: String, dir: PathBuf, name: String) -> Result<()> {
+    run(&command, &dir, &name)
+}
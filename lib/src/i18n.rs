@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    English,
+    Italian,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Italian];
+
+    fn catalog(self) -> &'static HashMap<&'static str, &'static str> {
+        static ENGLISH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+        static ITALIAN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+
+        match self {
+            Language::English => {
+                ENGLISH.get_or_init(|| parse(include_str!("../../assets/locales/en.ftl")))
+            }
+            Language::Italian => {
+                ITALIAN.get_or_init(|| parse(include_str!("../../assets/locales/it.ftl")))
+            }
+        }
+    }
+}
+
+// in the language itself, so it can be found by someone who can't read the current one
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Italian => write!(f, "Italiano"),
+        }
+    }
+}
+
+static CURRENT: RwLock<Language> = RwLock::new(Language::English);
+
+pub fn set_language(language: Language) {
+    *CURRENT.write().unwrap() = language;
+}
+
+// a subset of fluent: `key = value` lines, `{ $arg }` placeables and `#` comments
+fn parse(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
+
+// falls back to english, then to the key itself
+pub fn t(key: &str) -> String {
+    t_args(key, &[])
+}
+
+pub fn t_args(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let language = *CURRENT.read().unwrap();
+
+    let mut message = language
+        .catalog()
+        .get(key)
+        .or_else(|| Language::English.catalog().get(key))
+        .map_or_else(|| key.to_string(), |message| message.to_string());
+
+    for (name, value) in args {
+        message = message.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_args() {
+        assert_eq!(
+            t_args("instance-minecraft", &[("version", &"1.20.1")]),
+            "Minecraft 1.20.1"
+        );
+        assert_eq!(t("no-such-key"), "no-such-key");
+    }
+
+    #[test]
+    fn locales_have_the_same_keys() {
+        let mut english = Language::English.catalog().keys().collect::<Vec<_>>();
+        english.sort();
+
+        for language in Language::ALL {
+            let mut keys = language.catalog().keys().collect::<Vec<_>>();
+            keys.sort();
+
+            assert_eq!(keys, english, "{language} is out of sync");
+        }
+    }
+}
@@ -0,0 +1,461 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use zip::ZipArchive;
+
+use crate::instances::{Instance, Loader};
+use crate::runtime_manager::JavaVendor;
+use crate::{modrinth, vanilla_installer, DownloadItem, DownloadQueue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Mrpack,
+    MultiMc,
+    Technic,
+    AtLauncher,
+    Mod,
+}
+
+// what an instance needs to be created from a pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackInfo {
+    pub kind: FileKind,
+    pub name: String,
+    pub minecraft: String,
+    pub loader: Loader,
+    pub loader_version: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedPack {
+    pub queue: DownloadQueue,
+    // mods that couldn't be found, they have to be added by hand
+    pub skipped: Vec<String>,
+}
+
+pub fn detect(path: &Path) -> Option<FileKind> {
+    match path.extension()?.to_str()? {
+        "mrpack" => Some(FileKind::Mrpack),
+        "jar" => Some(FileKind::Mod),
+        "zip" if find_multimc_root(path).is_ok() => Some(FileKind::MultiMc),
+        "zip" if find_root(path, "instance.json").is_ok() => Some(FileKind::AtLauncher),
+        "zip" if has_entry(path, TECHNIC_VERSION) || has_entry(path, TECHNIC_MODPACK_JAR) => {
+            Some(FileKind::Technic)
+        }
+        _ => None,
+    }
+}
+
+pub fn read_pack(path: &Path) -> Result<PackInfo> {
+    match detect(path) {
+        Some(FileKind::Mrpack) => {
+            let index = modrinth::read_mrpack(path)?;
+
+            if index.dependencies.quilt_loader.is_some() {
+                bail!("Quilt packs aren't supported");
+            }
+            if index.dependencies.forge.is_some() {
+                bail!("Forge packs aren't supported");
+            }
+
+            Ok(PackInfo {
+                kind: FileKind::Mrpack,
+                name: index.name,
+                minecraft: index.dependencies.minecraft,
+                loader: if index.dependencies.fabric_loader.is_some() {
+                    Loader::Fabric
+                } else {
+                    Loader::Vanilla
+                },
+                loader_version: index.dependencies.fabric_loader,
+            })
+        }
+        Some(FileKind::MultiMc) => read_multimc(path),
+        Some(FileKind::Technic) => read_technic(path),
+        Some(FileKind::AtLauncher) => read_atlauncher(path),
+        _ => bail!("Not a modpack: {}", path.display()),
+    }
+}
+
+// extracts the pack into the instance, the files still to download are returned
+// along with the mods that were skipped
+pub fn import_pack(
+    path: &Path,
+    dest_dir: &Path,
+    threads: usize,
+) -> Result<(Vec<DownloadItem>, Vec<String>)> {
+    match detect(path) {
+        Some(FileKind::Mrpack) => Ok((
+            modrinth::install_mrpack(path, dest_dir, threads)?,
+            Vec::new(),
+        )),
+        Some(FileKind::MultiMc) => {
+            let root = find_multimc_root(path)?;
+
+            // older exports use minecraft instead of .minecraft
+            let archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+            let game_dir = [".minecraft", "minecraft"]
+                .into_iter()
+                .map(|dir| root.join(dir))
+                .find(|dir| {
+                    archive
+                        .file_names()
+                        .any(|name| Path::new(name).starts_with(dir))
+                })
+                .ok_or_else(|| anyhow!("No game folder in {}", path.display()))?;
+
+            modrinth::extract_overrides(path, &game_dir, dest_dir, threads)?;
+
+            Ok((Vec::new(), Vec::new()))
+        }
+        Some(FileKind::Technic) => {
+            // the game folder is the root of the zip, bin only holds the launcher's files
+            modrinth::extract_overrides(path, Path::new(""), dest_dir, threads)?;
+            fs::remove_dir_all(dest_dir.join("bin"))?;
+
+            Ok((Vec::new(), Vec::new()))
+        }
+        Some(FileKind::AtLauncher) => import_atlauncher(path, dest_dir, threads),
+        _ => bail!("Not a modpack: {}", path.display()),
+    }
+}
+
+// the game, its loader and whatever the pack still needs, all queued together
+pub async fn install_pack(
+    path: PathBuf,
+    info: Instance,
+    instance_dir: PathBuf,
+    vendor: JavaVendor,
+    threads: usize,
+) -> Result<ImportedPack> {
+    let mut queue = vanilla_installer::download_instance(info, vendor).await?;
+    let (items, skipped) = import_pack(&path, &instance_dir, threads)?;
+    queue.append(DownloadQueue::new(items));
+
+    Ok(ImportedPack { queue, skipped })
+}
+
+pub fn install_mod_file(path: &Path, instance_dir: &Path) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("Not a file: {}", path.display()))?;
+
+    let mods_dir = instance_dir.join("mods");
+    fs::create_dir_all(&mods_dir)?;
+    fs::copy(path, mods_dir.join(file_name))?;
+
+    Ok(())
+}
+
+// the shallowest folder with the given file, exports usually wrap everything in one
+fn find_root(path: &Path, file_name: &str) -> Result<PathBuf> {
+    let archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+    archive
+        .file_names()
+        .map(Path::new)
+        .filter(|name| name.file_name().is_some_and(|n| n == file_name))
+        .filter_map(|name| name.parent())
+        .min_by_key(|parent| parent.components().count())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("No {file_name} in {}", path.display()))
+}
+
+fn find_multimc_root(path: &Path) -> Result<PathBuf> {
+    find_root(path, "instance.cfg")
+        .map_err(|_| anyhow!("Not a MultiMC instance: {}", path.display()))
+}
+
+fn has_entry(path: &Path, name: &str) -> bool {
+    File::open(path)
+        .ok()
+        .and_then(|file| ZipArchive::new(BufReader::new(file)).ok())
+        .is_some_and(|mut archive| archive.by_name(name).is_ok())
+}
+
+// zip entries always use forward slashes
+fn entry_name(root: &Path, name: &str) -> String {
+    if root.as_os_str().is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", root.to_string_lossy(), name)
+    }
+}
+
+fn read_multimc(path: &Path) -> Result<PackInfo> {
+    #[derive(Deserialize)]
+    struct Component {
+        uid: String,
+        version: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct MmcPack {
+        components: Vec<Component>,
+    }
+
+    let root = find_multimc_root(path)?;
+    let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+    let mut cfg = String::new();
+    archive
+        .by_name(&entry_name(&root, "instance.cfg"))?
+        .read_to_string(&mut cfg)?;
+
+    let name = cfg
+        .lines()
+        .find_map(|line| line.strip_prefix("name="))
+        .unwrap_or("MultiMC instance")
+        .to_string();
+
+    let pack = archive.by_name(&entry_name(&root, "mmc-pack.json"))?;
+    let pack = serde_json::from_reader::<_, MmcPack>(pack)?;
+
+    let find = |uid: &str| {
+        pack.components
+            .iter()
+            .find(|component| component.uid == uid)
+            .and_then(|component| component.version.clone())
+    };
+
+    let minecraft = find("net.minecraft").ok_or_else(|| anyhow!("Unknown Minecraft version"))?;
+    let (loader, loader_version) = if let Some(version) = find("net.fabricmc.fabric-loader") {
+        (Loader::Fabric, Some(version))
+    } else if find("org.quiltmc.quilt-loader").is_some() {
+        bail!("Quilt packs aren't supported");
+    } else {
+        (Loader::Vanilla, None)
+    };
+
+    Ok(PackInfo {
+        kind: FileKind::MultiMc,
+        name,
+        minecraft,
+        loader,
+        loader_version,
+    })
+}
+
+const TECHNIC_VERSION: &str = "bin/version.json";
+// older packs only ship a patched forge jar
+const TECHNIC_MODPACK_JAR: &str = "bin/modpack.jar";
+
+fn pack_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// technic packs carry a mojang style version json, with the loader in its libraries
+fn parse_technic_version(json: &str) -> Result<(String, Loader, Option<String>)> {
+    #[derive(Deserialize)]
+    struct Library {
+        name: String,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct VersionJson {
+        id: String,
+        inherits_from: Option<String>,
+        #[serde(default)]
+        libraries: Vec<Library>,
+    }
+
+    let version = serde_json::from_str::<VersionJson>(json)?;
+    let minecraft = version.inherits_from.unwrap_or(version.id);
+
+    for library in &version.libraries {
+        if let Some(loader_version) = library.name.strip_prefix("net.fabricmc:fabric-loader:") {
+            return Ok((minecraft, Loader::Fabric, Some(loader_version.to_string())));
+        }
+        // there's no quilt installer yet
+        if library.name.starts_with("org.quiltmc:quilt-loader:") {
+            bail!("Quilt packs aren't supported");
+        }
+        if library.name.starts_with("net.minecraftforge:") {
+            bail!("Forge packs aren't supported");
+        }
+    }
+
+    Ok((minecraft, Loader::Vanilla, None))
+}
+
+fn read_technic(path: &Path) -> Result<PackInfo> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+    let mut json = String::new();
+    match archive.by_name(TECHNIC_VERSION) {
+        Ok(mut entry) => entry.read_to_string(&mut json)?,
+        Err(_) => bail!("Forge packs aren't supported"),
+    };
+
+    let (minecraft, loader, loader_version) = parse_technic_version(&json)?;
+
+    Ok(PackInfo {
+        kind: FileKind::Technic,
+        name: pack_name(path),
+        minecraft,
+        loader,
+        loader_version,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtLauncherLoader {
+    version: String,
+    r#type: String,
+}
+
+#[derive(Deserialize)]
+struct AtLauncherProject {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtLauncherMod {
+    name: String,
+    file: String,
+    modrinth_project: Option<AtLauncherProject>,
+    modrinth_version: Option<modrinth::Version>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtLauncherInfo {
+    name: String,
+    loader_version: Option<AtLauncherLoader>,
+    #[serde(default)]
+    mods: Vec<AtLauncherMod>,
+}
+
+#[derive(Deserialize)]
+struct AtLauncherInstance {
+    // the minecraft version
+    id: String,
+    launcher: AtLauncherInfo,
+}
+
+fn read_atlauncher_instance(path: &Path) -> Result<(PathBuf, AtLauncherInstance)> {
+    let root = find_root(path, "instance.json")?;
+    let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+    let entry = archive.by_name(&entry_name(&root, "instance.json"))?;
+    let instance = serde_json::from_reader(entry)
+        .map_err(|_| anyhow!("Not an ATLauncher instance: {}", path.display()))?;
+
+    Ok((root, instance))
+}
+
+fn read_atlauncher(path: &Path) -> Result<PackInfo> {
+    let (_, instance) = read_atlauncher_instance(path)?;
+
+    let (loader, loader_version) = match instance.launcher.loader_version {
+        Some(loader) => match loader.r#type.as_str() {
+            "Fabric" => (Loader::Fabric, Some(loader.version)),
+            other => bail!("{other} instances aren't supported"),
+        },
+        None => (Loader::Vanilla, None),
+    };
+
+    Ok(PackInfo {
+        kind: FileKind::AtLauncher,
+        name: instance.launcher.name,
+        minecraft: instance.id,
+        loader,
+        loader_version,
+    })
+}
+
+// mods missing from the export are looked up on modrinth, the rest is reported
+// a mod that can't be looked up doesn't stop the rest of the pack
+fn import_atlauncher(
+    path: &Path,
+    dest_dir: &Path,
+    threads: usize,
+) -> Result<(Vec<DownloadItem>, Vec<String>)> {
+    let (root, instance) = read_atlauncher_instance(path)?;
+    let info = read_atlauncher(path)?;
+
+    modrinth::extract_overrides(path, &root, dest_dir, threads)?;
+    fs::remove_file(dest_dir.join("instance.json"))?;
+
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for atlauncher_mod in instance.launcher.mods {
+        if dest_dir.join("mods").join(&atlauncher_mod.file).exists() {
+            continue;
+        }
+
+        let version = match (
+            atlauncher_mod.modrinth_version,
+            atlauncher_mod.modrinth_project,
+        ) {
+            (Some(version), _) => Some(version),
+            (None, Some(project)) => {
+                match modrinth::get_compatible_version(&project.id, &info.minecraft, info.loader) {
+                    Ok(version) => version,
+                    Err(error) => {
+                        tracing::warn!("failed to look up {}: {error:#}", atlauncher_mod.name);
+                        skipped.push(atlauncher_mod.name);
+                        continue;
+                    }
+                }
+            }
+            (None, None) => None,
+        };
+
+        match version.filter(|version| !version.files.is_empty()) {
+            Some(version) => items.push(modrinth::install_mod(&version, dest_dir)?),
+            None => {
+                tracing::warn!("{} isn't on Modrinth, skipping it", atlauncher_mod.name);
+                skipped.push(atlauncher_mod.name);
+            }
+        }
+    }
+
+    Ok((items, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_loader_of_technic_packs() {
+        let fabric = r#"{
+            "id": "fabric-loader-0.14.22-1.20.1",
+            "inheritsFrom": "1.20.1",
+            "libraries": [{ "name": "net.fabricmc:fabric-loader:0.14.22" }]
+        }"#;
+        assert_eq!(
+            parse_technic_version(fabric).unwrap(),
+            (
+                "1.20.1".to_string(),
+                Loader::Fabric,
+                Some("0.14.22".to_string())
+            )
+        );
+
+        let forge = r#"{
+            "id": "1.12.2-forge",
+            "libraries": [{ "name": "net.minecraftforge:forge:14.23.5.2860" }]
+        }"#;
+        assert!(parse_technic_version(forge).is_err());
+
+        let quilt = r#"{
+            "id": "quilt-loader-0.21.0-1.20.1",
+            "inheritsFrom": "1.20.1",
+            "libraries": [{ "name": "org.quiltmc:quilt-loader:0.21.0" }]
+        }"#;
+        assert!(parse_technic_version(quilt).is_err());
+    }
+}
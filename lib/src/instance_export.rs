@@ -0,0 +1,149 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::json;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use crate::instances::{Instance, Loader};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportPart {
+    Saves,
+    Mods,
+    Config,
+    ResourcePacks,
+    Logs,
+}
+
+impl ExportPart {
+    pub const ALL: [ExportPart; 5] = [
+        ExportPart::Saves,
+        ExportPart::Mods,
+        ExportPart::Config,
+        ExportPart::ResourcePacks,
+        ExportPart::Logs,
+    ];
+
+    // paths inside the instance folder
+    pub(crate) fn paths(self) -> &'static [&'static str] {
+        match self {
+            ExportPart::Saves => &["saves"],
+            ExportPart::Mods => &["mods"],
+            ExportPart::Config => &["config", "options.txt", "servers.dat"],
+            ExportPart::ResourcePacks => &["resourcepacks", "shaderpacks"],
+            ExportPart::Logs => &["logs", "crash-reports"],
+        }
+    }
+}
+
+impl fmt::Display for ExportPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportPart::Saves => write!(f, "Saves"),
+            ExportPart::Mods => write!(f, "Mods"),
+            ExportPart::Config => write!(f, "Configs"),
+            ExportPart::ResourcePacks => write!(f, "Resource packs"),
+            ExportPart::Logs => write!(f, "Logs"),
+        }
+    }
+}
+
+// the layout of a MultiMC export, so import::read_pack and other launchers
+// can read it back
+fn get_mmc_pack(info: &Instance) -> serde_json::Value {
+    let mut components = vec![json!({ "uid": "net.minecraft", "version": info.minecraft })];
+
+    let loader_uid = match info.loader {
+        Loader::Vanilla => None,
+        Loader::Fabric => Some("net.fabricmc.fabric-loader"),
+        Loader::Quilt => Some("org.quiltmc.quilt-loader"),
+    };
+    if let (Some(uid), Some(version)) = (loader_uid, &info.loader_version) {
+        components.push(json!({ "uid": uid, "version": version }));
+    }
+
+    json!({ "formatVersion": 1, "components": components })
+}
+
+pub(crate) fn add_path(
+    zip: &mut ZipWriter<BufWriter<File>>,
+    path: &Path,
+    entry: &str,
+    options: FileOptions,
+) -> Result<()> {
+    if path.is_dir() {
+        for child in fs::read_dir(path)? {
+            let child = child?;
+            let entry = format!("{entry}/{}", child.file_name().to_string_lossy());
+
+            add_path(zip, &child.path(), &entry, options)?;
+        }
+    } else if path.is_file() {
+        zip.start_file(entry, options)?;
+        io::copy(&mut File::open(path)?, zip)?;
+    }
+
+    Ok(())
+}
+
+pub async fn export(
+    name: String,
+    info: Instance,
+    instance_dir: PathBuf,
+    parts: Vec<ExportPart>,
+    path: PathBuf,
+) -> Result<()> {
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+    let options = FileOptions::default();
+
+    zip.start_file(format!("{name}/instance.cfg"), options)?;
+    io::Write::write_all(
+        &mut zip,
+        format!("InstanceType=OneSix\nname={name}\n").as_bytes(),
+    )?;
+
+    zip.start_file(format!("{name}/mmc-pack.json"), options)?;
+    serde_json::to_writer_pretty(&mut zip, &get_mmc_pack(&info))?;
+
+    // the importer looks for it even when no part was picked
+    zip.add_directory(format!("{name}/.minecraft/"), options)?;
+
+    for part in parts {
+        for path in part.paths() {
+            add_path(
+                &mut zip,
+                &instance_dir.join(path),
+                &format!("{name}/.minecraft/{path}"),
+                options,
+            )?;
+        }
+    }
+
+    zip.finish()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_the_loader_component() {
+        let info = Instance::from_toml(
+            "minecraft = \"1.20.1\"\nloader = \"fabric\"\nloader_version = \"0.14.22\"",
+        )
+        .unwrap();
+        let pack = get_mmc_pack(&info);
+
+        assert_eq!(pack["components"][0]["version"], "1.20.1");
+        assert_eq!(pack["components"][1]["uid"], "net.fabricmc.fabric-loader");
+    }
+}
@@ -1,28 +1,451 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::{fs, process};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, process};
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Duration, OffsetDateTime};
 
 use crate::accounts::Account;
-use crate::paths::{ASSETS_DIR, BASE_DIR};
-use crate::{adoptium, vanilla_installer};
+use crate::authlib_injector;
+use crate::backups::BackupSchedule;
+use crate::diagnostics;
+use crate::fabric::FabricMeta;
+use crate::file_lock;
+use crate::gpu;
+use crate::hooks;
+use crate::jvm::{self, GarbageCollector, JvmPreset};
+use crate::running::RunningGames;
+use crate::runtime_manager::Arch;
+use crate::server;
+use crate::settings::{Settings, DEFAULT_MEMORY};
+use crate::vanilla_installer::LatestRelease;
+use crate::{runtime_manager, vanilla_installer};
 
-// https://github.com/brucethemoose/Minecraft-Performance-Flags-Benchmarks
-const OPTIMIZED_FLAGS: &str = " -XX:+UnlockExperimentalVMOptions -XX:+UnlockDiagnosticVMOptions -XX:+AlwaysActAsServerClassMachine -XX:+AlwaysPreTouch -XX:+DisableExplicitGC -XX:+UseNUMA -XX:NmethodSweepActivity=1 -XX:ReservedCodeCacheSize=400M -XX:NonNMethodCodeHeapSize=12M -XX:ProfiledCodeHeapSize=194M -XX:NonProfiledCodeHeapSize=194M -XX:-DontCompileHugeMethods -XX:MaxNodeLimit=240000 -XX:NodeLimitFudgeFactor=8000 -XX:+UseVectorCmov -XX:+PerfDisableSharedMem -XX:+UseFastUnorderedTimeStamps -XX:+UseCriticalJavaThreadPriority -XX:ThreadPriorityPolicy=1 -XX:AllocatePrefetchStyle=3 -XX:+UseShenandoahGC -XX:ShenandoahGCMode=iu -XX:ShenandoahGuaranteedGCInterval=1000000 -XX:AllocatePrefetchStyle=1";
+pub const SCHEMA_VERSION: u32 = 4;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Loader {
+    #[default]
+    Vanilla,
+    Fabric,
+    Quilt,
+}
+
+impl fmt::Display for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Loader::Vanilla => write!(f, "Vanilla"),
+            Loader::Fabric => write!(f, "Fabric"),
+            Loader::Quilt => write!(f, "Quilt"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstanceKind {
+    #[default]
+    Client,
+    // a dedicated server, started with a console instead of an account
+    Server,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourcePack {
+    pub provider: String,
+    pub project_id: String,
+    pub version_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceIcon {
+    // relative to the instance directory
+    pub path: String,
+    // e.g. "#1e1e2e", so transparent icons look right in both themes
+    pub background: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Instance {
-    last_played: String,
+    #[serde(default)]
+    pub schema_version: u32,
     pub minecraft: String,
-    pub fabric: Option<String>,
-    pub optimize_jvm: bool,
-    pub memory: String,
+    #[serde(default)]
+    pub loader: Loader,
+    pub loader_version: Option<String>,
+    #[serde(default)]
+    pub kind: InstanceKind,
+    pub java: Option<String>,
+    // overrides the managed runtime
+    #[serde(default)]
+    pub java_path: Option<PathBuf>,
+    // falls back to the preset in the settings
+    #[serde(default)]
+    pub jvm_preset: Option<JvmPreset>,
+    // these fall back to the settings, see InheritedSetting
+    #[serde(default)]
+    pub memory: Option<String>,
+    #[serde(default)]
+    pub window_width: Option<u32>,
+    #[serde(default)]
+    pub window_height: Option<u32>,
+    #[serde(default)]
+    pub pre_launch_hook: Option<String>,
+    #[serde(default)]
+    pub post_exit_hook: Option<String>,
+    // appended after the preset's flags
+    #[serde(default)]
+    pub jvm_args: Vec<String>,
+    // free-form, e.g. "hardcore" or "with-friends"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // injected into the game process
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub last_played: Option<OffsetDateTime>,
+    // in seconds, summed when a session ends
+    #[serde(default)]
+    pub playtime: u64,
+    pub source: Option<SourcePack>,
+    // id of the account last used to launch this instance
+    pub account: Option<String>,
+    pub icon: Option<InstanceIcon>,
+    // collect gc and jfr logs into the diagnostics folder
+    #[serde(default)]
+    pub profiling: bool,
+    // see gpu::detect, the system default when unset
+    #[serde(default)]
+    pub gpu: Option<String>,
+    #[serde(default)]
+    pub backup_schedule: BackupSchedule,
+    // how many backups to keep, see backups::DEFAULT_RETENTION
+    #[serde(default)]
+    pub backup_retention: Option<usize>,
+
+    // Unknown keys are kept so that third-party tools can store their own data
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Instance {
+    pub fn new(
+        minecraft: String,
+        loader: Loader,
+        loader_version: Option<String>,
+        jvm_preset: Option<JvmPreset>,
+        memory: Option<String>,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            minecraft,
+            loader,
+            loader_version,
+            kind: InstanceKind::Client,
+            java: None,
+            java_path: None,
+            jvm_preset,
+            memory,
+            window_width: None,
+            window_height: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            jvm_args: Vec::new(),
+            tags: Vec::new(),
+            env: BTreeMap::new(),
+            notes: String::new(),
+            created: Some(OffsetDateTime::now_utc()),
+            last_played: None,
+            playtime: 0,
+            source: None,
+            account: None,
+            icon: None,
+            profiling: false,
+            gpu: None,
+            backup_schedule: BackupSchedule::Manual,
+            backup_retention: None,
+            extra: toml::Table::new(),
+        }
+    }
+
+    // none if it was never launched
+    pub fn days_since_played(&self) -> Option<i64> {
+        self.last_played
+            .map(|last_played| (OffsetDateTime::now_utc() - last_played).whole_days())
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let mut table = toml::from_str::<toml::Table>(content)?;
+
+        let schema_version = table
+            .get("schema_version")
+            .and_then(|v| v.as_integer())
+            .unwrap_or(0);
+
+        // Unversioned instances stored last_played as a display string and only supported Fabric
+        if schema_version < 1 {
+            if let Some(last_played) = table.remove("last_played") {
+                let format = format_description!(
+                    "[year]-[month]-[day] [hour padding:none]:[minute]:[second].[subsecond] [offset_hour sign:mandatory]:[offset_minute]:[offset_second]"
+                );
+
+                // one that can't be read is dropped, it only sorts the list
+                if let Some(last_played) = last_played
+                    .as_str()
+                    .and_then(|value| OffsetDateTime::parse(value, format).ok())
+                    .and_then(|value| value.format(&Rfc3339).ok())
+                {
+                    table.insert("last_played".to_string(), last_played.into());
+                }
+            }
+
+            if let Some(fabric) = table.remove("fabric") {
+                table.insert("loader".to_string(), "fabric".into());
+                table.insert("loader_version".to_string(), fabric);
+            }
+        }
+
+        // The optimize_jvm toggle became a preset
+        if schema_version < 2 {
+            // turning it off was a choice too, it shouldn't pick up the default preset
+            if let Some(optimize_jvm) = table.remove("optimize_jvm").and_then(|v| v.as_bool()) {
+                let preset = if optimize_jvm { "optimized" } else { "none" };
+                table.insert("jvm_preset".to_string(), preset.into());
+            }
+        }
+
+        // Groups were never shown anywhere, tags replaced them
+        if schema_version < 3 {
+            if let Some(toml::Value::Array(groups)) = table.remove("groups") {
+                let tags = table
+                    .entry("tags")
+                    .or_insert_with(|| toml::Value::Array(Vec::new()));
+
+                if let toml::Value::Array(tags) = tags {
+                    for group in groups {
+                        if !tags.contains(&group) {
+                            tags.push(group);
+                        }
+                    }
+                }
+            }
+        }
+
+        // 4G was the hardcoded default, now it comes from the settings
+        if schema_version < 4 && table.get("memory").and_then(|m| m.as_str()) == Some("4G") {
+            table.remove("memory");
+        }
+
+        table.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+
+        let instance = toml::Value::Table(table).try_into()?;
+        Ok(instance)
+    }
+
+    // mods and loaders usually don't support the new version yet
+    pub fn can_update_to(&self, latest: &LatestRelease) -> bool {
+        self.loader == Loader::Vanilla && latest.is_newer_than(&self.minecraft)
+    }
+
+    pub fn get_memory<'a>(&'a self, settings: &'a Settings) -> &'a str {
+        match (&self.memory, settings.memory.as_str()) {
+            (Some(memory), _) => memory,
+            (None, "") => DEFAULT_MEMORY,
+            (None, memory) => memory,
+        }
+    }
+
+    pub fn get_java_path(&self, arch: Arch) -> Result<PathBuf> {
+        match &self.java_path {
+            Some(path) => Ok(path.clone()),
+            None => runtime_manager::get_path(&runtime_manager::get_runtime_id(
+                self.java.as_deref().unwrap_or("17"),
+                arch,
+            )),
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        let content = toml::to_string_pretty(self)?;
+        Ok(content)
+    }
+}
+
+// launcher-wide defaults that an instance can override
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InheritedSetting {
+    Memory,
+    WindowWidth,
+    WindowHeight,
+    PreLaunchHook,
+    PostExitHook,
+}
+
+impl InheritedSetting {
+    pub const ALL: [InheritedSetting; 5] = [
+        InheritedSetting::Memory,
+        InheritedSetting::WindowWidth,
+        InheritedSetting::WindowHeight,
+        InheritedSetting::PreLaunchHook,
+        InheritedSetting::PostExitHook,
+    ];
+
+    // used for the translations too
+    pub fn key(&self) -> &'static str {
+        match self {
+            InheritedSetting::Memory => "memory",
+            InheritedSetting::WindowWidth => "window-width",
+            InheritedSetting::WindowHeight => "window-height",
+            InheritedSetting::PreLaunchHook => "pre-launch-hook",
+            InheritedSetting::PostExitHook => "post-exit-hook",
+        }
+    }
+
+    // None when the instance inherits it
+    pub fn get_override(&self, info: &Instance) -> Option<String> {
+        match self {
+            InheritedSetting::Memory => info.memory.clone(),
+            InheritedSetting::WindowWidth => info.window_width.map(|w| w.to_string()),
+            InheritedSetting::WindowHeight => info.window_height.map(|h| h.to_string()),
+            InheritedSetting::PreLaunchHook => info.pre_launch_hook.clone(),
+            InheritedSetting::PostExitHook => info.post_exit_hook.clone(),
+        }
+    }
+
+    pub fn get_default(&self, settings: &Settings) -> Option<String> {
+        match self {
+            InheritedSetting::Memory => Some(settings.memory.clone()),
+            InheritedSetting::WindowWidth => settings.window_width.map(|w| w.to_string()),
+            InheritedSetting::WindowHeight => settings.window_height.map(|h| h.to_string()),
+            InheritedSetting::PreLaunchHook => settings.pre_launch_hook.clone(),
+            InheritedSetting::PostExitHook => settings.post_exit_hook.clone(),
+        }
+    }
+
+    // shown next to the field, the value is kept so it can be fixed
+    pub fn validate(&self, value: &str) -> Result<()> {
+        match self {
+            InheritedSetting::Memory if !value.is_empty() => jvm::validate_memory(value),
+            InheritedSetting::WindowWidth | InheritedSetting::WindowHeight if !value.is_empty() => {
+                value.parse::<u32>()?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn set_default(&self, settings: &mut Settings, value: String) -> Result<()> {
+        let value = Some(value).filter(|value| !value.is_empty());
+
+        match self {
+            InheritedSetting::Memory => settings.memory = value.unwrap_or_default(),
+            InheritedSetting::WindowWidth => {
+                settings.window_width = value.map(|w| w.parse()).transpose()?
+            }
+            InheritedSetting::WindowHeight => {
+                settings.window_height = value.map(|h| h.parse()).transpose()?
+            }
+            InheritedSetting::PreLaunchHook => settings.pre_launch_hook = value,
+            InheritedSetting::PostExitHook => settings.post_exit_hook = value,
+        }
+
+        Ok(())
+    }
+
+    fn set(&self, info: &mut Instance, value: Option<String>) -> Result<()> {
+        match self {
+            InheritedSetting::Memory => info.memory = value,
+            InheritedSetting::WindowWidth => {
+                info.window_width = value.map(|w| w.parse()).transpose()?
+            }
+            InheritedSetting::WindowHeight => {
+                info.window_height = value.map(|h| h.parse()).transpose()?
+            }
+            InheritedSetting::PreLaunchHook => info.pre_launch_hook = value,
+            InheritedSetting::PostExitHook => info.post_exit_hook = value,
+        }
+
+        Ok(())
+    }
+}
+
+// how a single launch differs from a normal one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchOptions {
+    // no account needed, the world is limited to five in-game days
+    pub demo: bool,
+    // folder name of a world in saves, skips the title screen
+    pub quick_play: Option<String>,
+    // nothing gets downloaded, the cached token is used as is
+    pub offline: bool,
+}
+
+pub(crate) fn copy_dir(src: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let dest = dest.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), dest)?;
+        }
+    }
+
+    Ok(())
+}
+
+// deleted instances wait here until restored or expired, inside the
+// instances folder so they're moved along with it
+const TRASH_DIR: &str = ".trash";
+
+#[derive(Debug, Clone)]
+pub struct TrashedInstance {
+    pub name: String,
+    pub deleted: OffsetDateTime,
+    path: PathBuf,
+}
+
+impl TrashedInstance {
+    pub fn days_left(&self, retention_days: u32) -> i64 {
+        let expires = self.deleted + Duration::days(retention_days.into());
+
+        (expires - OffsetDateTime::now_utc()).whole_days().max(0)
+    }
+}
+
+// <unix time>-<name>
+fn parse_trashed(path: PathBuf) -> Option<TrashedInstance> {
+    let file_name = path.file_name()?.to_str()?;
+    let (timestamp, name) = file_name.split_once('-')?;
+
+    Some(TrashedInstance {
+        name: name.to_string(),
+        deleted: OffsetDateTime::from_unix_timestamp(timestamp.parse().ok()?).ok()?,
+        path,
+    })
+}
+
+// names become folders, e.g. from a modpack manifest, they can't point elsewhere
+pub(crate) fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        bail!("Invalid instance name: {}", name);
+    }
+
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -32,8 +455,7 @@ pub struct Instances {
 }
 
 impl Instances {
-    pub fn load() -> Result<Self> {
-        let base_dir = BASE_DIR.join("instances");
+    pub fn load(base_dir: PathBuf) -> Result<Self> {
         fs::create_dir_all(&base_dir)?;
 
         let mut list = HashMap::new();
@@ -42,8 +464,8 @@ impl Instances {
             let entry = entry?;
             let path = entry.path();
 
-            // Skip non-directories
-            if !path.is_dir() {
+            // Skip non-directories and the trash
+            if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
                 continue;
             }
 
@@ -51,8 +473,11 @@ impl Instances {
 
             let info = {
                 let path = path.join("instance.toml");
-                let info = fs::read_to_string(path)?;
-                toml::from_str::<Instance>(&info)?
+                file_lock::read(&path, |info| {
+                    Instance::from_toml(info)
+                        .with_context(|| format!("Invalid instance config: {}", path.display()))
+                })
+                .with_context(|| format!("Failed to read {}", path.display()))?
             };
 
             list.insert(name, info);
@@ -61,6 +486,10 @@ impl Instances {
         Ok(Self { base_dir, list })
     }
 
+    pub fn get_base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
     pub fn get_dir(&self, name: &str) -> PathBuf {
         self.base_dir.join(name)
     }
@@ -74,105 +503,711 @@ impl Instances {
         Ok(())
     }
 
+    // like delete, but the instance can be restored until the trash expires
+    pub fn trash(&mut self, name: &str) -> Result<()> {
+        let trash_dir = self.base_dir.join(TRASH_DIR);
+        fs::create_dir_all(&trash_dir)?;
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        fs::rename(self.get_dir(name), trash_dir.join(format!("{now}-{name}")))?;
+
+        self.list.remove(name);
+
+        Ok(())
+    }
+
+    // newest first
+    pub fn list_trash(&self) -> Vec<TrashedInstance> {
+        let Ok(entries) = fs::read_dir(self.base_dir.join(TRASH_DIR)) else {
+            return Vec::new();
+        };
+
+        let mut trashed = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| parse_trashed(entry.path()))
+            .collect::<Vec<_>>();
+
+        trashed.sort_by_key(|trashed| std::cmp::Reverse(trashed.deleted));
+        trashed
+    }
+
+    // gets a new name if another instance took the old one in the meantime
+    pub fn restore(&mut self, trashed: &TrashedInstance) -> Result<String> {
+        let name = (1..)
+            .map(|i| match i {
+                1 => trashed.name.clone(),
+                i => format!("{} ({i})", trashed.name),
+            })
+            .find(|name| !self.get_dir(name).exists())
+            .unwrap();
+
+        let info = file_lock::read(&trashed.path.join("instance.toml"), Instance::from_toml)?;
+        fs::rename(&trashed.path, self.get_dir(&name))?;
+
+        self.list.insert(name.clone(), info);
+
+        Ok(name)
+    }
+
+    pub fn delete_trashed(&self, trashed: &TrashedInstance) -> Result<()> {
+        fs::remove_dir_all(&trashed.path)?;
+
+        Ok(())
+    }
+
+    // removes what's been in the trash for longer than the retention window
+    pub fn purge_trash(&self, retention_days: u32) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+
+        for trashed in self.list_trash() {
+            if now - trashed.deleted > Duration::days(retention_days.into()) {
+                self.delete_trashed(&trashed)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // folder names of the singleplayer worlds
+    pub fn get_worlds(&self, name: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.get_dir(name).join("saves")) else {
+            return Vec::new();
+        };
+
+        let mut worlds = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("level.dat").exists())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+
+        worlds.sort();
+        worlds
+    }
+
     pub fn get_config_path(&self, name: &str) -> PathBuf {
         self.get_dir(name).join("instance.toml")
     }
 
-    pub fn create(
+    pub fn save(&self, name: &str) -> Result<()> {
+        let info = self
+            .list
+            .get(name)
+            .ok_or_else(|| anyhow!("Instance not found"))?;
+
+        file_lock::write(&self.get_config_path(name), info.to_toml()?)?;
+
+        Ok(())
+    }
+
+    // applied to the instance.toml on disk, another launcher process could have
+    // changed it since it was loaded
+    fn update(
+        &mut self,
+        name: &str,
+        modify: impl FnOnce(&mut Instance) -> Result<()>,
+    ) -> Result<()> {
+        if !self.list.contains_key(name) {
+            bail!("Instance not found");
+        }
+
+        let info = file_lock::update(
+            &self.get_config_path(name),
+            Instance::from_toml,
+            Instance::to_toml,
+            modify,
+        )?;
+        self.list.insert(name.to_string(), info);
+
+        Ok(())
+    }
+
+    pub fn set_account(&mut self, name: &str, account_id: Option<&str>) -> Result<()> {
+        self.update(name, |info| {
+            info.account = account_id.map(str::to_string);
+            Ok(())
+        })
+    }
+
+    // every tag used by at least one instance, sorted
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags = self
+            .list
+            .values()
+            .flat_map(|info| info.tags.iter().cloned())
+            .collect::<Vec<_>>();
+
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    pub fn add_tag(&mut self, name: &str, tag: &str) -> Result<()> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            bail!("Tags can't be empty");
+        }
+
+        self.update(name, |info| {
+            if !info.tags.iter().any(|t| t == tag) {
+                info.tags.push(tag.to_string());
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_tag(&mut self, name: &str, tag: &str) -> Result<()> {
+        self.update(name, |info| {
+            info.tags.retain(|t| t != tag);
+            Ok(())
+        })
+    }
+
+    // None removes the variable
+    pub fn set_env(&mut self, name: &str, key: &str, value: Option<String>) -> Result<()> {
+        let key = key.trim();
+        if key.is_empty() || key.contains('=') {
+            bail!("Invalid environment variable name: {}", key);
+        }
+
+        self.update(name, |info| {
+            match value {
+                Some(value) => info.env.insert(key.to_string(), value),
+                None => info.env.remove(key),
+            };
+
+            Ok(())
+        })
+    }
+
+    // either a managed runtime version or a custom java binary
+    pub fn set_java(
+        &mut self,
+        name: &str,
+        java: Option<String>,
+        java_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.update(name, |info| {
+            info.java = java;
+            info.java_path = java_path;
+            Ok(())
+        })
+    }
+
+    // an empty value goes back to the default
+    pub fn set_inherited(
         &mut self,
-        name: String,
-        minecraft_version: String,
-        fabric_version: Option<String>,
-        optimize_jvm: bool,
-        memory: String,
+        name: &str,
+        setting: InheritedSetting,
+        value: Option<String>,
     ) -> Result<()> {
+        self.update(name, |info| {
+            setting.set(info, value.filter(|value| !value.is_empty()))
+        })
+    }
+
+    pub fn set_jvm_preset(&mut self, name: &str, jvm_preset: Option<JvmPreset>) -> Result<()> {
+        self.update(name, |info| {
+            info.jvm_preset = jvm_preset;
+            Ok(())
+        })
+    }
+
+    pub fn set_gpu(&mut self, name: &str, gpu: Option<String>) -> Result<()> {
+        self.update(name, |info| {
+            info.gpu = gpu;
+            Ok(())
+        })
+    }
+
+    pub fn set_profiling(&mut self, name: &str, profiling: bool) -> Result<()> {
+        self.update(name, |info| {
+            info.profiling = profiling;
+            Ok(())
+        })
+    }
+
+    pub fn set_backup_schedule(&mut self, name: &str, schedule: BackupSchedule) -> Result<()> {
+        self.update(name, |info| {
+            info.backup_schedule = schedule;
+            Ok(())
+        })
+    }
+
+    pub fn set_backup_retention(&mut self, name: &str, retention: Option<usize>) -> Result<()> {
+        self.update(name, |info| {
+            info.backup_retention = retention;
+            Ok(())
+        })
+    }
+
+    // run before launch() by the caller, it can take a while
+    pub fn get_pre_launch_hook(&self, name: &str, settings: &Settings) -> Option<String> {
+        self.list
+            .get(name)?
+            .pre_launch_hook
+            .as_ref()
+            .or(settings.pre_launch_hook.as_ref())
+            .cloned()
+    }
+
+    pub fn run_post_exit_hook(&self, name: &str, settings: &Settings) -> Result<()> {
+        let info = self
+            .list
+            .get(name)
+            .ok_or_else(|| anyhow!("Instance not found"))?;
+
+        if let Some(hook) = info
+            .post_exit_hook
+            .as_ref()
+            .or(settings.post_exit_hook.as_ref())
+        {
+            hooks::run(hook, &self.get_dir(name), name)?;
+        }
+
+        Ok(())
+    }
+
+    // the session started when the instance was last played
+    pub fn add_playtime(&mut self, name: &str) -> Result<()> {
+        self.update(name, |info| {
+            if let Some(last_played) = info.last_played {
+                let session = (OffsetDateTime::now_utc() - last_played).whole_seconds();
+                info.playtime += session.max(0) as u64;
+            }
+
+            Ok(())
+        })
+    }
+
+    // the game files have to be verified afterwards
+    pub fn set_minecraft(&mut self, name: &str, minecraft: String) -> Result<()> {
+        self.update(name, |info| {
+            info.minecraft = minecraft;
+            Ok(())
+        })
+    }
+
+    pub fn set_loader(
+        &mut self,
+        name: &str,
+        loader: Loader,
+        loader_version: Option<String>,
+    ) -> Result<()> {
+        self.update(name, |info| {
+            info.loader = loader;
+            info.loader_version = loader_version;
+            Ok(())
+        })
+    }
+
+    // copies worlds, mods and settings too
+    pub fn duplicate(&mut self, name: &str, new_name: String) -> Result<()> {
+        let mut info = self
+            .list
+            .get(name)
+            .ok_or_else(|| anyhow!("Instance not found"))?
+            .clone();
+
+        check_name(&new_name)?;
+
+        let dest = self.get_dir(&new_name);
+        if dest.exists() {
+            bail!("An instance named {} already exists", new_name);
+        }
+
+        copy_dir(&self.get_dir(name), &dest)?;
+
+        info.created = Some(OffsetDateTime::now_utc());
+        info.last_played = None;
+        info.playtime = 0;
+        self.list.insert(new_name.clone(), info);
+        self.save(&new_name)
+    }
+
+    pub fn create(&mut self, name: String, info: Instance) -> Result<()> {
+        check_name(&name)?;
+
         let path = self.get_dir(&name);
         fs::create_dir(&path)?;
 
-        let last_played = OffsetDateTime::now_utc().to_string();
-
-        let info = Instance {
-            last_played,
-            minecraft: minecraft_version,
-            fabric: fabric_version,
-            optimize_jvm,
-            memory,
-        };
-        let info_str = toml::to_string_pretty(&info)?;
-        fs::write(self.get_config_path(&name), info_str)?;
+        file_lock::write(&self.get_config_path(&name), info.to_toml()?)?;
 
         self.list.insert(name, info);
 
         Ok(())
     }
 
-    pub fn launch(&self, name: &str, account: &Account) -> Result<()> {
+    // the caller has to wait for the game and remove it from running
+    pub fn launch(
+        &mut self,
+        name: &str,
+        account: &Account,
+        settings: &Settings,
+        options: &LaunchOptions,
+        running: &mut RunningGames,
+    ) -> Result<process::Child> {
+        if let Some(game) = running.get(name) {
+            bail!("{} is already running (pid {})", name, game.pid);
+        }
+
         let instance = self
             .list
             .get(name)
             .ok_or_else(|| anyhow!("Instance not found"))?;
 
+        if instance.kind == InstanceKind::Server {
+            bail!("{} is a server, start it from the launcher", name);
+        }
+
+        self.update(name, |info| {
+            info.last_played = Some(OffsetDateTime::now_utc());
+            Ok(())
+        })?;
+        let instance = self.list[name].clone();
+
         let version_meta = vanilla_installer::VersionMeta::load(&instance.minecraft)?;
 
-        let java_path = adoptium::get_path("17")?;
+        let mut main_class = version_meta.main_class.clone();
+        let mut loader_paths = Vec::new();
+
+        if let (Loader::Fabric, Some(loader_version)) = (instance.loader, &instance.loader_version)
+        {
+            let fabric_meta = FabricMeta::load(&instance.minecraft, loader_version)?;
+            main_class = fabric_meta.main_class.clone();
+            loader_paths = fabric_meta.get_paths();
+        }
+
+        let arch = version_meta.get_arch();
+        version_meta.check_natives(arch)?;
+
+        if arch != Arch::HOST && instance.java_path.is_none() {
+            runtime_manager::check_rosetta()?;
+        }
+
+        let java_path = instance.get_java_path(arch)?;
+        let assets_dir = version_meta.get_assets_dir(&self.get_dir(name))?;
+
+        let memory = instance.get_memory(settings);
+        jvm::validate_memory(memory)?;
+        let mut jvm_flags = vec![format!("-Xmx{}", memory), format!("-Xms{}", memory)];
+
+        let jvm_preset = instance.jvm_preset.unwrap_or(settings.jvm_preset);
+        let mut preset_flags = jvm_preset.get_flags();
+
+        // only ask the runtime for its version when a flag depends on it
+        let java_version =
+            if settings.garbage_collector != GarbageCollector::Default || instance.profiling {
+                let version = runtime_manager::system::check(&java_path)?;
+                let major = jvm::get_major_version(&version)
+                    .ok_or_else(|| anyhow!("Unknown java version: {}", version))?;
+
+                Some((version, major))
+            } else {
+                None
+            };
+
+        if let Some((version, java_major)) = java_version
+            .as_ref()
+            .filter(|_| settings.garbage_collector != GarbageCollector::Default)
+        {
+            let gc_flags = settings.garbage_collector.get_flags(*java_major)?;
+            if !runtime_manager::system::supports_flags(&java_path, &gc_flags) {
+                bail!(
+                    "{} isn't supported by Java {}, pick a different garbage collector in the settings",
+                    settings.garbage_collector,
+                    version
+                );
+            }
+
+            // the preset's collector is replaced
+            preset_flags.retain(|flag| !jvm::is_gc_flag(flag));
+            preset_flags.extend(gc_flags);
+        }
+
+        jvm_flags.extend(preset_flags.into_iter().map(str::to_string));
+        let natives_dir = self.get_dir(name).join("natives");
+        let has_natives = version_meta.extract_natives(&natives_dir, arch)?;
 
-        let mut jvm_flags = format!("-Xmx{0} -Xms{0}", instance.memory);
+        jvm_flags.extend(instance.jvm_args.iter().cloned());
 
-        if instance.optimize_jvm {
-            jvm_flags.push_str(OPTIMIZED_FLAGS);
+        if let Some((_, java_major)) = java_version.as_ref().filter(|_| instance.profiling) {
+            let session_dir = diagnostics::new_session_dir(&self.get_dir(name))?;
+            jvm_flags.extend(diagnostics::get_flags(&session_dir, *java_major));
+        }
+
+        let user_type = match &account.auth_server {
+            Some(server) => {
+                jvm_flags.extend(authlib_injector::get_flags(server, options.offline)?);
+                "mojang"
+            }
+            None => "msa",
+        };
+
+        let values = HashMap::from([
+            ("auth_player_name", account.mc_username.clone()),
+            ("auth_uuid", account.mc_id.clone()),
+            ("auth_access_token", account.mc_access_token.clone()),
+            // before 1.7.3
+            (
+                "auth_session",
+                format!("token:{}:{}", account.mc_access_token, account.mc_id),
+            ),
+            ("user_type", user_type.to_string()),
+            ("user_properties", "{}".to_string()),
+            ("version_name", instance.minecraft.clone()),
+            ("version_type", version_meta.get_release_type().to_string()),
+            ("game_directory", ".".to_string()),
+            ("assets_root", assets_dir.to_string_lossy().to_string()),
+            ("game_assets", assets_dir.to_string_lossy().to_string()),
+            ("assets_index_name", version_meta.assets.clone()),
+            (
+                "clientid",
+                format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            ),
+            (
+                "natives_directory",
+                natives_dir.to_string_lossy().to_string(),
+            ),
+            ("launcher_name", env!("CARGO_PKG_NAME").to_string()),
+            ("launcher_version", env!("CARGO_PKG_VERSION").to_string()),
+            ("classpath", version_meta.get_classpath(&loader_paths)?),
+        ]);
 
-            if cfg!(target_os = "linux") {
-                jvm_flags.push_str(" -XX:+UseTransparentHugePages");
+        match version_meta.get_jvm_args(&values) {
+            Some(jvm_args) => jvm_flags.extend(jvm_args),
+            None => {
+                if has_natives {
+                    jvm_flags.push(format!("-Djava.library.path={}", natives_dir.display()));
+                }
+
+                if cfg!(target_os = "macos") {
+                    jvm_flags.push("-XstartOnFirstThread".to_string());
+                }
+
+                jvm_flags.extend([
+                    format!("-Dminecraft.launcher.brand={}", env!("CARGO_PKG_NAME")),
+                    format!("-Dminecraft.launcher.version={}", env!("CARGO_PKG_VERSION")),
+                    "-cp".to_string(),
+                    values["classpath"].clone(),
+                ]);
             }
         }
 
-        if cfg!(target_os = "macos") {
-            jvm_flags.push_str(" -XstartOnFirstThread");
+        let mut game_args = version_meta.get_game_args(&values);
+
+        if let Some(width) = instance.window_width.or(settings.window_width) {
+            game_args.push("--width".to_string());
+            game_args.push(width.to_string());
+        }
+
+        if let Some(height) = instance.window_height.or(settings.window_height) {
+            game_args.push("--height".to_string());
+            game_args.push(height.to_string());
         }
 
-        let mut child = process::Command::new(java_path)
+        if options.demo {
+            game_args.push("--demo".to_string());
+        }
+
+        if let Some(world) = &options.quick_play {
+            if !version_meta.supports_quick_play() {
+                bail!("Quick Play needs Minecraft 1.20 or newer (snapshot 23w14a or later)");
+            }
+
+            game_args.push("--quickPlaySingleplayer".to_string());
+            game_args.push(world.clone());
+        }
+
+        let gpu_env = gpu::get_env(instance.gpu.as_deref(), &java_path)?;
+
+        let child = process::Command::new(java_path)
             .current_dir(&self.get_dir(name))
-            .args(jvm_flags.split(' '))
-            .arg("-cp")
-            .arg(version_meta.get_classpath()?)
-            .arg(format!(
-                "-Dminecraft.launcher.brand={}",
-                env!("CARGO_PKG_NAME")
-            ))
-            .arg(format!(
-                "-Dminecraft.launcher.version={}",
-                env!("CARGO_PKG_VERSION")
-            ))
-            .arg(version_meta.main_class)
-            .arg("--username")
-            .arg(&account.mc_username)
-            .arg("--uuid")
-            .arg(&account.mc_id)
-            .arg("--accessToken")
-            .arg(&account.mc_access_token)
-            .arg("--userType")
-            .arg("msa")
-            .arg("--version")
-            .arg(&instance.minecraft)
-            .arg("--gameDir")
-            .arg(".")
-            .arg("--assetsDir")
-            .arg(ASSETS_DIR.to_string_lossy().to_string())
-            .arg("--assetIndex")
-            .arg(version_meta.assets)
-            .arg("--versionType")
-            .arg("release")
-            .arg("--clientId")
-            .arg(format!(
-                "{}/{}",
-                env!("CARGO_PKG_NAME"),
-                env!("CARGO_PKG_VERSION")
-            ))
+            .envs(gpu_env)
+            .envs(&instance.env)
+            .args(jvm_flags)
+            .arg(main_class)
+            .args(game_args)
             .spawn()?;
 
-        println!("Launched instance: {}", name);
+        tracing::info!("Launched instance: {}", name);
 
-        child.wait()?;
-        Ok(())
+        running.add(name, child.id())?;
+
+        Ok(child)
+    }
+
+    // the pipes are left to the caller, to show the console and send commands
+    pub fn start_server(
+        &mut self,
+        name: &str,
+        settings: &Settings,
+        running: &mut RunningGames,
+    ) -> Result<process::Child> {
+        if let Some(game) = running.get(name) {
+            bail!("{} is already running (pid {})", name, game.pid);
+        }
+
+        let dir = self.get_dir(name);
+        if !server::eula_accepted(&dir) {
+            bail!("The Minecraft EULA has to be accepted to start {}", name);
+        }
+
+        self.update(name, |info| {
+            info.last_played = Some(OffsetDateTime::now_utc());
+            Ok(())
+        })?;
+        let instance = self.list[name].clone();
+
+        let version_meta = vanilla_installer::VersionMeta::load(&instance.minecraft)?;
+        let jvm_preset = instance.jvm_preset.unwrap_or(settings.jvm_preset);
+        jvm::validate_memory(instance.get_memory(settings))?;
+
+        let child = process::Command::new(instance.get_java_path(Arch::HOST)?)
+            .current_dir(&dir)
+            .envs(&instance.env)
+            .arg(format!("-Xmx{}", instance.get_memory(settings)))
+            .arg(format!("-Xms{}", instance.get_memory(settings)))
+            .args(jvm_preset.get_flags())
+            .args(&instance.jvm_args)
+            .arg("-jar")
+            .arg(version_meta.get_server_path())
+            .arg("nogui")
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .stderr(process::Stdio::piped())
+            .spawn()?;
+
+        tracing::info!("Started server: {}", name);
+
+        running.add(name, child.id())?;
+
+        Ok(child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_names_outside_the_instances_folder() {
+        assert!(check_name("Survival (2)").is_ok());
+        assert!(check_name("../escaped").is_err());
+        assert!(check_name("nested/name").is_err());
+        assert!(check_name("..\\escaped").is_err());
+        assert!(check_name("").is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut instance = Instance::new(
+            "1.20.1".to_string(),
+            Loader::Fabric,
+            Some("0.14.22".to_string()),
+            Some(JvmPreset::Aikar),
+            Some("4G".to_string()),
+        );
+        instance.jvm_args = vec!["-Dfml.ignorePatchDiscrepancies=true".to_string()];
+        instance.playtime = 3600;
+        instance.tags = vec!["1.20".to_string(), "with-friends".to_string()];
+        instance
+            .env
+            .insert("__GL_THREADED_OPTIMIZATIONS".to_string(), "1".to_string());
+        instance.notes = "Played with friends".to_string();
+        instance.source = Some(SourcePack {
+            provider: "modrinth".to_string(),
+            project_id: "1KVo5zza".to_string(),
+            version_id: "8o4Jfmzt".to_string(),
+        });
+
+        let content = instance.to_toml().unwrap();
+        let parsed = Instance::from_toml(&content).unwrap();
+
+        assert_eq!(parsed, instance);
+    }
+
+    #[test]
+    fn keeps_unknown_keys() {
+        let content = r#"
+            schema_version = 1
+            minecraft = "1.20.1"
+            loader = "vanilla"
+            optimize_jvm = false
+            memory = "2G"
+            my_tool_id = "abc"
+        "#;
+
+        let instance = Instance::from_toml(content).unwrap();
+        assert_eq!(instance.extra["my_tool_id"].as_str(), Some("abc"));
+
+        let content = instance.to_toml().unwrap();
+        assert!(content.contains("my_tool_id = \"abc\""));
+    }
+
+    #[test]
+    fn migrates_unversioned() {
+        let content = r#"
+            last_played = "2023-10-01 12:00:00.0 +00:00:00"
+            minecraft = "1.20.1"
+            fabric = "0.14.22"
+            optimize_jvm = true
+            memory = "4G"
+        "#;
+
+        let instance = Instance::from_toml(content).unwrap();
+        assert_eq!(instance.schema_version, SCHEMA_VERSION);
+        assert_eq!(instance.loader, Loader::Fabric);
+        assert_eq!(instance.loader_version.as_deref(), Some("0.14.22"));
+        assert_eq!(
+            instance.last_played,
+            Some(time::macros::datetime!(2023-10-01 12:00 UTC))
+        );
+        assert_eq!(instance.jvm_preset, Some(JvmPreset::Optimized));
+        assert_eq!(instance.memory, None);
+        assert!(instance.extra.is_empty());
+    }
+
+    #[test]
+    fn migrates_optimize_jvm() {
+        let content = r#"
+            schema_version = 1
+            minecraft = "1.20.1"
+            loader = "vanilla"
+            optimize_jvm = false
+            memory = "2G"
+        "#;
+
+        let instance = Instance::from_toml(content).unwrap();
+        assert_eq!(instance.schema_version, SCHEMA_VERSION);
+        assert_eq!(instance.jvm_preset, Some(JvmPreset::None));
+        assert_eq!(instance.memory.as_deref(), Some("2G"));
+        assert!(instance.extra.is_empty());
+    }
+
+    #[test]
+    fn migrates_groups_to_tags() {
+        let content = r#"
+            schema_version = 2
+            minecraft = "1.20.1"
+            loader = "vanilla"
+            memory = "2G"
+            groups = ["Survival", "1.20"]
+            tags = ["1.20"]
+        "#;
+
+        let instance = Instance::from_toml(content).unwrap();
+        assert_eq!(instance.schema_version, SCHEMA_VERSION);
+        assert_eq!(instance.tags, ["1.20", "Survival"]);
+        assert!(instance.extra.is_empty());
+    }
+
+    #[test]
+    fn parses_trashed_instances() {
+        let trashed = parse_trashed(PathBuf::from(".trash/1700000000-my-world")).unwrap();
+
+        assert_eq!(trashed.name, "my-world");
+        assert_eq!(trashed.deleted.unix_timestamp(), 1700000000);
+        assert!(parse_trashed(PathBuf::from(".trash/not-trashed")).is_none());
     }
 }
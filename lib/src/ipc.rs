@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use crate::paths::IPC_PORT_PATH;
+
+// the first launcher listens, the ones started later hand it their arguments and exit
+pub fn listen() -> Result<TcpListener> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    fs::write(&*IPC_PORT_PATH, listener.local_addr()?.port().to_string())?;
+
+    Ok(listener)
+}
+
+// fails if no launcher is running
+pub fn send(message: &str) -> Result<()> {
+    let port = fs::read_to_string(&*IPC_PORT_PATH)?.trim().parse::<u16>()?;
+
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    writeln!(stream, "{message}")?;
+
+    // the port could be stale and taken by something else
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() != "ok" {
+        bail!("Unexpected reply: {}", reply);
+    }
+
+    Ok(())
+}
+
+// blocks until the next message
+pub fn accept(listener: &TcpListener) -> Result<String> {
+    let (mut stream, _) = listener.accept()?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut message = String::new();
+    BufReader::new(&stream).read_line(&mut message)?;
+    writeln!(stream, "ok")?;
+
+    Ok(message.trim().to_string())
+}
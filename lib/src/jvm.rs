@@ -0,0 +1,193 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+// https://github.com/brucethemoose/Minecraft-Performance-Flags-Benchmarks
+const OPTIMIZED_FLAGS: &str = "-XX:+UnlockExperimentalVMOptions -XX:+UnlockDiagnosticVMOptions -XX:+AlwaysActAsServerClassMachine -XX:+AlwaysPreTouch -XX:+DisableExplicitGC -XX:+UseNUMA -XX:NmethodSweepActivity=1 -XX:ReservedCodeCacheSize=400M -XX:NonNMethodCodeHeapSize=12M -XX:ProfiledCodeHeapSize=194M -XX:NonProfiledCodeHeapSize=194M -XX:-DontCompileHugeMethods -XX:MaxNodeLimit=240000 -XX:NodeLimitFudgeFactor=8000 -XX:+UseVectorCmov -XX:+PerfDisableSharedMem -XX:+UseFastUnorderedTimeStamps -XX:+UseCriticalJavaThreadPriority -XX:ThreadPriorityPolicy=1 -XX:AllocatePrefetchStyle=3 -XX:+UseShenandoahGC -XX:ShenandoahGCMode=iu -XX:ShenandoahGuaranteedGCInterval=1000000 -XX:AllocatePrefetchStyle=1";
+
+// https://docs.papermc.io/paper/aikars-flags
+const AIKAR_FLAGS: &str = "-XX:+UseG1GC -XX:+ParallelRefProcEnabled -XX:MaxGCPauseMillis=200 -XX:+UnlockExperimentalVMOptions -XX:+DisableExplicitGC -XX:+AlwaysPreTouch -XX:G1NewSizePercent=30 -XX:G1MaxNewSizePercent=40 -XX:G1HeapRegionSize=8M -XX:G1ReservePercent=20 -XX:G1HeapWastePercent=5 -XX:G1MixedGCCountTarget=4 -XX:InitiatingHeapOccupancyPercent=15 -XX:G1MixedGCLiveThresholdPercent=90 -XX:G1RSetUpdatingPauseTimePercent=5 -XX:SurvivorRatio=32 -XX:+PerfDisableSharedMem -XX:MaxTenuringThreshold=1";
+
+// gives memory back to the system as soon as possible
+const LOW_MEMORY_FLAGS: &str = "-XX:+UseSerialGC -XX:MinHeapFreeRatio=10 -XX:MaxHeapFreeRatio=20 -XX:ReservedCodeCacheSize=64M -Xss512k";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JvmPreset {
+    None,
+    #[default]
+    Optimized,
+    Aikar,
+    LowMemory,
+}
+
+impl JvmPreset {
+    pub const ALL: [JvmPreset; 4] = [
+        JvmPreset::None,
+        JvmPreset::Optimized,
+        JvmPreset::Aikar,
+        JvmPreset::LowMemory,
+    ];
+
+    pub fn get_flags(&self) -> Vec<&'static str> {
+        let mut flags = match self {
+            JvmPreset::None => Vec::new(),
+            JvmPreset::Optimized => OPTIMIZED_FLAGS.split(' ').collect(),
+            JvmPreset::Aikar => AIKAR_FLAGS.split(' ').collect(),
+            JvmPreset::LowMemory => LOW_MEMORY_FLAGS.split(' ').collect(),
+        };
+
+        if *self == JvmPreset::Optimized && cfg!(target_os = "linux") {
+            flags.push("-XX:+UseTransparentHugePages");
+        }
+
+        flags
+    }
+}
+
+impl fmt::Display for JvmPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JvmPreset::None => write!(f, "No extra flags"),
+            JvmPreset::Optimized => write!(f, "Optimized"),
+            JvmPreset::Aikar => write!(f, "Aikar's flags"),
+            JvmPreset::LowMemory => write!(f, "Low memory"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GarbageCollector {
+    // whatever the preset or the runtime picks
+    #[default]
+    Default,
+    G1,
+    Z,
+    Shenandoah,
+}
+
+impl GarbageCollector {
+    pub const ALL: [GarbageCollector; 4] = [
+        GarbageCollector::Default,
+        GarbageCollector::G1,
+        GarbageCollector::Z,
+        GarbageCollector::Shenandoah,
+    ];
+
+    pub fn get_flags(&self, java_major: u32) -> Result<Vec<&'static str>> {
+        let flags = match self {
+            GarbageCollector::Default => Vec::new(),
+            GarbageCollector::G1 => vec!["-XX:+UseG1GC"],
+            GarbageCollector::Z => match java_major {
+                0..=10 => bail!("ZGC needs Java 11 or newer"),
+                11..=14 => vec!["-XX:+UnlockExperimentalVMOptions", "-XX:+UseZGC"],
+                15..=20 => vec!["-XX:+UseZGC"],
+                // generational mode became the default in 23
+                21..=22 => vec!["-XX:+UseZGC", "-XX:+ZGenerational"],
+                _ => vec!["-XX:+UseZGC"],
+            },
+            GarbageCollector::Shenandoah => match java_major {
+                0..=11 => bail!("Shenandoah needs Java 12 or newer"),
+                _ => vec!["-XX:+UseShenandoahGC"],
+            },
+        };
+
+        Ok(flags)
+    }
+}
+
+impl fmt::Display for GarbageCollector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GarbageCollector::Default => write!(f, "Default"),
+            GarbageCollector::G1 => write!(f, "G1"),
+            GarbageCollector::Z => write!(f, "ZGC"),
+            GarbageCollector::Shenandoah => write!(f, "Shenandoah"),
+        }
+    }
+}
+
+// flags that only make sense together with a specific collector
+pub fn is_gc_flag(flag: &str) -> bool {
+    (flag.starts_with("-XX:+Use") && flag.ends_with("GC"))
+        || flag.starts_with("-XX:Shenandoah")
+        || flag.starts_with("-XX:G1")
+}
+
+// e.g. 8 for 1.8.0_381 and 17 for 17.0.8
+pub fn get_major_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(['.', '_', '-', '+']);
+    let major = parts.next()?.parse::<u32>().ok()?;
+
+    if major == 1 {
+        parts.next()?.parse().ok()
+    } else {
+        Some(major)
+    }
+}
+
+// what -Xmx takes, limited to sizes that make sense for the game
+pub fn validate_memory(memory: &str) -> Result<()> {
+    // the unit may be any character while typing, so no byte slicing
+    let megabytes = if let Some(amount) = memory.strip_suffix(['M', 'm']) {
+        amount.parse::<u64>().ok()
+    } else if let Some(amount) = memory.strip_suffix(['G', 'g']) {
+        amount
+            .parse::<u64>()
+            .ok()
+            .map(|amount| amount.saturating_mul(1024))
+    } else {
+        None
+    };
+
+    let Some(megabytes) = megabytes else {
+        bail!("Invalid memory \"{memory}\", use a number followed by M or G, like 4G or 4096M")
+    };
+
+    if megabytes < 256 {
+        bail!("{memory} isn't enough memory to run the game");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_memory() {
+        assert!(validate_memory("4G").is_ok());
+        assert!(validate_memory("4096m").is_ok());
+        assert!(validate_memory("4").is_err());
+        assert!(validate_memory("4 G").is_err());
+        assert!(validate_memory("G").is_err());
+        assert!(validate_memory("128M").is_err());
+        assert!(validate_memory("4é").is_err());
+        assert!(validate_memory("é").is_err());
+    }
+
+    #[test]
+    fn parses_major_version() {
+        assert_eq!(get_major_version("1.8.0_381"), Some(8));
+        assert_eq!(get_major_version("17.0.8"), Some(17));
+        assert_eq!(get_major_version("21"), Some(21));
+        assert_eq!(get_major_version("21-ea"), Some(21));
+        assert_eq!(get_major_version("java"), None);
+    }
+
+    #[test]
+    fn checks_gc_support() {
+        assert!(GarbageCollector::Z.get_flags(8).is_err());
+        assert!(GarbageCollector::Shenandoah.get_flags(11).is_err());
+        assert_eq!(
+            GarbageCollector::Z.get_flags(17).unwrap(),
+            vec!["-XX:+UseZGC"]
+        );
+    }
+}
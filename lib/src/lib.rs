@@ -2,22 +2,59 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub mod accounts;
-pub mod adoptium;
+pub mod authlib_injector;
+pub mod backups;
+pub mod cache;
+pub mod classpath;
+pub mod config_transfer;
+pub mod crash_reporter;
+pub mod deep_link;
+pub mod diagnostics;
+pub mod experiments;
 pub mod fabric;
+pub mod feedback;
+pub mod file_lock;
+pub mod gpu;
+pub mod hooks;
+pub mod i18n;
+pub mod import;
+pub mod instance_export;
 pub mod instances;
+pub mod ipc;
+pub mod jvm;
+pub mod logging;
+pub mod markdown;
 pub mod modrinth;
+pub mod nbt;
+pub mod network;
 pub mod paths;
+pub mod running;
+pub mod runtime_manager;
+pub mod sealed;
+pub mod server;
+pub mod server_list;
 pub mod settings;
+pub mod skin;
+pub mod store;
 pub mod updater;
 pub mod vanilla_installer;
+pub mod world;
 
 use std::{
-    fs::{self, File},
-    io::{self, BufReader, BufWriter, Read, Seek},
-    path::PathBuf,
+    cell::Cell,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
 };
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, Context as _, Result};
 use digest::Digest;
 use flate2::bufread::GzDecoder;
 use once_cell::sync::Lazy;
@@ -31,6 +68,193 @@ use zip::ZipArchive;
 const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 pub static AGENT: Lazy<Agent> = Lazy::new(|| AgentBuilder::new().user_agent(USER_AGENT).build());
 
+const MIRRORS: &[(&str, &str)] = &[
+    (
+        "https://libraries.minecraft.net/",
+        "https://bmclapi2.bangbang93.com/maven/",
+    ),
+    (
+        "https://resources.download.minecraft.net/",
+        "https://bmclapi2.bangbang93.com/assets/",
+    ),
+];
+
+// 0.5s, 1s, 2s... up to 32s, attempt starts at 1
+fn get_backoff(attempt: u32) -> Duration {
+    Duration::from_millis(500 << attempt.saturating_sub(1).min(6))
+}
+
+// a 404 or 403 won't go away by asking again, the mirror is still tried
+fn is_retryable(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<ureq::Error>() {
+        Some(ureq::Error::Status(status, _)) => *status == 429 || *status >= 500,
+        _ => true,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadOptions {
+    pub threads: usize,
+    pub retries: u32,
+    pub use_mirrors: bool,
+    // KiB/s, 0 means unlimited
+    pub speed_limit: u32,
+}
+
+// Shared by all download threads so the limit applies to the whole queue
+struct Throttle {
+    bytes_per_second: u64,
+    window: Mutex<(Instant, u64)>,
+}
+
+impl Throttle {
+    fn new(speed_limit: u32) -> Option<Self> {
+        if speed_limit == 0 {
+            return None;
+        }
+
+        Some(Self {
+            bytes_per_second: speed_limit as u64 * 1024,
+            window: Mutex::new((Instant::now(), 0)),
+        })
+    }
+
+    fn consume(&self, bytes: usize) {
+        let mut window = self.window.lock().unwrap();
+        window.1 += bytes as u64;
+
+        let expected = Duration::from_secs_f64(window.1 as f64 / self.bytes_per_second as f64);
+        let elapsed = window.0.elapsed();
+
+        // the lock is held while sleeping on purpose, other threads have to wait too
+        if expected > elapsed {
+            thread::sleep(expected - elapsed);
+        }
+
+        if window.0.elapsed() > Duration::from_secs(1) {
+            *window = (Instant::now(), 0);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DownloadControl {
+    paused: Arc<AtomicBool>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl DownloadControl {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    // blocks while paused, fails once cancelled
+    fn wait(&self) -> io::Result<()> {
+        loop {
+            if self.is_cancelled() {
+                return Err(io::Error::other("download cancelled"));
+            }
+
+            if !self.is_paused() {
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+// the usize is the index of the worker thread
+#[derive(Debug)]
+pub enum DownloadEvent {
+    Started(usize, DownloadItem),
+    Transferred(usize, u64),
+    // entries for zip files, compressed bytes for tarballs
+    Extracted {
+        path: PathBuf,
+        done: u64,
+        total: u64,
+    },
+    Finished(usize, DownloadItem),
+    // interrupted by a pause, the item goes back into the queue
+    Parked(usize),
+    Failed(anyhow::Error),
+}
+
+// Everything a queued download shares with the rest of the queue
+#[derive(Default)]
+struct Context<'a> {
+    worker: usize,
+    throttle: Option<&'a Throttle>,
+    control: Option<&'a DownloadControl>,
+    events: Option<&'a mpsc::Sender<DownloadEvent>>,
+}
+
+struct DownloadReader<'a, R> {
+    inner: R,
+    context: &'a Context<'a>,
+}
+
+impl<R: Read> Read for DownloadReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if let Some(control) = self.context.control {
+            if control.is_cancelled() {
+                return Err(io::Error::other("download cancelled"));
+            }
+
+            // drop the connection instead of holding it open, the partial file is resumed later
+            if control.is_paused() {
+                return Err(io::Error::other("download paused"));
+            }
+        }
+
+        let count = self.inner.read(buf)?;
+
+        if let Some(throttle) = self.context.throttle {
+            throttle.consume(count);
+        }
+
+        if let Some(events) = self.context.events {
+            let _ = events.send(DownloadEvent::Transferred(
+                self.context.worker,
+                count as u64,
+            ));
+        }
+
+        Ok(count)
+    }
+}
+
+struct CountingReader<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.count.set(self.count.get() + count as u64);
+
+        Ok(count)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HashAlgorithm {
     Sha1,
@@ -59,17 +283,73 @@ pub struct DownloadItem {
     pub url: String,
     pub path: PathBuf,
     pub hash: Option<Hash>,
+    // used to weight the progress, if known
+    pub size: Option<u64>,
     pub extract: bool,
 }
 
 impl DownloadItem {
     pub fn download_file(&self) -> Result<()> {
+        self.download_from(&self.url, &Context::default())
+            .with_context(|| format!("Failed to download {}", self.url))
+    }
+
+    fn download_file_with_retries(
+        &self,
+        options: &DownloadOptions,
+        context: &Context,
+    ) -> Result<()> {
+        let mirror = if options.use_mirrors {
+            self.get_mirror_url()
+        } else {
+            None
+        };
+        let urls = std::iter::once(self.url.as_str()).chain(mirror.as_deref());
+
+        let mut result = Ok(());
+
+        for url in urls {
+            for attempt in 0..=options.retries {
+                if context
+                    .control
+                    .is_some_and(|c| c.is_cancelled() || c.is_paused())
+                {
+                    return result.and(Err(anyhow!("download interrupted")));
+                }
+
+                if attempt > 0 {
+                    let backoff = get_backoff(attempt);
+                    tracing::warn!("retrying in {:?}: {}", backoff, url);
+                    thread::sleep(backoff);
+                }
+
+                result = self.download_from(url, context);
+                match &result {
+                    Ok(()) => return result,
+                    Err(error) if !is_retryable(error) => break,
+                    Err(_) => {}
+                }
+            }
+        }
+
+        result.with_context(|| format!("Failed to download {}", self.url))
+    }
+
+    fn get_mirror_url(&self) -> Option<String> {
+        MIRRORS.iter().find_map(|(origin, mirror)| {
+            self.url
+                .strip_prefix(origin)
+                .map(|rest| format!("{}{}", mirror, rest))
+        })
+    }
+
+    fn download_from(&self, url: &str, context: &Context) -> Result<()> {
         if self.path.exists() {
-            println!("file already exists: {}", self.path.display());
+            tracing::debug!("file already exists: {}", self.path.display());
             return Ok(());
         }
 
-        println!("downloading file: {} to {}", self.url, self.path.display());
+        tracing::debug!("downloading file: {} to {}", url, self.path.display());
 
         // create parent directory
         {
@@ -80,51 +360,168 @@ impl DownloadItem {
             fs::create_dir_all(parent)?;
         }
 
-        let response = AGENT.get(&self.url).call()?;
-        let mut file = NamedTempFile::new()?;
+        let part_path = self.get_part_path();
+
+        // resume a previously interrupted download
+        let offset = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = AGENT.get(url);
+        if offset > 0 {
+            tracing::debug!("resuming download from byte {}", offset);
+            request = request.set("Range", &format!("bytes={}-", offset));
+        }
+        let response = match request.call() {
+            Ok(response) => Some(response),
+            // the partial file is already complete
+            Err(ureq::Error::Status(416, _)) if offset > 0 => None,
+            Err(error) => return Err(error.into()),
+        };
 
         // write to file
-        {
-            let mut writer = BufWriter::new(&mut file);
-            io::copy(&mut response.into_reader(), &mut writer)?;
-            writer.seek(io::SeekFrom::Start(0))?;
+        if let Some(response) = response {
+            // the server may ignore the range and send the whole file
+            let file = if response.status() == 206 {
+                OpenOptions::new().append(true).open(&part_path)?
+            } else {
+                File::create(&part_path)?
+            };
+
+            let mut writer = BufWriter::new(file);
+            let mut reader = DownloadReader {
+                inner: response.into_reader(),
+                context,
+            };
+
+            if let Err(error) = io::copy(&mut reader, &mut writer) {
+                // a cancelled download is not going to be resumed
+                if context.control.is_some_and(|c| c.is_cancelled()) {
+                    drop(writer);
+                    fs::remove_file(&part_path)?;
+                }
+
+                return Err(error.into());
+            }
+
+            writer.flush()?;
         }
 
         // check hash
         if let Some(hash) = &self.hash {
-            let mut reader = BufReader::new(&mut file);
-            check_hash(&mut reader, hash)?;
-            reader.seek(io::SeekFrom::Start(0))?;
+            let reader = BufReader::new(File::open(&part_path)?);
+
+            if let Err(error) = check_hash(reader, hash) {
+                // don't resume from a corrupted file
+                fs::remove_file(&part_path)?;
+                return Err(error);
+            }
         }
 
         if self.extract {
-            println!("extracting archive: {}", self.path.display());
+            tracing::debug!("extracting archive: {}", self.path.display());
 
-            let reader = BufReader::new(&file);
+            if let Err(error) = self.extract_archive(&part_path, context) {
+                // keep the verified archive around if the user just cancelled
+                if !context.control.is_some_and(|c| c.is_cancelled()) {
+                    fs::remove_file(&part_path)?;
+                }
 
-            if self.url.ends_with(".zip") || self.url.ends_with(".mrpack") {
-                let mut archive = ZipArchive::new(reader)?;
-                archive.extract(self.path.parent().unwrap())?;
-            } else if self.url.ends_with(".tar.gz") {
-                let mut archive = Archive::new(GzDecoder::new(reader));
-                archive.unpack(self.path.parent().unwrap())?;
-            } else {
-                fs::remove_file(&self.path)?;
-                bail!("unsupported archive format: {}", self.url);
+                return Err(error);
             }
 
-            fs::remove_file(&file)?;
+            fs::remove_file(&part_path)?;
         } else {
             // move file to destination
-            fs::rename(file, &self.path)?;
+            fs::rename(&part_path, &self.path)?;
         }
 
         Ok(())
     }
 
+    fn extract_archive(&self, archive_path: &Path, context: &Context) -> Result<()> {
+        let dir = self.path.parent().unwrap();
+        let file = File::open(archive_path)?;
+
+        let report = |done, total| {
+            if let Some(events) = context.events {
+                let _ = events.send(DownloadEvent::Extracted {
+                    path: self.path.clone(),
+                    done,
+                    total,
+                });
+            }
+        };
+
+        if self.url.ends_with(".zip") || self.url.ends_with(".mrpack") {
+            let mut archive = ZipArchive::new(BufReader::new(file))?;
+            let total = archive.len() as u64;
+
+            for i in 0..archive.len() {
+                if let Some(control) = context.control {
+                    control.wait()?;
+                }
+
+                let mut entry = archive.by_index(i)?;
+
+                // skip entries that would end up outside of dir
+                let Some(name) = entry.enclosed_name() else {
+                    continue;
+                };
+                let out_path = dir.join(name);
+
+                if entry.is_dir() {
+                    fs::create_dir_all(&out_path)?;
+                } else {
+                    if let Some(parent) = out_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+
+                    let mut writer = File::create(&out_path)?;
+                    io::copy(&mut entry, &mut writer)?;
+
+                    #[cfg(unix)]
+                    if let Some(mode) = entry.unix_mode() {
+                        use std::os::unix::fs::PermissionsExt;
+                        fs::set_permissions(&out_path, fs::Permissions::from_mode(mode))?;
+                    }
+                }
+
+                report(i as u64 + 1, total);
+            }
+        } else if self.url.ends_with(".tar.gz") {
+            let total = file.metadata()?.len();
+            let done = Rc::new(Cell::new(0));
+            let reader = CountingReader {
+                inner: file,
+                count: Rc::clone(&done),
+            };
+
+            let mut archive = Archive::new(GzDecoder::new(BufReader::new(reader)));
+
+            for entry in archive.entries()? {
+                if let Some(control) = context.control {
+                    control.wait()?;
+                }
+
+                entry?.unpack_in(dir)?;
+                report(done.get(), total);
+            }
+        } else {
+            bail!("unsupported archive format: {}", self.url);
+        }
+
+        Ok(())
+    }
+
+    fn get_part_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".part");
+
+        PathBuf::from(path)
+    }
+
     pub fn download_json<T: for<'a> serde::Deserialize<'a>>(&self) -> Result<T> {
         if self.path.exists() {
-            println!("json already exists: {}", self.path.display());
+            tracing::debug!("json already exists: {}", self.path.display());
 
             let file = File::open(&self.path)?;
             let reader = BufReader::new(file);
@@ -133,7 +530,7 @@ impl DownloadItem {
             return Ok(json);
         }
 
-        println!("downloading json: {} to {}", self.url, self.path.display());
+        tracing::debug!("downloading json: {} to {}", self.url, self.path.display());
 
         // create parent directory
         {
@@ -144,7 +541,10 @@ impl DownloadItem {
             fs::create_dir_all(parent)?;
         }
 
-        let response = AGENT.get(&self.url).call()?;
+        let response = AGENT
+            .get(&self.url)
+            .call()
+            .with_context(|| format!("Failed to download {}", self.url))?;
         let file = NamedTempFile::new()?;
 
         // write to file
@@ -171,7 +571,7 @@ impl DownloadItem {
     }
 }
 
-fn calc_hash<D: Digest>(mut reader: impl Read + Seek) -> Result<String> {
+pub(crate) fn calc_hash<D: Digest>(mut reader: impl Read) -> Result<String> {
     let mut hasher = D::new();
 
     loop {
@@ -183,14 +583,13 @@ fn calc_hash<D: Digest>(mut reader: impl Read + Seek) -> Result<String> {
         hasher.update(&buffer[..count]);
     }
 
-    let digest = hasher.finalize();
-    let digest = format!("{:x?}", digest.as_slice());
+    let digest = hex::encode(hasher.finalize());
 
     Ok(digest)
 }
 
 fn check_hash(reader: impl Read + Seek, hash: &Hash) -> Result<()> {
-    println!("checking hash: {:?} {}", hash.function, hash.hash);
+    tracing::trace!("checking hash: {:?} {}", hash.function, hash.hash);
 
     let digest = match hash.function {
         HashAlgorithm::Sha1 => calc_hash::<Sha1>(reader)?,
@@ -217,12 +616,116 @@ impl DownloadQueue {
         self.0.len()
     }
 
-    pub fn download_next(&mut self) -> Result<bool> {
-        if let Some(item) = self.0.pop() {
-            item.download_file()?;
-            Ok(true)
-        } else {
-            Ok(false)
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // keeps only the items that are missing or corrupted, deleting the latter
+    pub fn verify(self) -> Result<Self> {
+        let mut items = Vec::new();
+
+        for item in self.0 {
+            if !item.path.exists() {
+                items.push(item);
+                continue;
+            }
+
+            // the hash of an archive can't be checked once it's extracted
+            if item.extract {
+                continue;
+            }
+
+            if let Some(hash) = &item.hash {
+                let reader = BufReader::new(File::open(&item.path)?);
+
+                if check_hash(reader, hash).is_err() {
+                    tracing::warn!("corrupted file: {}", item.path.display());
+                    fs::remove_file(&item.path)?;
+                    items.push(item);
+                }
+            }
         }
+
+        Ok(Self(items))
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.0.iter().filter_map(|item| item.size).sum()
+    }
+
+    pub fn append(&mut self, other: DownloadQueue) {
+        for item in other.0 {
+            if !self.0.iter().any(|i| i.path == item.path) {
+                self.0.push(item);
+            }
+        }
+    }
+
+    pub fn start(
+        self,
+        options: DownloadOptions,
+        control: DownloadControl,
+    ) -> mpsc::Receiver<DownloadEvent> {
+        let (sender, receiver) = mpsc::channel();
+        let items = Arc::new(Mutex::new(self.0));
+        let throttle = Arc::new(Throttle::new(options.speed_limit));
+
+        for worker in 0..options.threads.max(1) {
+            let items = Arc::clone(&items);
+            let throttle = Arc::clone(&throttle);
+            let control = control.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                let context = Context {
+                    worker,
+                    throttle: throttle.as_ref().as_ref(),
+                    control: Some(&control),
+                    events: Some(&sender),
+                };
+
+                loop {
+                    if control.wait().is_err() {
+                        break;
+                    }
+
+                    let item = items.lock().unwrap().pop();
+
+                    let Some(item) = item else {
+                        break;
+                    };
+
+                    let _ = sender.send(DownloadEvent::Started(worker, item.clone()));
+
+                    let event = match item.download_file_with_retries(&options, &context) {
+                        Ok(()) => DownloadEvent::Finished(worker, item),
+                        Err(_) if control.is_paused() && !control.is_cancelled() => {
+                            items.lock().unwrap().push(item);
+                            DownloadEvent::Parked(worker)
+                        }
+                        Err(error) => DownloadEvent::Failed(error),
+                    };
+
+                    // the receiver is gone, nobody cares about the remaining items
+                    if sender.send(event).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        receiver
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_the_backoff() {
+        assert_eq!(get_backoff(1), Duration::from_millis(500));
+        assert_eq!(get_backoff(3), Duration::from_secs(2));
+        assert_eq!(get_backoff(u32::MAX), Duration::from_secs(32));
     }
 }
@@ -0,0 +1,273 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+// just enough markdown for modrinth descriptions: inline formatting, images and
+// html tags are dropped, only the text and the block structure are kept
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    ListItem(String),
+    Quote(String),
+    Code(String),
+    // a paragraph that is only a link, like "[Discord](https://...)"
+    Link(String, String),
+    Rule,
+}
+
+fn html_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" | "#39" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => None,
+    }
+}
+
+// "[text](url)" at the start of the string, with the length that was consumed;
+// the text can hold brackets too, e.g. a badge "[![alt](image)](url)"
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let rest = text.strip_prefix('[')?;
+
+    let mut depth = 0;
+    let close = rest.char_indices().find_map(|(i, c)| match c {
+        '[' => {
+            depth += 1;
+            None
+        }
+        ']' if depth == 0 => Some(i),
+        ']' => {
+            depth -= 1;
+            None
+        }
+        _ => None,
+    })?;
+
+    let url = rest[close + 1..].strip_prefix('(')?;
+    let url_end = url.find(')')?;
+
+    Some((&rest[..close], &url[..url_end], close + url_end + 4))
+}
+
+fn clean_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        let rest = &text[i..];
+
+        if let Some(image) = rest.strip_prefix('!') {
+            if let Some((_, _, len)) = parse_link(image) {
+                i += len + 1;
+                continue;
+            }
+        }
+
+        if let Some((label, _, len)) = parse_link(rest) {
+            out.push_str(&clean_inline(label));
+            i += len;
+            continue;
+        }
+
+        if rest.starts_with('<') {
+            if let Some(end) = rest.find('>') {
+                let tag = rest[1..end].trim_end_matches('/').trim().to_lowercase();
+                if tag == "br" {
+                    out.push('\n');
+                }
+                i += end + 1;
+                continue;
+            }
+        }
+
+        if rest.starts_with('&') {
+            if let Some(end) = rest.find(';').filter(|end| *end < 8) {
+                if let Some(c) = html_entity(&rest[1..end]) {
+                    out.push(c);
+                    i += end + 1;
+                    continue;
+                }
+            }
+        }
+
+        // single underscores are left alone, they are common in mod ids
+        if let Some(marker) = ["**", "__", "~~", "*", "`"]
+            .into_iter()
+            .find(|marker| rest.starts_with(marker))
+        {
+            i += marker.len();
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap();
+        out.push(c);
+        i += c.len_utf8();
+    }
+
+    out.trim().to_string()
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|bullet| line.strip_prefix(bullet))
+    {
+        return Some(item);
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        return line[digits..].strip_prefix(". ");
+    }
+
+    None
+}
+
+fn is_rule(line: &str) -> bool {
+    let line = line.replace(' ', "");
+
+    line.len() >= 3
+        && ['-', '*', '_']
+            .into_iter()
+            .any(|c| line.chars().all(|other| other == c))
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    if paragraph.is_empty() {
+        return;
+    }
+
+    let raw = paragraph.join(" ");
+    paragraph.clear();
+
+    if let Some((label, url, len)) = parse_link(raw.trim()) {
+        if len == raw.trim().len() {
+            let mut text = clean_inline(label);
+            // a badge, named by the image's alt text
+            if text.is_empty() {
+                if let Some((alt, _, _)) = label.strip_prefix('!').and_then(parse_link) {
+                    text = alt.to_string();
+                }
+            }
+
+            blocks.push(Block::Link(text, url.to_string()));
+            return;
+        }
+    }
+
+    let text = clean_inline(&raw);
+    if !text.is_empty() {
+        blocks.push(Block::Paragraph(text));
+    }
+}
+
+pub fn parse(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(Block::Code(lines.join("\n"))),
+                None => {
+                    flush_paragraph(&mut paragraph, &mut blocks);
+                    code = Some(Vec::new());
+                }
+            }
+            continue;
+        }
+
+        if let Some(lines) = &mut code {
+            lines.push(line);
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            let text = clean_inline(&trimmed[hashes..]);
+            if !text.is_empty() {
+                blocks.push(Block::Heading(hashes, text));
+            }
+        } else if is_rule(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(Block::Rule);
+        } else if let Some(item) = list_item(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(Block::ListItem(clean_inline(item)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(Block::Quote(clean_inline(quote)));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    flush_paragraph(&mut paragraph, &mut blocks);
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines.join("\n")));
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_description() {
+        let markdown = "# **Sodium**\n\
+            <p align=\"center\"><img src=\"logo.png\"></p>\n\
+            \n\
+            A *fast* rendering\nengine for [Fabric](https://fabricmc.net) &amp; Quilt.\n\
+            ![screenshot](shot.png)\n\
+            \n\
+            - Faster chunks\n\
+            2. Better `fps`\n\
+            \n\
+            ---\n\
+            ```\nkey = value\n```\n\
+            [Discord](https://discord.gg/x)";
+
+        assert_eq!(
+            parse(markdown),
+            vec![
+                Block::Heading(1, "Sodium".to_string()),
+                Block::Paragraph("A fast rendering engine for Fabric & Quilt.".to_string()),
+                Block::ListItem("Faster chunks".to_string()),
+                Block::ListItem("Better fps".to_string()),
+                Block::Rule,
+                Block::Code("key = value".to_string()),
+                Block::Link("Discord".to_string(), "https://discord.gg/x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_nested_links() {
+        assert_eq!(
+            parse("[![Discord](badge.svg)](https://discord.gg/x)"),
+            vec![Block::Link(
+                "Discord".to_string(),
+                "https://discord.gg/x".to_string()
+            )]
+        );
+        assert_eq!(
+            parse("See [![logo](logo.png) the wiki](https://wiki.example) too"),
+            vec![Block::Paragraph("See the wiki too".to_string())]
+        );
+    }
+}
@@ -1,18 +1,62 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::{fs, io::BufReader, path::Path};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+    thread,
+};
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use serde::Deserialize;
+use serde_json::json;
+use sha1::Sha1;
+use zip::ZipArchive;
 
-use crate::{DownloadItem, Hash, HashAlgorithm, AGENT};
+use crate::i18n::t;
+use crate::instances::Loader;
+use crate::{calc_hash, DownloadItem, Hash, HashAlgorithm, AGENT};
+
+// Base libraries most Fabric/Quilt mods depend on
+const ESSENTIAL_MODS: &[(Loader, &str, &str)] = &[
+    (Loader::Fabric, "P7dR8mSH", "Fabric API"),
+    (Loader::Quilt, "qvIfYCYJ", "QSL"),
+];
+
+// vetted to not change gameplay, quilt can load all of them
+const PERFORMANCE_MODS: &[(&str, &str, &str)] = &[
+    ("P7dR8mSH", "Fabric API", "Library needed by the other mods"),
+    ("AANobbMI", "Sodium", "Much faster rendering engine"),
+    (
+        "gvQqBUqZ",
+        "Lithium",
+        "Optimizes game logic, physics and mob AI",
+    ),
+    ("uXXizFIs", "FerriteCore", "Reduces memory usage"),
+    (
+        "NNAgCjsB",
+        "Entity Culling",
+        "Skips rendering entities that aren't visible",
+    ),
+    (
+        "nmDcB62a",
+        "ModernFix",
+        "Faster startup and lower memory usage",
+    ),
+];
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Project {
     pub project_id: String,
     pub title: String,
-    pub icon_url: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub featured_gallery: Option<String>,
     pub downloads: usize,
     pub display_categories: Vec<String>,
 }
@@ -20,34 +64,333 @@ pub struct Project {
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Projects {
     pub hits: Vec<Project>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub total_hits: usize,
 }
 
-pub async fn search_modpacks(query: &str) -> Result<Projects> {
-    let url = format!(
-        "https://api.modrinth.com/v2/search?query={query}&facets=[[\"categories:fabric\"],[\"project_type:modpack\"]]&limit=20",
-    );
+impl Projects {
+    // a first page replaces the list and the next one is appended; a page that
+    // doesn't follow the list, e.g. asked for twice, is dropped; answers to an older
+    // search are told apart by the caller, they can have the same offset
+    pub fn add_to(self, list: &mut Vec<Project>) -> bool {
+        if self.offset == 0 {
+            *list = self.hits;
+        } else if self.offset == list.len() {
+            list.extend(self.hits);
+        } else {
+            return false;
+        }
 
-    let resp = AGENT.get(&url).call()?.into_json()?;
+        true
+    }
+}
+
+pub const PAGE_SIZE: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortIndex {
+    #[default]
+    Relevance,
+    Downloads,
+    Updated,
+}
+
+impl SortIndex {
+    pub const ALL: [SortIndex; 3] = [
+        SortIndex::Relevance,
+        SortIndex::Downloads,
+        SortIndex::Updated,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SortIndex::Relevance => "relevance",
+            SortIndex::Downloads => "downloads",
+            SortIndex::Updated => "updated",
+        }
+    }
+}
+
+impl fmt::Display for SortIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            t(&format!("search-filters-sort-{}", self.as_str()))
+        )
+    }
+}
+
+pub const MOD_CATEGORIES: [&str; 12] = [
+    "adventure",
+    "decoration",
+    "equipment",
+    "food",
+    "library",
+    "magic",
+    "mobs",
+    "optimization",
+    "storage",
+    "technology",
+    "utility",
+    "worldgen",
+];
+
+pub const MODPACK_CATEGORIES: [&str; 10] = [
+    "adventure",
+    "challenging",
+    "combat",
+    "kitchen-sink",
+    "lightweight",
+    "magic",
+    "multiplayer",
+    "optimization",
+    "quests",
+    "technology",
+];
+
+// empty fields and no loader match everything
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchFilters {
+    pub minecraft_version: String,
+    pub loader: Option<Loader>,
+    pub category: String,
+    // an spdx id, like MIT or LGPL-3.0-only
+    pub license: String,
+    pub sort: SortIndex,
+}
+
+impl SearchFilters {
+    // facets in the same list are ORed, the lists are ANDed
+    fn to_facets(&self, project_type: &str) -> Result<Vec<Vec<String>>> {
+        let mut facets = vec![vec![format!("project_type:{project_type}")]];
+
+        if let Some(loader) = self.loader {
+            let loader = serde_json::to_value(loader)?;
+            let loader = loader.as_str().unwrap_or_default();
+            facets.push(vec![format!("categories:{loader}")]);
+        }
+
+        let fields = [
+            ("versions", &self.minecraft_version),
+            ("categories", &self.category),
+            ("license", &self.license),
+        ];
+        for (facet, value) in fields {
+            let value = value.trim();
+            if !value.is_empty() {
+                facets.push(vec![format!("{facet}:{value}")]);
+            }
+        }
+
+        Ok(facets)
+    }
+}
+
+// pages are requested by offset, the response says how many hits there are in total
+fn search(
+    project_type: &str,
+    query: &str,
+    filters: &SearchFilters,
+    offset: usize,
+) -> Result<Projects> {
+    let resp = AGENT
+        .get("https://api.modrinth.com/v2/search")
+        .query("query", query)
+        .query(
+            "facets",
+            &serde_json::to_string(&filters.to_facets(project_type)?)?,
+        )
+        .query("index", filters.sort.as_str())
+        .query("offset", &offset.to_string())
+        .query("limit", &PAGE_SIZE.to_string())
+        .call()?
+        .into_json()?;
 
     Ok(resp)
 }
 
+pub async fn search_modpacks(
+    query: String,
+    filters: SearchFilters,
+    offset: usize,
+) -> Result<Projects> {
+    search("modpack", &query, &filters, offset)
+}
+
+// Curated lists for the mod browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModList {
+    Popular,
+    RecentlyUpdated,
+    Performance,
+    QualityOfLife,
+    WorldGeneration,
+    Adventure,
+    Decoration,
+}
+
+impl ModList {
+    pub const ALL: [ModList; 7] = [
+        ModList::Popular,
+        ModList::RecentlyUpdated,
+        ModList::Performance,
+        ModList::QualityOfLife,
+        ModList::WorldGeneration,
+        ModList::Adventure,
+        ModList::Decoration,
+    ];
+
+    fn sort(&self) -> SortIndex {
+        match self {
+            ModList::RecentlyUpdated => SortIndex::Updated,
+            _ => SortIndex::Downloads,
+        }
+    }
+
+    fn category(&self) -> &'static str {
+        match self {
+            ModList::Popular | ModList::RecentlyUpdated => "",
+            ModList::Performance => "optimization",
+            ModList::QualityOfLife => "utility",
+            ModList::WorldGeneration => "worldgen",
+            ModList::Adventure => "adventure",
+            ModList::Decoration => "decoration",
+        }
+    }
+
+    // the lists are presets, the filters can still be changed afterwards
+    pub fn apply(&self, filters: &mut SearchFilters) {
+        filters.category = self.category().to_string();
+        filters.sort = self.sort();
+    }
+}
+
+impl fmt::Display for ModList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModList::Popular => write!(f, "Popular"),
+            ModList::RecentlyUpdated => write!(f, "Recently updated"),
+            ModList::Performance => write!(f, "Performance"),
+            ModList::QualityOfLife => write!(f, "Quality of life"),
+            ModList::WorldGeneration => write!(f, "World generation"),
+            ModList::Adventure => write!(f, "Adventure"),
+            ModList::Decoration => write!(f, "Decoration"),
+        }
+    }
+}
+
+// only mods that work with the instance are listed
+pub async fn browse_mods(
+    query: String,
+    minecraft_version: String,
+    loader: Loader,
+    mut filters: SearchFilters,
+    offset: usize,
+) -> Result<Projects> {
+    filters.minecraft_version = minecraft_version;
+    filters.loader = Some(loader);
+
+    search("mod", &query, &filters, offset)
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct License {
+    pub id: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GalleryImage {
+    pub url: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub ordering: i64,
+}
+
+#[derive(Deserialize)]
+struct User {
+    username: String,
+}
+
 #[derive(Deserialize)]
+struct Member {
+    user: User,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDetails {
+    pub title: String,
+    // markdown
+    pub body: String,
+    pub downloads: usize,
+    pub game_versions: Vec<String>,
+    pub loaders: Vec<String>,
+    #[serde(default)]
+    pub license: Option<License>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryImage>,
+    #[serde(skip)]
+    pub authors: Vec<String>,
+}
+
+pub async fn get_project(project_id: String) -> Result<ProjectDetails> {
+    let url = format!("https://api.modrinth.com/v2/project/{project_id}");
+    let mut details = AGENT.get(&url).call()?.into_json::<ProjectDetails>()?;
+    details.gallery.sort_by_key(|image| image.ordering);
+
+    let members = AGENT
+        .get(&format!("{url}/members"))
+        .call()?
+        .into_json::<Vec<Member>>()?;
+    details.authors = members
+        .into_iter()
+        .map(|member| member.user.username)
+        .collect();
+
+    Ok(details)
+}
+
+pub async fn get_image(url: String) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    AGENT
+        .get(&url)
+        .call()?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+pub async fn get_mod_version(
+    project_id: String,
+    minecraft_version: String,
+    loader: Loader,
+) -> Result<Option<Version>> {
+    get_compatible_version(&project_id, &minecraft_version, loader)
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Hashes {
     pub sha512: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct File {
     pub hashes: Hashes,
     pub url: String,
     pub filename: String,
+    pub size: u64,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Version {
     pub name: String,
     pub files: Vec<File>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
 }
 
 pub async fn get_versions(modpack_id: &str) -> Result<Vec<Version>> {
@@ -58,7 +401,262 @@ pub async fn get_versions(modpack_id: &str) -> Result<Vec<Version>> {
     Ok(resp)
 }
 
-pub fn install_version(version: &Version, dest_dir: &Path) -> Result<Vec<DownloadItem>> {
+// modrinth lists the newest first; packs are imported as fabric or vanilla instances
+fn pick_modpack_file(versions: &[Version]) -> Option<&File> {
+    versions
+        .iter()
+        .filter(|version| {
+            version
+                .loaders
+                .iter()
+                .all(|loader| loader == "fabric" || loader == "minecraft")
+        })
+        .find_map(|version| version.files.first())
+}
+
+// the newest .mrpack goes to the temp dir, from there it's imported like a dropped
+// file; the caller deletes it once it's imported
+pub async fn download_modpack(modpack_id: String) -> Result<PathBuf> {
+    let versions = get_versions(&modpack_id).await?;
+    let Some(file) = pick_modpack_file(&versions) else {
+        bail!("This modpack has no Fabric or vanilla version to download");
+    };
+
+    let item = DownloadItem {
+        url: file.url.to_owned(),
+        path: std::env::temp_dir().join(&file.filename),
+        hash: Some(Hash {
+            function: HashAlgorithm::Sha512,
+            hash: file.hashes.sha512.to_owned(),
+        }),
+        size: Some(file.size),
+        extract: false,
+    };
+    item.download_file()?;
+
+    Ok(item.path)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub title: String,
+    pub description: String,
+    pub version: Version,
+}
+
+pub(crate) fn get_compatible_version(
+    project_id: &str,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Option<Version>> {
+    let url = format!("https://api.modrinth.com/v2/project/{project_id}/version");
+    let loader = serde_json::to_string(&[loader])?;
+    let game_versions = serde_json::to_string(&[minecraft_version])?;
+
+    let versions = AGENT
+        .get(&url)
+        .query("loaders", &loader)
+        .query("game_versions", &game_versions)
+        .call()?
+        .into_json::<Vec<Version>>()?;
+
+    Ok(versions.into_iter().next())
+}
+
+pub async fn get_suggestions(minecraft_version: String, loader: Loader) -> Result<Vec<Suggestion>> {
+    let mut suggestions = Vec::new();
+
+    for (_, project_id, title) in ESSENTIAL_MODS.iter().filter(|(l, _, _)| *l == loader) {
+        if let Some(version) = get_compatible_version(project_id, &minecraft_version, loader)? {
+            suggestions.push(Suggestion {
+                title: title.to_string(),
+                description: "Library needed by most mods".to_string(),
+                version,
+            });
+        }
+    }
+
+    Ok(suggestions)
+}
+
+pub async fn get_performance_mods(
+    minecraft_version: String,
+    loader: Loader,
+) -> Result<Vec<Suggestion>> {
+    if loader == Loader::Vanilla {
+        bail!("Performance mods need a mod loader");
+    }
+
+    let mut suggestions = Vec::new();
+
+    for (project_id, title, description) in PERFORMANCE_MODS {
+        // not every mod is available for every version
+        if let Some(version) =
+            get_compatible_version(project_id, &minecraft_version, Loader::Fabric)?
+        {
+            suggestions.push(Suggestion {
+                title: title.to_string(),
+                description: description.to_string(),
+                version,
+            });
+        }
+    }
+
+    Ok(suggestions)
+}
+
+pub fn install_mod(version: &Version, instance_dir: &Path) -> Result<DownloadItem> {
+    let file = version
+        .files
+        .first()
+        .ok_or_else(|| anyhow!("{} has no files to download", version.name))?;
+
+    Ok(DownloadItem {
+        url: file.url.to_owned(),
+        path: instance_dir.join("mods").join(&file.filename),
+        hash: Some(Hash {
+            function: HashAlgorithm::Sha512,
+            hash: file.hashes.sha512.to_owned(),
+        }),
+        size: Some(file.size),
+        extract: false,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credit {
+    pub filename: String,
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub license: Option<String>,
+    pub url: Option<String>,
+}
+
+pub async fn get_credits(mods_dir: PathBuf) -> Result<Vec<Credit>> {
+    #[derive(Deserialize)]
+    struct FileVersion {
+        project_id: String,
+    }
+
+    #[derive(Deserialize)]
+    struct License {
+        id: String,
+    }
+
+    #[derive(Deserialize)]
+    struct ProjectInfo {
+        id: String,
+        slug: String,
+        title: String,
+        team: String,
+        license: License,
+        project_type: String,
+    }
+
+    #[derive(Deserialize)]
+    struct User {
+        username: String,
+    }
+
+    #[derive(Deserialize)]
+    struct TeamMember {
+        team_id: String,
+        user: User,
+    }
+
+    let mut files = Vec::new();
+    if mods_dir.exists() {
+        for entry in fs::read_dir(&mods_dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == "jar") {
+                let hash = calc_hash::<Sha1>(BufReader::new(fs::File::open(&path)?))?;
+                let filename = path.file_name().unwrap().to_string_lossy().to_string();
+                files.push((filename, hash));
+            }
+        }
+    }
+    files.sort();
+
+    let hashes = files.iter().map(|(_, hash)| hash).collect::<Vec<_>>();
+    let versions = AGENT
+        .post("https://api.modrinth.com/v2/version_files")
+        .send_json(json!({ "hashes": hashes, "algorithm": "sha1" }))?
+        .into_json::<HashMap<String, FileVersion>>()?;
+
+    let ids = versions.values().map(|v| &v.project_id).collect::<Vec<_>>();
+    let projects = AGENT
+        .get("https://api.modrinth.com/v2/projects")
+        .query("ids", &serde_json::to_string(&ids)?)
+        .call()?
+        .into_json::<Vec<ProjectInfo>>()?;
+
+    let teams = projects.iter().map(|p| &p.team).collect::<Vec<_>>();
+    let members = AGENT
+        .get("https://api.modrinth.com/v2/teams")
+        .query("ids", &serde_json::to_string(&teams)?)
+        .call()?
+        .into_json::<Vec<Vec<TeamMember>>>()?;
+
+    let credits = files
+        .into_iter()
+        .map(|(filename, hash)| {
+            let project = versions
+                .get(&hash)
+                .and_then(|v| projects.iter().find(|p| p.id == v.project_id));
+
+            match project {
+                Some(project) => Credit {
+                    filename,
+                    title: Some(project.title.clone()),
+                    authors: members
+                        .iter()
+                        .flatten()
+                        .filter(|m| m.team_id == project.team)
+                        .map(|m| m.user.username.clone())
+                        .collect(),
+                    license: Some(project.license.id.clone()),
+                    url: Some(format!(
+                        "https://modrinth.com/{}/{}",
+                        project.project_type, project.slug
+                    )),
+                },
+                None => Credit {
+                    filename,
+                    title: None,
+                    authors: Vec::new(),
+                    license: None,
+                    url: None,
+                },
+            }
+        })
+        .collect();
+
+    Ok(credits)
+}
+
+pub fn credits_to_markdown(credits: &[Credit]) -> String {
+    let mut markdown =
+        String::from("# Credits\n\n| Mod | Authors | License | Source |\n|---|---|---|---|\n");
+
+    for credit in credits {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            credit.title.as_deref().unwrap_or(&credit.filename),
+            credit.authors.join(", "),
+            credit.license.as_deref().unwrap_or("Unknown"),
+            credit.url.as_deref().unwrap_or("Unknown"),
+        ));
+    }
+
+    markdown
+}
+
+pub fn install_version(
+    version: &Version,
+    dest_dir: &Path,
+    threads: usize,
+) -> Result<Vec<DownloadItem>> {
     let tmp_dir = tempfile::tempdir()?;
 
     let file = &version.files[0];
@@ -68,63 +666,242 @@ pub fn install_version(version: &Version, dest_dir: &Path) -> Result<Vec<Downloa
         hash: file.hashes.sha512.to_owned(),
     };
 
+    let mrpack_path = tmp_dir.path().join(&file.filename);
+
     DownloadItem {
         url: file.url.to_owned(),
-        path: tmp_dir.path().to_path_buf(),
+        path: mrpack_path.clone(),
         hash: Some(hash),
-        extract: true,
+        size: Some(file.size),
+        extract: false,
     }
     .download_file()?;
 
+    install_mrpack(&mrpack_path, dest_dir, threads)
+}
+
+#[derive(Deserialize)]
+struct MrpackFile {
+    path: String,
+    hashes: Hashes,
+    downloads: Vec<String>,
+    #[serde(rename = "fileSize")]
+    file_size: u64,
+}
+
+#[derive(Deserialize)]
+pub struct MrpackDependencies {
+    pub minecraft: String,
+    #[serde(rename = "fabric-loader")]
+    pub fabric_loader: Option<String>,
+    // only read to refuse these packs
+    #[serde(rename = "quilt-loader")]
+    pub quilt_loader: Option<String>,
+    pub forge: Option<String>,
+}
+
+// modrinth.index.json
+#[derive(Deserialize)]
+pub struct MrpackIndex {
+    pub name: String,
+    files: Vec<MrpackFile>,
+    pub dependencies: MrpackDependencies,
+}
+
+pub fn read_mrpack(mrpack_path: &Path) -> Result<MrpackIndex> {
+    let mut archive = ZipArchive::new(BufReader::new(fs::File::open(mrpack_path)?))?;
+    let index = archive.by_name("modrinth.index.json")?;
+    let index = serde_json::from_reader::<_, MrpackIndex>(index)?;
+
+    Ok(index)
+}
+
+// extracts the overrides, the mods are returned to be downloaded
+pub fn install_mrpack(
+    mrpack_path: &Path,
+    dest_dir: &Path,
+    threads: usize,
+) -> Result<Vec<DownloadItem>> {
+    let index = read_mrpack(mrpack_path)?;
     let mut items = Vec::new();
 
-    // parse modrinth.index.json
-    {
-        #[derive(Deserialize)]
-        struct File {
-            path: String,
-            hashes: Hashes,
-            downloads: Vec<String>,
+    for file in index.files {
+        // the index is untrusted, its files can't end up outside of the instance
+        if !Path::new(&file.path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!("Invalid file path in the modpack: {}", file.path);
         }
 
-        #[derive(Deserialize)]
-        struct Dependencies {
-            minecraft: String,
-            #[serde(rename = "fabric-loader")]
-            fabric_loader: Option<String>,
-        }
+        let url = file
+            .downloads
+            .first()
+            .ok_or_else(|| anyhow!("No download for {} in the modpack", file.path))?;
 
-        #[derive(Deserialize)]
-        struct Index {
-            files: Vec<File>,
-            dependencies: Dependencies,
-        }
+        let hash = Hash {
+            function: HashAlgorithm::Sha512,
+            hash: file.hashes.sha512.to_owned(),
+        };
 
-        let index = tmp_dir.path().join("modrinth.index.json");
-        let index = BufReader::new(fs::File::open(index)?);
-        let index = serde_json::from_reader::<_, Vec<File>>(index)?;
-
-        for file in index {
-            let hash = Hash {
-                function: HashAlgorithm::Sha512,
-                hash: file.hashes.sha512.to_owned(),
-            };
-
-            items.push(DownloadItem {
-                url: file.downloads[0].to_owned(),
-                path: dest_dir.join(file.path),
-                hash: Some(hash),
-                extract: false,
-            });
+        items.push(DownloadItem {
+            url: url.to_owned(),
+            path: dest_dir.join(file.path),
+            hash: Some(hash),
+            size: Some(file.file_size),
+            extract: false,
+        });
+    }
+
+    extract_overrides(mrpack_path, Path::new("overrides"), dest_dir, threads)?;
+
+    Ok(items)
+}
+
+// Big packs ship thousands of small overrides, so every thread gets its own
+// handle to the archive and a share of the entries
+pub(crate) fn extract_overrides(
+    mrpack_path: &Path,
+    prefix: &Path,
+    dest_dir: &Path,
+    threads: usize,
+) -> Result<()> {
+    let threads = threads.max(1);
+
+    let extracted = thread::scope(|scope| {
+        let handles = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || -> Result<Vec<(PathBuf, u64)>> {
+                    let mut archive =
+                        ZipArchive::new(BufReader::new(fs::File::open(mrpack_path)?))?;
+                    let mut extracted = Vec::new();
+
+                    for i in (worker..archive.len()).step_by(threads) {
+                        let mut entry = archive.by_index(i)?;
+
+                        let Some(name) = entry.enclosed_name().map(Path::to_path_buf) else {
+                            continue;
+                        };
+                        let Ok(name) = name.strip_prefix(prefix) else {
+                            continue;
+                        };
+
+                        let dest = dest_dir.join(name);
+
+                        if entry.is_dir() {
+                            fs::create_dir_all(&dest)?;
+                            continue;
+                        }
+
+                        if let Some(parent) = dest.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+
+                        let mut writer = BufWriter::new(fs::File::create(&dest)?);
+                        io::copy(&mut entry, &mut writer)?;
+                        writer.flush()?;
+
+                        extracted.push((dest, entry.size()));
+                    }
+
+                    Ok(extracted)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    // make sure nothing was left behind before the pack is deleted
+    for (path, size) in extracted.into_iter().flatten() {
+        let len = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        if len != size {
+            bail!("failed to extract override: {}", path.display());
         }
     }
 
-    // copy overrides to dest_dir
-    for r#override in tmp_dir.path().join("overrides").read_dir()? {
-        let r#override = r#override?;
-        let dest = dest_dir.join(r#override.file_name());
-        fs::copy(r#override.path(), dest)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_filters_to_facets() {
+        let filters = SearchFilters {
+            minecraft_version: "1.20.1".to_string(),
+            loader: Some(Loader::Quilt),
+            license: " MIT ".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            filters.to_facets("mod").unwrap(),
+            vec![
+                vec!["project_type:mod".to_string()],
+                vec!["categories:quilt".to_string()],
+                vec!["versions:1.20.1".to_string()],
+                vec!["license:MIT".to_string()],
+            ]
+        );
+
+        assert_eq!(
+            SearchFilters::default().to_facets("modpack").unwrap(),
+            vec![vec!["project_type:modpack".to_string()]]
+        );
+    }
+
+    #[test]
+    fn appends_the_next_page() {
+        let project = |id: &str| Project {
+            project_id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            icon_url: None,
+            featured_gallery: None,
+            downloads: 0,
+            display_categories: Vec::new(),
+        };
+        let page = |offset, ids: &[&str]| Projects {
+            hits: ids.iter().map(|id| project(id)).collect(),
+            offset,
+            total_hits: 3,
+        };
+
+        let mut list = vec![project("old")];
+        assert!(page(0, &["a", "b"]).add_to(&mut list));
+        assert!(page(2, &["c"]).add_to(&mut list));
+        assert!(!page(2, &["c"]).add_to(&mut list));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[2].project_id, "c");
     }
 
-    Ok(items)
+    #[test]
+    fn picks_the_newest_supported_modpack() {
+        let version = |name: &str, loader: &str| Version {
+            name: name.to_string(),
+            files: vec![File {
+                hashes: Hashes {
+                    sha512: String::new(),
+                },
+                url: String::new(),
+                filename: format!("{name}.mrpack"),
+                size: 0,
+            }],
+            loaders: vec![loader.to_string()],
+        };
+
+        let versions = [
+            version("2.0", "quilt"),
+            version("1.1", "fabric"),
+            version("1.0", "fabric"),
+        ];
+        assert_eq!(pick_modpack_file(&versions).unwrap().filename, "1.1.mrpack");
+        assert!(pick_modpack_file(&versions[..1]).is_none());
+    }
 }
@@ -0,0 +1,342 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+// the uncompressed, big endian format of level.dat and friends
+
+use anyhow::{bail, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    // the element type is kept, empty lists still need it
+    List(u8, Vec<Tag>),
+    // in file order, so an untouched file is written back the same
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+const END: u8 = 0;
+const COMPOUND: u8 = 10;
+
+impl Tag {
+    fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => 1,
+            Tag::Short(_) => 2,
+            Tag::Int(_) => 3,
+            Tag::Long(_) => 4,
+            Tag::Float(_) => 5,
+            Tag::Double(_) => 6,
+            Tag::ByteArray(_) => 7,
+            Tag::String(_) => 8,
+            Tag::List(..) => 9,
+            Tag::Compound(_) => COMPOUND,
+            Tag::IntArray(_) => 11,
+            Tag::LongArray(_) => 12,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Tag> {
+        match self {
+            Tag::Compound(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Tag> {
+        match self {
+            Tag::Compound(entries) => entries
+                .iter_mut()
+                .find(|(name, _)| name == key)
+                .map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    // replaces the value in place, or appends it when it's missing
+    pub fn insert(&mut self, key: &str, value: Tag) {
+        if let Some(tag) = self.get_mut(key) {
+            *tag = value;
+        } else if let Tag::Compound(entries) = self {
+            entries.push((key.to_string(), value));
+        }
+    }
+}
+
+// java's modified utf-8: nulls take two bytes and characters outside the basic
+// plane are written as two three byte surrogates
+fn encode_modified_utf8(string: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(string.len());
+
+    for unit in string.encode_utf16() {
+        match unit {
+            0x01..=0x7f => bytes.push(unit as u8),
+            0x00..=0x7ff => {
+                bytes.push(0xc0 | (unit >> 6) as u8);
+                bytes.push(0x80 | (unit & 0x3f) as u8);
+            }
+            _ => {
+                bytes.push(0xe0 | (unit >> 12) as u8);
+                bytes.push(0x80 | ((unit >> 6) & 0x3f) as u8);
+                bytes.push(0x80 | (unit & 0x3f) as u8);
+            }
+        }
+    }
+
+    bytes
+}
+
+fn decode_modified_utf8(bytes: &[u8]) -> Result<String> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    let continuation = |i: usize| match bytes.get(i) {
+        Some(byte) if byte & 0xc0 == 0x80 => Ok((byte & 0x3f) as u16),
+        _ => bail!("Invalid NBT string"),
+    };
+
+    while i < bytes.len() {
+        let byte = bytes[i] as u16;
+
+        if byte < 0x80 {
+            units.push(byte);
+            i += 1;
+        } else if byte & 0xe0 == 0xc0 {
+            units.push((byte & 0x1f) << 6 | continuation(i + 1)?);
+            i += 2;
+        } else if byte & 0xf0 == 0xe0 {
+            units.push((byte & 0x0f) << 12 | continuation(i + 1)? << 6 | continuation(i + 2)?);
+            i += 3;
+        } else {
+            bail!("Invalid NBT string");
+        }
+    }
+
+    Ok(String::from_utf16(&units)?)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            bail!("Unexpected end of NBT data");
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        let len = i32::from_be_bytes(self.array()?);
+        if len < 0 {
+            bail!("Negative NBT length");
+        }
+
+        Ok(len as usize)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+
+        decode_modified_utf8(self.take(len)?)
+    }
+
+    fn payload(&mut self, id: u8) -> Result<Tag> {
+        let tag = match id {
+            1 => Tag::Byte(i8::from_be_bytes(self.array()?)),
+            2 => Tag::Short(i16::from_be_bytes(self.array()?)),
+            3 => Tag::Int(i32::from_be_bytes(self.array()?)),
+            4 => Tag::Long(i64::from_be_bytes(self.array()?)),
+            5 => Tag::Float(f32::from_be_bytes(self.array()?)),
+            6 => Tag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.len()?;
+                Tag::ByteArray(self.take(len)?.iter().map(|b| *b as i8).collect())
+            }
+            8 => Tag::String(self.string()?),
+            9 => {
+                let element = self.u8()?;
+                let len = self.len()?;
+                let list = (0..len)
+                    .map(|_| self.payload(element))
+                    .collect::<Result<_>>()?;
+                Tag::List(element, list)
+            }
+            COMPOUND => {
+                let mut entries = Vec::new();
+                loop {
+                    let id = self.u8()?;
+                    if id == END {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(id)?));
+                }
+                Tag::Compound(entries)
+            }
+            11 => {
+                let len = self.len()?;
+                Tag::IntArray(
+                    (0..len)
+                        .map(|_| Ok(i32::from_be_bytes(self.array()?)))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            12 => {
+                let len = self.len()?;
+                Tag::LongArray(
+                    (0..len)
+                        .map(|_| Ok(i64::from_be_bytes(self.array()?)))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            id => bail!("Unknown NBT tag {id}"),
+        };
+
+        Ok(tag)
+    }
+}
+
+// the root is a named compound
+pub fn read(bytes: &[u8]) -> Result<(String, Tag)> {
+    let mut reader = Reader { bytes };
+
+    if reader.u8()? != COMPOUND {
+        bail!("The NBT root isn't a compound");
+    }
+    let name = reader.string()?;
+    let root = reader.payload(COMPOUND)?;
+
+    Ok((name, root))
+}
+
+fn write_string(out: &mut Vec<u8>, string: &str) -> Result<()> {
+    let bytes = encode_modified_utf8(string);
+    let len = u16::try_from(bytes.len())?;
+    out.extend(len.to_be_bytes());
+    out.extend(bytes);
+
+    Ok(())
+}
+
+fn write_len(out: &mut Vec<u8>, len: usize) -> Result<()> {
+    out.extend(i32::try_from(len)?.to_be_bytes());
+
+    Ok(())
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) -> Result<()> {
+    match tag {
+        Tag::Byte(value) => out.extend(value.to_be_bytes()),
+        Tag::Short(value) => out.extend(value.to_be_bytes()),
+        Tag::Int(value) => out.extend(value.to_be_bytes()),
+        Tag::Long(value) => out.extend(value.to_be_bytes()),
+        Tag::Float(value) => out.extend(value.to_be_bytes()),
+        Tag::Double(value) => out.extend(value.to_be_bytes()),
+        Tag::ByteArray(values) => {
+            write_len(out, values.len())?;
+            out.extend(values.iter().map(|b| *b as u8));
+        }
+        Tag::String(value) => write_string(out, value)?,
+        Tag::List(element, list) => {
+            out.push(*element);
+            write_len(out, list.len())?;
+            for tag in list {
+                write_payload(out, tag)?;
+            }
+        }
+        Tag::Compound(entries) => {
+            for (name, tag) in entries {
+                out.push(tag.id());
+                write_string(out, name)?;
+                write_payload(out, tag)?;
+            }
+            out.push(END);
+        }
+        Tag::IntArray(values) => {
+            write_len(out, values.len())?;
+            values.iter().for_each(|v| out.extend(v.to_be_bytes()));
+        }
+        Tag::LongArray(values) => {
+            write_len(out, values.len())?;
+            values.iter().for_each(|v| out.extend(v.to_be_bytes()));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn write(name: &str, root: &Tag) -> Result<Vec<u8>> {
+    if !matches!(root, Tag::Compound(_)) {
+        bail!("The NBT root has to be a compound");
+    }
+
+    let mut out = vec![COMPOUND];
+    write_string(&mut out, name)?;
+    write_payload(&mut out, root)?;
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_back_what_it_reads() {
+        let root = Tag::Compound(vec![
+            ("name".to_string(), Tag::String("World".to_string())),
+            ("seed".to_string(), Tag::Long(-42)),
+            ("empty".to_string(), Tag::List(END, Vec::new())),
+            (
+                "nested".to_string(),
+                Tag::Compound(vec![
+                    ("ints".to_string(), Tag::IntArray(vec![1, -2])),
+                    ("pi".to_string(), Tag::Double(0.5)),
+                ]),
+            ),
+        ]);
+
+        let bytes = write("", &root).unwrap();
+        assert_eq!(read(&bytes).unwrap(), (String::new(), root));
+
+        assert!(read(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn uses_java_modified_utf8() {
+        let name = "World \u{1f30d}\0";
+        let root = Tag::Compound(vec![("name".to_string(), Tag::String(name.to_string()))]);
+
+        let bytes = write("", &root).unwrap();
+        // the emoji is a surrogate pair and the null takes two bytes
+        assert!(bytes
+            .windows(8)
+            .any(|w| w == [0xed, 0xa0, 0xbc, 0xed, 0xbc, 0x8d, 0xc0, 0x80]));
+        assert_eq!(read(&bytes).unwrap(), (String::new(), root));
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+// every online session talks to it anyway
+const PROBE_HOST: &str = "launchermeta.mojang.com:443";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// only opens a connection, nothing is sent
+pub fn is_online() -> bool {
+    PROBE_HOST
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .is_some_and(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok())
+}
+
+pub async fn check_connection() -> bool {
+    is_online()
+}
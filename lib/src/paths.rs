@@ -45,6 +45,42 @@ pub static RUNTIMES_DIR: Lazy<PathBuf> = Lazy::new(|| {
 
     dir
 });
+
+pub static UPDATES_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let dir = BASE_DIR.join("updates");
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+});
+
+pub static LOGS_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let dir = BASE_DIR.join("logs");
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+});
+
+pub static CRASHES_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let dir = BASE_DIR.join("crashes");
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+});
+
+pub static BACKUPS_DIR: Lazy<PathBuf> = Lazy::new(|| {
+    let dir = BASE_DIR.join("backups");
+    fs::create_dir_all(&dir).unwrap();
+
+    dir
+});
+
 pub static SETTINGS_PATH: Lazy<PathBuf> = Lazy::new(|| BASE_DIR.join("settings.toml"));
 
 pub static ACCOUNTS_PATH: Lazy<PathBuf> = Lazy::new(|| BASE_DIR.join("accounts.toml"));
+
+// see sealed.rs
+pub static SEALING_KEY_PATH: Lazy<PathBuf> = Lazy::new(|| BASE_DIR.join("sealing.key"));
+
+pub static RUNNING_PATH: Lazy<PathBuf> = Lazy::new(|| BASE_DIR.join("running.toml"));
+
+pub static IPC_PORT_PATH: Lazy<PathBuf> = Lazy::new(|| BASE_DIR.join("ipc-port"));
@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, process};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::paths::RUNNING_PATH;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunningGame {
+    pub instance: String,
+    pub pid: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started: OffsetDateTime,
+}
+
+// Persisted so games outliving the launcher can be found again
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RunningGames {
+    #[serde(default)]
+    pub list: Vec<RunningGame>,
+}
+
+impl RunningGames {
+    // games that exited in the meantime are dropped
+    pub fn load() -> Result<Self> {
+        if !RUNNING_PATH.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&*RUNNING_PATH)?;
+        let mut running = toml::from_str::<Self>(&content)?;
+
+        let count = running.list.len();
+        running.list.retain(|game| is_alive(game.pid));
+        if running.list.len() != count {
+            running.save()?;
+        }
+
+        Ok(running)
+    }
+
+    fn save(&self) -> Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        fs::write(&*RUNNING_PATH, content)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, instance: &str) -> Option<&RunningGame> {
+        self.list
+            .iter()
+            .find(|game| game.instance == instance && is_alive(game.pid))
+    }
+
+    pub fn add(&mut self, instance: &str, pid: u32) -> Result<()> {
+        self.list.push(RunningGame {
+            instance: instance.to_string(),
+            pid,
+            started: OffsetDateTime::now_utc(),
+        });
+
+        self.save()
+    }
+
+    pub fn remove(&mut self, pid: u32) -> Result<()> {
+        self.list.retain(|game| game.pid != pid);
+
+        self.save()
+    }
+
+    pub fn terminate(&mut self, pid: u32) -> Result<()> {
+        if is_alive(pid) {
+            kill(pid)?;
+        }
+
+        self.remove(pid)
+    }
+}
+
+// pids get reused, so the process has to be a java one too
+#[cfg(not(target_os = "windows"))]
+pub fn is_alive(pid: u32) -> bool {
+    process::Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("java"))
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_alive(pid: u32) -> bool {
+    process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("java"))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn kill(pid: u32) -> Result<()> {
+    let status = process::Command::new("kill")
+        .arg(pid.to_string())
+        .status()?;
+
+    if !status.success() {
+        bail!("Failed to terminate process {}", pid);
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn kill(pid: u32) -> Result<()> {
+    let status = process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status()?;
+
+    if !status.success() {
+        bail!("Failed to terminate process {}", pid);
+    }
+
+    Ok(())
+}
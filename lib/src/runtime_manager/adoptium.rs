@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use super::{Arch, Release, Vendor};
+use crate::{Hash, HashAlgorithm, AGENT};
+
+#[cfg(target_os = "windows")]
+const OS: &str = "windows";
+
+#[cfg(target_os = "linux")]
+const OS: &str = "linux";
+
+#[cfg(target_os = "macos")]
+const OS: &str = "mac";
+
+#[derive(Deserialize)]
+struct Package {
+    checksum: String,
+    link: String,
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct Binary {
+    package: Package,
+}
+
+#[derive(Deserialize)]
+struct Assets {
+    binary: Binary,
+    release_name: String,
+}
+
+pub struct Adoptium;
+
+impl Vendor for Adoptium {
+    fn get_latest(&self, java_version: &str, arch: Arch) -> Result<Release> {
+        let url = format!(
+            "https://api.adoptium.net/v3/assets/latest/{}/hotspot?architecture={}&image_type=jre&os={}&vendor=eclipse",
+            java_version,
+            arch.id(),
+            OS
+        );
+
+        let assets = AGENT
+            .get(&url)
+            .call()?
+            .into_json::<Vec<Assets>>()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No Adoptium runtime found for version {}", java_version))?;
+
+        Ok(Release {
+            id: assets.release_name,
+            url: assets.binary.package.link,
+            hash: Some(Hash {
+                hash: assets.binary.package.checksum,
+                function: HashAlgorithm::Sha256,
+            }),
+            size: Some(assets.binary.package.size),
+        })
+    }
+}
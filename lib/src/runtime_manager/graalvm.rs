@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anyhow::{bail, Result};
+
+use super::{Arch, Release, Vendor};
+use crate::{Hash, HashAlgorithm, AGENT};
+
+#[cfg(target_os = "windows")]
+const PLATFORM: &str = "windows";
+
+#[cfg(target_os = "linux")]
+const PLATFORM: &str = "linux";
+
+#[cfg(target_os = "macos")]
+const PLATFORM: &str = "macos";
+
+#[cfg(target_os = "windows")]
+const ARCHIVE_TYPE: &str = "zip";
+
+#[cfg(not(target_os = "windows"))]
+const ARCHIVE_TYPE: &str = "tar.gz";
+
+pub struct GraalVm;
+
+impl Vendor for GraalVm {
+    fn get_latest(&self, java_version: &str, arch: Arch) -> Result<Release> {
+        // only these get builds, and only as full JDKs
+        if !matches!(java_version, "17" | "21") {
+            bail!("GraalVM is not available for Java {}", java_version);
+        }
+
+        if !matches!(arch, Arch::X64 | Arch::Aarch64) {
+            bail!("GraalVM is not available for {}", arch);
+        }
+
+        let url = format!(
+            "https://download.oracle.com/graalvm/{java_version}/latest/graalvm-jdk-{java_version}_{PLATFORM}-{}_bin.{ARCHIVE_TYPE}",
+            arch.id()
+        );
+
+        // the "latest" url never changes, the checksum does
+        let checksum = AGENT
+            .get(&format!("{url}.sha256"))
+            .call()?
+            .into_string()?
+            .trim()
+            .to_string();
+
+        Ok(Release {
+            id: checksum.chars().take(12).collect(),
+            url,
+            hash: Some(Hash {
+                hash: checksum,
+                function: HashAlgorithm::Sha256,
+            }),
+            size: None,
+        })
+    }
+}
@@ -0,0 +1,236 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+mod adoptium;
+mod graalvm;
+pub mod system;
+mod zulu;
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::paths::RUNTIMES_DIR;
+use crate::{DownloadItem, Hash};
+
+// The latest build of a java version, as published by a vendor
+pub struct Release {
+    // changes whenever a new build is published
+    pub id: String,
+    pub url: String,
+    pub hash: Option<Hash>,
+    pub size: Option<u64>,
+}
+
+pub trait Vendor {
+    fn get_latest(&self, java_version: &str, arch: Arch) -> Result<Release>;
+}
+
+// what a runtime is built for, not always the same as the launcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    X64,
+    Aarch64,
+    // 32-bit, e.g. old pcs and arm sbcs
+    X86,
+    Arm,
+}
+
+impl Arch {
+    #[cfg(target_arch = "aarch64")]
+    pub const HOST: Arch = Arch::Aarch64;
+
+    #[cfg(target_arch = "x86")]
+    pub const HOST: Arch = Arch::X86;
+
+    #[cfg(target_arch = "arm")]
+    pub const HOST: Arch = Arch::Arm;
+
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "arm")))]
+    pub const HOST: Arch = Arch::X64;
+
+    // as adoptium calls it, also used for the runtime folders
+    fn id(&self) -> &'static str {
+        match self {
+            Arch::X64 => "x64",
+            Arch::Aarch64 => "aarch64",
+            Arch::X86 => "x32",
+            Arch::Arm => "arm",
+        }
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arch::X64 => write!(f, "x86_64"),
+            Arch::Aarch64 => write!(f, "aarch64"),
+            Arch::X86 => write!(f, "x86"),
+            Arch::Arm => write!(f, "arm"),
+        }
+    }
+}
+
+// runtimes for another architecture are kept next to the native ones
+pub fn get_runtime_id(java_version: &str, arch: Arch) -> String {
+    if arch == Arch::HOST {
+        java_version.to_string()
+    } else {
+        format!("{}-{}", java_version, arch.id())
+    }
+}
+
+// installed on demand, x86_64 runtimes don't start on apple silicon without it
+const ROSETTA_RUNTIME: &str = "/Library/Apple/usr/libexec/oah/libRosettaRuntime";
+
+pub fn check_rosetta() -> Result<()> {
+    if !Path::new(ROSETTA_RUNTIME).exists() {
+        bail!("This version needs Rosetta 2, install it with: softwareupdate --install-rosetta");
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JavaVendor {
+    #[default]
+    Adoptium,
+    Zulu,
+    GraalVm,
+}
+
+impl JavaVendor {
+    pub const ALL: [JavaVendor; 3] = [JavaVendor::Adoptium, JavaVendor::Zulu, JavaVendor::GraalVm];
+
+    fn get(&self) -> &'static dyn Vendor {
+        match self {
+            JavaVendor::Adoptium => &adoptium::Adoptium,
+            JavaVendor::Zulu => &zulu::Zulu,
+            JavaVendor::GraalVm => &graalvm::GraalVm,
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            JavaVendor::Adoptium => "adoptium",
+            JavaVendor::Zulu => "zulu",
+            JavaVendor::GraalVm => "graalvm",
+        }
+    }
+}
+
+impl fmt::Display for JavaVendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JavaVendor::Adoptium => write!(f, "Eclipse Adoptium"),
+            JavaVendor::Zulu => write!(f, "Azul Zulu"),
+            JavaVendor::GraalVm => write!(f, "Oracle GraalVM"),
+        }
+    }
+}
+
+pub fn install(java_version: &str, vendor: JavaVendor, arch: Arch) -> Result<Vec<DownloadItem>> {
+    let runtime_id = get_runtime_id(java_version, arch);
+
+    let release = match vendor.get().get_latest(java_version, arch) {
+        Ok(release) => release,
+        // a pre-installed runtime is good enough when offline
+        Err(error) => {
+            if get_path(&runtime_id).is_ok() {
+                tracing::warn!("Using installed runtime: {}", error);
+                return Ok(Vec::new());
+            }
+
+            return Err(error);
+        }
+    };
+
+    let dir = RUNTIMES_DIR
+        .join(&runtime_id)
+        .join(format!("{}-{}", vendor.id(), release.id));
+
+    // the folder alone could just hold an interrupted download
+    if find_java(&dir).is_some() {
+        tracing::debug!("Runtime already up to date");
+
+        // only one runtime per java version is kept, the old ones go once the new one is in place
+        for old_dir in get_subdirs(&RUNTIMES_DIR.join(&runtime_id)) {
+            if old_dir != dir {
+                let _ = fs::remove_dir_all(old_dir);
+            }
+        }
+
+        return Ok(Vec::new());
+    }
+
+    // the archive is extracted next to itself
+    let file_name = release
+        .url
+        .rsplit('/')
+        .next()
+        .ok_or_else(|| anyhow!("Invalid runtime url: {}", release.url))?;
+
+    Ok(vec![DownloadItem {
+        url: release.url.clone(),
+        path: dir.join(file_name),
+        hash: release.hash,
+        size: release.size,
+        extract: true,
+    }])
+}
+
+fn get_java_path(runtime_dir: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        runtime_dir.join("bin").join("java.exe")
+    } else if cfg!(target_os = "macos") {
+        runtime_dir
+            .join("Contents")
+            .join("Home")
+            .join("bin")
+            .join("java")
+    } else {
+        runtime_dir.join("bin").join("java")
+    }
+}
+
+fn get_subdirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// archives either contain the runtime directly or wrap it in a single folder
+fn find_java(dir: &Path) -> Option<PathBuf> {
+    let mut candidates = vec![dir.to_path_buf()];
+    candidates.extend(get_subdirs(dir));
+
+    candidates
+        .into_iter()
+        .map(|dir| get_java_path(&dir))
+        .find(|path| path.exists())
+}
+
+// takes a runtime id, see get_runtime_id
+pub fn get_path(java_version: &str) -> Result<PathBuf> {
+    let mut dirs = get_subdirs(&RUNTIMES_DIR.join(java_version));
+
+    // an old build sticks around until the next install, prefer the newest one
+    dirs.sort_by_key(|dir| std::cmp::Reverse(fs::metadata(dir).and_then(|m| m.modified()).ok()));
+
+    // runtimes are either extracted straight into the version directory
+    // or into a vendor-release subdirectory
+    dirs.iter()
+        .find_map(|dir| find_java(dir))
+        .ok_or_else(|| anyhow!("No runtime found for version {}", java_version))
+}
@@ -0,0 +1,173 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use super::get_subdirs;
+use crate::paths::RUNTIMES_DIR;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaInstallation {
+    pub path: PathBuf,
+    pub version: String,
+    // downloaded by the launcher, the version is the runtime directory name
+    pub managed: bool,
+}
+
+impl fmt::Display for JavaInstallation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.managed {
+            write!(f, "Java {} (managed)", self.version)
+        } else {
+            write!(f, "Java {} ({})", self.version, self.path.display())
+        }
+    }
+}
+
+fn get_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Some(program_files) = env::var_os(var) {
+                let program_files = PathBuf::from(program_files);
+
+                for vendor in ["Java", "Eclipse Adoptium", "Zulu", "Microsoft", "BellSoft"] {
+                    dirs.extend(get_subdirs(&program_files.join(vendor)));
+                }
+            }
+        }
+    } else if cfg!(target_os = "macos") {
+        dirs.extend(get_subdirs(Path::new("/Library/Java/JavaVirtualMachines")));
+    } else {
+        for parent in ["/usr/lib/jvm", "/usr/java", "/opt/java", "/opt/jdk"] {
+            dirs.extend(get_subdirs(Path::new(parent)));
+        }
+    }
+
+    dirs
+}
+
+fn find_java(dir: &Path) -> Option<PathBuf> {
+    let binary = if cfg!(target_os = "windows") {
+        "java.exe"
+    } else {
+        "java"
+    };
+
+    // JAVA_HOME on macOS points inside the bundle
+    [
+        dir.join("bin").join(binary),
+        dir.join("Contents").join("Home").join("bin").join(binary),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+fn parse_version(output: &str) -> Option<String> {
+    // e.g. openjdk version "17.0.8" 2023-07-18
+    let line = output.lines().find(|line| line.contains("version"))?;
+    let version = line.split('"').nth(1)?;
+
+    Some(version.to_string())
+}
+
+// runs `java -version` to make sure the binary actually works
+pub fn check(path: &Path) -> Result<String> {
+    let output = process::Command::new(path).arg("-version").output()?;
+
+    if !output.status.success() {
+        bail!("{} -version exited with {}", path.display(), output.status);
+    }
+
+    // the version is printed to stderr
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    parse_version(&stderr).ok_or_else(|| anyhow!("Not a java binary: {}", path.display()))
+}
+
+// whether the runtime starts with the given flags, e.g. to check if a GC is compiled in
+pub fn supports_flags(path: &Path, flags: &[&str]) -> bool {
+    process::Command::new(path)
+        .args(flags)
+        .arg("-version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+pub async fn check_java(path: PathBuf) -> Result<JavaInstallation> {
+    let version = check(&path)?;
+
+    Ok(JavaInstallation {
+        path,
+        version,
+        managed: false,
+    })
+}
+
+pub async fn detect() -> Vec<JavaInstallation> {
+    let mut installations = Vec::new();
+
+    for dir in get_subdirs(&RUNTIMES_DIR) {
+        if let Some(version) = dir.file_name() {
+            if let Ok(path) = super::get_path(&version.to_string_lossy()) {
+                installations.push(JavaInstallation {
+                    path,
+                    version: version.to_string_lossy().to_string(),
+                    managed: true,
+                });
+            }
+        }
+    }
+
+    let mut dirs = Vec::new();
+    if let Some(java_home) = env::var_os("JAVA_HOME") {
+        dirs.push(PathBuf::from(java_home));
+    }
+    dirs.extend(get_search_dirs());
+
+    for dir in dirs {
+        let Some(path) = find_java(&dir) else {
+            continue;
+        };
+
+        // distros often symlink the same jvm under several names
+        let path = path.canonicalize().unwrap_or(path);
+        if installations.iter().any(|java| java.path == path) {
+            continue;
+        }
+
+        match check(&path) {
+            Ok(version) => installations.push(JavaInstallation {
+                path,
+                version,
+                managed: false,
+            }),
+            Err(error) => tracing::warn!("Skipping broken java installation: {}", error),
+        }
+    }
+
+    installations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version() {
+        let output = "openjdk version \"17.0.8\" 2023-07-18\nOpenJDK Runtime Environment Temurin-17.0.8+7 (build 17.0.8+7)\n";
+        assert_eq!(parse_version(output), Some("17.0.8".to_string()));
+
+        let output =
+            "java version \"1.8.0_381\"\nJava(TM) SE Runtime Environment (build 1.8.0_381-b09)\n";
+        assert_eq!(parse_version(output), Some("1.8.0_381".to_string()));
+
+        assert_eq!(parse_version("command not found"), None);
+    }
+}
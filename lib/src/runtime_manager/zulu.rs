@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use super::{Arch, Release, Vendor};
+use crate::{Hash, HashAlgorithm, AGENT};
+
+const API_URL: &str = "https://api.azul.com/metadata/v1/zulu/packages";
+
+#[cfg(target_os = "windows")]
+const OS: &str = "windows";
+
+#[cfg(target_os = "linux")]
+const OS: &str = "linux";
+
+#[cfg(target_os = "macos")]
+const OS: &str = "macos";
+
+#[cfg(target_os = "windows")]
+const ARCHIVE_TYPE: &str = "zip";
+
+#[cfg(not(target_os = "windows"))]
+const ARCHIVE_TYPE: &str = "tar.gz";
+
+#[derive(Deserialize)]
+struct Package {
+    package_uuid: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct PackageDetails {
+    download_url: String,
+    sha256_hash: String,
+    size: u64,
+}
+
+pub struct Zulu;
+
+impl Vendor for Zulu {
+    fn get_latest(&self, java_version: &str, arch: Arch) -> Result<Release> {
+        let url = format!(
+            "{API_URL}/?java_version={java_version}&os={OS}&arch={}&archive_type={ARCHIVE_TYPE}&java_package_type=jre&javafx_bundled=false&release_status=ga&availability_types=CA&latest=true",
+            match arch {
+                Arch::X64 => "x64",
+                Arch::Aarch64 => "aarch64",
+                Arch::X86 => "i686",
+                Arch::Arm => "arm",
+            }
+        );
+
+        let package = AGENT
+            .get(&url)
+            .call()?
+            .into_json::<Vec<Package>>()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No Zulu runtime found for version {}", java_version))?;
+
+        // the list doesn't include checksums
+        let details = AGENT
+            .get(&format!("{API_URL}/{}", package.package_uuid))
+            .call()?
+            .into_json::<PackageDetails>()?;
+
+        let id = package
+            .name
+            .trim_end_matches(&format!(".{ARCHIVE_TYPE}"))
+            .to_string();
+
+        Ok(Release {
+            id,
+            url: details.download_url,
+            hash: Some(Hash {
+                hash: details.sha256_hash,
+                function: HashAlgorithm::Sha256,
+            }),
+            size: Some(details.size),
+        })
+    }
+}
@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+// values encrypted with a key that never leaves this machine, for what
+// shouldn't be readable from a copied or shared accounts file
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::paths::SEALING_KEY_PATH;
+
+const KEY_LEN: usize = 32;
+
+fn random<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| anyhow!("Failed to generate random bytes"))?;
+
+    Ok(bytes)
+}
+
+// created on first use, only readable by the user
+fn load_key(path: &Path) -> Result<[u8; KEY_LEN]> {
+    if let Ok(bytes) = fs::read(path) {
+        return bytes
+            .try_into()
+            .map_err(|_| anyhow!("Invalid key in {}", path.display()));
+    }
+
+    let key = random()?;
+
+    let mut options = File::options();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    match options.open(path) {
+        Ok(mut file) => file.write_all(&key)?,
+        // another launcher process got there first
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => return load_key(path),
+        Err(error) => return Err(error.into()),
+    }
+
+    Ok(key)
+}
+
+fn get_key(key: &[u8; KEY_LEN]) -> Result<LessSafeKey> {
+    let key = UnboundKey::new(&AES_256_GCM, key).map_err(|_| anyhow!("Invalid key"))?;
+
+    Ok(LessSafeKey::new(key))
+}
+
+// the random nonce goes in front of the ciphertext
+fn seal(key: &[u8; KEY_LEN], plaintext: &[u8]) -> Result<String> {
+    let nonce = random::<NONCE_LEN>()?;
+
+    let mut in_out = plaintext.to_vec();
+    get_key(key)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow!("Failed to encrypt"))?;
+
+    Ok(STANDARD.encode([&nonce[..], &in_out].concat()))
+}
+
+fn open(key: &[u8; KEY_LEN], sealed: &str) -> Result<Vec<u8>> {
+    let bytes = STANDARD.decode(sealed)?;
+    if bytes.len() < NONCE_LEN {
+        bail!("Truncated sealed value");
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| anyhow!("Invalid nonce"))?;
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = get_key(key)?
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| anyhow!("Failed to decrypt, the value was sealed elsewhere or changed"))?;
+
+    Ok(plaintext.to_vec())
+}
+
+// for #[serde(with = "crate::sealed")], the value is sealed as json
+pub fn serialize<T: Serialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    let sealed = serde_json::to_vec(value)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| seal(&load_key(&SEALING_KEY_PATH)?, &json))
+        .map_err(serde::ser::Error::custom)?;
+
+    serializer.serialize_str(&sealed)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Stored<T> {
+    Sealed(String),
+    // written before the value was sealed
+    Plain(T),
+}
+
+// a value that can't be opened is dropped, as if it was never there
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: DeserializeOwned + Default,
+    D: Deserializer<'de>,
+{
+    let sealed = match Stored::<T>::deserialize(deserializer)? {
+        Stored::Sealed(sealed) => sealed,
+        Stored::Plain(value) => return Ok(value),
+    };
+
+    let value = load_key(&SEALING_KEY_PATH)
+        .and_then(|key| open(&key, &sealed))
+        .and_then(|json| Ok(serde_json::from_slice(&json)?));
+
+    Ok(value.unwrap_or_else(|error| {
+        tracing::warn!("dropping a sealed value: {error:#}");
+        T::default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_only_what_it_sealed() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = load_key(&dir.path().join("sealing.key")).unwrap();
+        assert_eq!(load_key(&dir.path().join("sealing.key")).unwrap(), key);
+
+        let sealed = seal(&key, b"tombstone").unwrap();
+        assert!(!sealed.contains("tombstone"));
+        assert_eq!(open(&key, &sealed).unwrap(), b"tombstone");
+
+        let other = load_key(&dir.path().join("other.key")).unwrap();
+        assert!(open(&other, &sealed).is_err());
+    }
+}
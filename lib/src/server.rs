@@ -0,0 +1,185 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+
+// the server refuses to start until eula.txt says so
+pub fn eula_accepted(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("eula.txt"))
+        .is_ok_and(|eula| eula.lines().any(|line| line.trim() == "eula=true"))
+}
+
+pub fn accept_eula(dir: &Path) -> Result<()> {
+    fs::write(
+        dir.join("eula.txt"),
+        format!("# accepted in the launcher, see {EULA_URL}\neula=true\n"),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+    Bool,
+    Int { min: i64, max: i64 },
+    Choice(&'static [&'static str]),
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Property {
+    pub key: &'static str,
+    pub kind: PropertyKind,
+    pub default: &'static str,
+}
+
+impl Property {
+    const fn new(key: &'static str, kind: PropertyKind, default: &'static str) -> Self {
+        Self { key, kind, default }
+    }
+
+    pub fn validate(&self, value: &str) -> Result<()> {
+        match self.kind {
+            PropertyKind::Bool if value != "true" && value != "false" => {
+                bail!("{} must be true or false", self.key)
+            }
+            PropertyKind::Int { min, max } => match value.parse::<i64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(()),
+                _ => bail!("{} must be a number between {} and {}", self.key, min, max),
+            },
+            PropertyKind::Choice(choices) if !choices.contains(&value) => {
+                bail!("{} must be one of {}", self.key, choices.join(", "))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// the ones worth a field, anything else is kept as it is
+pub const PROPERTIES: &[Property] = &[
+    Property::new("motd", PropertyKind::Text, "A Minecraft Server"),
+    Property::new(
+        "server-port",
+        PropertyKind::Int { min: 1, max: 65535 },
+        "25565",
+    ),
+    Property::new("max-players", PropertyKind::Int { min: 1, max: 1000 }, "20"),
+    Property::new(
+        "gamemode",
+        PropertyKind::Choice(&["survival", "creative", "adventure", "spectator"]),
+        "survival",
+    ),
+    Property::new(
+        "difficulty",
+        PropertyKind::Choice(&["peaceful", "easy", "normal", "hard"]),
+        "easy",
+    ),
+    Property::new("hardcore", PropertyKind::Bool, "false"),
+    Property::new("pvp", PropertyKind::Bool, "true"),
+    Property::new("online-mode", PropertyKind::Bool, "true"),
+    Property::new("white-list", PropertyKind::Bool, "false"),
+    Property::new("allow-flight", PropertyKind::Bool, "false"),
+    Property::new("enable-command-block", PropertyKind::Bool, "false"),
+    Property::new("level-name", PropertyKind::Text, "world"),
+    Property::new("level-seed", PropertyKind::Text, ""),
+    Property::new("view-distance", PropertyKind::Int { min: 3, max: 32 }, "10"),
+    Property::new(
+        "simulation-distance",
+        PropertyKind::Int { min: 3, max: 32 },
+        "10",
+    ),
+    Property::new(
+        "spawn-protection",
+        PropertyKind::Int { min: 0, max: 1000 },
+        "16",
+    ),
+];
+
+// lines are kept as they are, so comments and unknown keys survive an edit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServerProperties {
+    lines: Vec<String>,
+}
+
+impl ServerProperties {
+    // a server that never ran doesn't have the file yet
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join("server.properties");
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        for property in PROPERTIES {
+            property.validate(self.get(property))?;
+        }
+
+        fs::write(dir.join("server.properties"), self.lines.join("\n") + "\n")?;
+
+        Ok(())
+    }
+
+    fn find(&self, key: &str) -> Option<usize> {
+        self.lines.iter().position(|line| {
+            !line.starts_with('#') && line.split_once('=').is_some_and(|(k, _)| k.trim() == key)
+        })
+    }
+
+    pub fn get(&self, property: &Property) -> &str {
+        self.find(property.key)
+            .and_then(|i| self.lines[i].split_once('='))
+            .map_or(property.default, |(_, value)| value.trim())
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        let line = format!("{key}={value}");
+
+        match self.find(key) {
+            Some(i) => self.lines[i] = line,
+            None => self.lines.push(line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_unknown_lines() {
+        let mut properties =
+            ServerProperties::parse("#Minecraft server properties\nmotd=Hi\nfoo=bar");
+
+        assert_eq!(properties.get(&PROPERTIES[0]), "Hi");
+        assert_eq!(properties.get(&PROPERTIES[1]), "25565");
+
+        properties.set("motd", "Hello");
+        properties.set("server-port", "25566");
+
+        assert_eq!(
+            properties.lines,
+            [
+                "#Minecraft server properties",
+                "motd=Hello",
+                "foo=bar",
+                "server-port=25566"
+            ]
+        );
+        assert!(PROPERTIES[1].validate("70000").is_err());
+    }
+}
@@ -0,0 +1,406 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::nbt::{self, Tag};
+
+const DEFAULT_PORT: u16 = 25565;
+const TIMEOUT: Duration = Duration::from_secs(5);
+// any version gets a status answer
+const PROTOCOL_VERSION: i32 = 47;
+const COMPOUND: u8 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerEntry {
+    pub name: String,
+    pub address: String,
+}
+
+// servers.dat, the multiplayer screen's list; icons and other tags are kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerList {
+    root_name: String,
+    root: Tag,
+}
+
+impl Default for ServerList {
+    fn default() -> Self {
+        Self {
+            root_name: String::new(),
+            root: Tag::Compound(vec![(
+                "servers".to_string(),
+                Tag::List(COMPOUND, Vec::new()),
+            )]),
+        }
+    }
+}
+
+fn text(tag: &Tag, key: &str) -> String {
+    match tag.get(key) {
+        Some(Tag::String(value)) => value.clone(),
+        _ => String::new(),
+    }
+}
+
+impl ServerList {
+    fn servers(&self) -> &[Tag] {
+        match self.root.get("servers") {
+            Some(Tag::List(_, servers)) => servers,
+            _ => &[],
+        }
+    }
+
+    fn servers_mut(&mut self) -> &mut Vec<Tag> {
+        if !matches!(self.root.get("servers"), Some(Tag::List(..))) {
+            self.root.insert("servers", Tag::List(COMPOUND, Vec::new()));
+        }
+
+        match self.root.get_mut("servers") {
+            Some(Tag::List(element, servers)) => {
+                *element = COMPOUND;
+                servers
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn entries(&self) -> Vec<ServerEntry> {
+        self.servers()
+            .iter()
+            .map(|server| ServerEntry {
+                name: text(server, "name"),
+                address: text(server, "ip"),
+            })
+            .collect()
+    }
+
+    pub fn add(&mut self, name: String, address: String) -> Result<()> {
+        if address.trim().is_empty() {
+            bail!("The server needs an address");
+        }
+        parse_address(&address)?;
+
+        self.servers_mut().push(Tag::Compound(vec![
+            ("name".to_string(), Tag::String(name)),
+            ("ip".to_string(), Tag::String(address.trim().to_string())),
+        ]));
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        let servers = self.servers_mut();
+        if index < servers.len() {
+            servers.remove(index);
+        }
+    }
+}
+
+// a missing servers.dat is an empty list
+pub fn load(instance_dir: &Path) -> Result<ServerList> {
+    let Ok(bytes) = fs::read(instance_dir.join("servers.dat")) else {
+        return Ok(ServerList::default());
+    };
+
+    let (root_name, root) = nbt::read(&bytes)?;
+
+    Ok(ServerList { root_name, root })
+}
+
+pub fn save(instance_dir: &Path, list: &ServerList) -> Result<()> {
+    let bytes = nbt::write(&list.root_name, &list.root)?;
+
+    let temp_path = instance_dir.join("servers.dat.tmp");
+    fs::write(&temp_path, bytes)?;
+    fs::rename(temp_path, instance_dir.join("servers.dat"))?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerStatus {
+    pub motd: String,
+    pub version: String,
+    pub online: u32,
+    pub max: u32,
+    pub latency: Duration,
+}
+
+// "host", "host:port" or "[ipv6]:port"; srv records aren't looked up
+fn parse_address(address: &str) -> Result<(String, u16)> {
+    let address = address.trim();
+
+    let (host, port) = if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest
+            .split_once(']')
+            .ok_or_else(|| anyhow!("Invalid address: {address}"))?;
+        (host, rest.strip_prefix(':'))
+    } else {
+        match address.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => (host, Some(port)),
+            _ => (address, None),
+        }
+    };
+
+    let port = match port {
+        Some(port) => port
+            .parse()
+            .map_err(|_| anyhow!("Invalid port in {address}"))?,
+        None => DEFAULT_PORT,
+    };
+
+    if host.is_empty() {
+        bail!("Invalid address: {address}");
+    }
+
+    Ok((host.to_string(), port))
+}
+
+// without a port the game also looks up _minecraft._tcp srv records, this doesn't,
+// so those servers look offline here
+pub fn may_use_srv(address: &str) -> bool {
+    let address = address.trim();
+
+    !address.starts_with('[') && !address.contains(':') && address.parse::<IpAddr>().is_err()
+}
+
+fn write_varint(out: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7f == 0 {
+            out.push(value as u8);
+            return;
+        }
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> Result<i32> {
+    let mut value = 0u32;
+
+    for i in 0..5 {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        value |= ((byte[0] & 0x7f) as u32) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+
+    bail!("VarInt is too long")
+}
+
+fn write_string(out: &mut Vec<u8>, string: &str) {
+    write_varint(out, string.len() as i32);
+    out.extend(string.as_bytes());
+}
+
+// packets are prefixed with their length
+fn send_packet(stream: &mut TcpStream, id: i32, data: &[u8]) -> Result<()> {
+    let mut body = Vec::new();
+    write_varint(&mut body, id);
+    body.extend(data);
+
+    let mut packet = Vec::new();
+    write_varint(&mut packet, body.len() as i32);
+    packet.extend(body);
+
+    stream.write_all(&packet)?;
+
+    Ok(())
+}
+
+fn read_packet(stream: &mut TcpStream) -> Result<(i32, Vec<u8>)> {
+    let len = read_varint(stream)?;
+    if !(1..=1 << 21).contains(&len) {
+        bail!("Invalid packet length");
+    }
+
+    let mut body = vec![0; len as usize];
+    stream.read_exact(&mut body)?;
+
+    let mut reader = body.as_slice();
+    let id = read_varint(&mut reader)?;
+
+    Ok((id, reader.to_vec()))
+}
+
+// the motd is either a string or a chat component, formatting codes are dropped
+fn flatten_description(description: &Value) -> String {
+    fn collect(value: &Value, out: &mut String) {
+        match value {
+            Value::String(text) => out.push_str(text),
+            Value::Object(component) => {
+                if let Some(text) = component.get("text") {
+                    collect(text, out);
+                }
+                if let Some(Value::Array(extra)) = component.get("extra") {
+                    extra.iter().for_each(|value| collect(value, out));
+                }
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect(value, out)),
+            _ => {}
+        }
+    }
+
+    let mut raw = String::new();
+    collect(description, &mut raw);
+
+    let mut motd = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            motd.push(c);
+        }
+    }
+
+    motd.trim().to_string()
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    max: u32,
+    online: u32,
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    version: StatusVersion,
+    players: StatusPlayers,
+    #[serde(default)]
+    description: Value,
+}
+
+// server list ping: handshake, status request, then a ping for the latency
+pub async fn ping(address: String) -> Result<ServerStatus> {
+    let (host, port) = parse_address(&address)?;
+    let socket = (host.as_str(), port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow!("Can't resolve {host}"))?;
+
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, PROTOCOL_VERSION);
+    write_string(&mut handshake, &host);
+    handshake.extend(port.to_be_bytes());
+    // the next state is status
+    write_varint(&mut handshake, 1);
+    send_packet(&mut stream, 0x00, &handshake)?;
+    send_packet(&mut stream, 0x00, &[])?;
+
+    let (id, data) = read_packet(&mut stream)?;
+    if id != 0x00 {
+        bail!("Unexpected answer from {address}");
+    }
+    let mut reader = data.as_slice();
+    let len = read_varint(&mut reader)? as usize;
+    let json = reader
+        .get(..len)
+        .ok_or_else(|| anyhow!("Truncated answer from {address}"))?;
+    let status = serde_json::from_slice::<StatusResponse>(json)?;
+
+    let start = Instant::now();
+    send_packet(&mut stream, 0x01, &0i64.to_be_bytes())?;
+    read_packet(&mut stream)?;
+    let latency = start.elapsed();
+
+    Ok(ServerStatus {
+        motd: flatten_description(&status.description),
+        version: status.version.name,
+        online: status.players.online,
+        max: status.players.max,
+        latency,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_addresses() {
+        assert_eq!(
+            parse_address("play.example.com").unwrap(),
+            ("play.example.com".to_string(), 25565)
+        );
+        assert_eq!(
+            parse_address("1.2.3.4:25570").unwrap(),
+            ("1.2.3.4".to_string(), 25570)
+        );
+        assert_eq!(
+            parse_address("[::1]:25566").unwrap(),
+            ("::1".to_string(), 25566)
+        );
+        assert!(parse_address("host:port").is_err());
+    }
+
+    #[test]
+    fn detects_possible_srv_records() {
+        assert!(may_use_srv("play.example.com"));
+        assert!(!may_use_srv("play.example.com:25565"));
+        assert!(!may_use_srv("1.2.3.4"));
+        assert!(!may_use_srv("[::1]"));
+    }
+
+    #[test]
+    fn encodes_varints() {
+        for value in [0, 1, 127, 128, 25565, -1] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, value);
+            assert_eq!(read_varint(&mut bytes.as_slice()).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn flattens_the_motd() {
+        let description = json!({
+            "text": "§aHello ",
+            "extra": [{ "text": "world" }, "!"]
+        });
+        assert_eq!(flatten_description(&description), "Hello world!");
+    }
+
+    #[test]
+    fn edits_the_list() {
+        let mut list = ServerList::default();
+        list.add("Home".to_string(), "localhost".to_string())
+            .unwrap();
+        list.add("Other".to_string(), "example.com:25570".to_string())
+            .unwrap();
+        assert!(list.add(String::new(), " ".to_string()).is_err());
+
+        list.remove(0);
+
+        let dir = tempfile::tempdir().unwrap();
+        save(dir.path(), &list).unwrap();
+        assert_eq!(
+            load(dir.path()).unwrap().entries(),
+            vec![ServerEntry {
+                name: "Other".to_string(),
+                address: "example.com:25570".to_string(),
+            }]
+        );
+    }
+}
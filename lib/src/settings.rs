@@ -1,22 +1,119 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use std::fs;
+use std::{fmt, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::paths::SETTINGS_PATH;
+use crate::file_lock;
+use crate::i18n::Language;
+use crate::jvm::{self, GarbageCollector, JvmPreset};
+use crate::logging::LogLevel;
+use crate::paths::{BASE_DIR, SETTINGS_PATH};
+use crate::runtime_manager::JavaVendor;
+use crate::DownloadOptions;
+
+// also used while the setting is being edited and is still empty
+pub const DEFAULT_MEMORY: &str = "4G";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Dark, Theme::Light];
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Theme::Dark => write!(f, "Dark"),
+            Theme::Light => write!(f, "Light"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstancesView {
+    #[default]
+    Grid,
+    List,
+}
+
+impl InstancesView {
+    pub const ALL: [InstancesView; 2] = [InstancesView::Grid, InstancesView::List];
+}
+
+impl fmt::Display for InstancesView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstancesView::Grid => write!(f, "Grid"),
+            InstancesView::List => write!(f, "List"),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Settings {
     pub check_for_updates: bool,
+    // offer to report the crashes of the previous session
+    pub crash_reports: bool,
+    // the window comes back when the last game exits
+    pub minimize_on_launch: bool,
+    pub download_threads: usize,
+    pub download_retries: u32,
+    pub use_mirrors: bool,
+    pub download_speed_limit: u32,
+    pub theme: Theme,
+    pub instances_view: InstancesView,
+    pub language: Language,
+    pub java_vendor: JavaVendor,
+    pub jvm_preset: JvmPreset,
+    pub garbage_collector: GarbageCollector,
+    pub instances_dir: Option<PathBuf>,
+    // the defaults of every instance, see InheritedSetting
+    pub memory: String,
+    pub window_width: Option<u32>,
+    pub window_height: Option<u32>,
+    pub pre_launch_hook: Option<String>,
+    pub post_exit_hook: Option<String>,
+    // applied on the next start
+    pub log_level: LogLevel,
+    // deleted instances can be restored for this long
+    pub trash_retention_days: u32,
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             check_for_updates: true,
+            crash_reports: false,
+            minimize_on_launch: false,
+            download_threads: 8,
+            download_retries: 3,
+            use_mirrors: false,
+            download_speed_limit: 0,
+            theme: Theme::Dark,
+            instances_view: InstancesView::Grid,
+            language: Language::English,
+            java_vendor: JavaVendor::Adoptium,
+            jvm_preset: JvmPreset::Optimized,
+            garbage_collector: GarbageCollector::Default,
+            instances_dir: None,
+            memory: DEFAULT_MEMORY.to_string(),
+            window_width: None,
+            window_height: None,
+            pre_launch_hook: None,
+            post_exit_hook: None,
+            log_level: LogLevel::Info,
+            trash_retention_days: 30,
         }
     }
 }
@@ -27,14 +124,36 @@ impl Settings {
             return Ok(Self::default());
         }
 
-        let settings = fs::read_to_string(&*SETTINGS_PATH)?;
-        let settings: Self = toml::from_str(&settings)?;
+        let settings = file_lock::read(&SETTINGS_PATH, |settings| {
+            toml::from_str::<Self>(settings)
+                .with_context(|| format!("Invalid settings: {}", SETTINGS_PATH.display()))
+        })?;
         Ok(settings)
     }
 
+    pub fn instances_dir(&self) -> PathBuf {
+        self.instances_dir
+            .clone()
+            .unwrap_or_else(|| BASE_DIR.join("instances"))
+    }
+
+    pub fn download_options(&self) -> DownloadOptions {
+        DownloadOptions {
+            threads: self.download_threads,
+            retries: self.download_retries,
+            use_mirrors: self.use_mirrors,
+            speed_limit: self.download_speed_limit,
+        }
+    }
+
     pub fn save(&self) -> Result<()> {
+        // empty means the default
+        if !self.memory.is_empty() {
+            jvm::validate_memory(&self.memory)?;
+        }
+
         let settings = toml::to_string_pretty(self)?;
-        fs::write(&*SETTINGS_PATH, settings)?;
+        file_lock::write(&SETTINGS_PATH, settings)?;
         Ok(())
     }
 }
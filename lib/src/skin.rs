@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::io;
+
+use anyhow::{bail, Result};
+use image::{imageops, DynamicImage, GenericImageView, ImageFormat, RgbaImage};
+
+use crate::accounts::{self, Account};
+use crate::authlib_injector::ELY_BY_SERVER;
+use crate::AGENT;
+
+// a player is 16x32 texture pixels seen from the front or the back
+const WIDTH: u32 = 16;
+const HEIGHT: u32 = 32;
+const SCALE: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkinPreview {
+    pub account_id: String,
+    // png
+    pub front: Vec<u8>,
+    pub back: Vec<u8>,
+}
+
+// a rectangle of the skin texture and where it goes in the preview
+struct Part {
+    from: (u32, u32),
+    size: (u32, u32),
+    to: (u32, u32),
+}
+
+const fn part(from: (u32, u32), size: (u32, u32), to: (u32, u32)) -> Part {
+    Part { from, size, to }
+}
+
+const HEAD: (u32, u32) = (8, 8);
+const BODY: (u32, u32) = (8, 12);
+const LIMB: (u32, u32) = (4, 12);
+
+// the player's right side is on the viewer's left
+const FRONT: [Part; 6] = [
+    part((8, 8), HEAD, (4, 0)),
+    part((20, 20), BODY, (4, 8)),
+    part((44, 20), LIMB, (0, 8)),
+    part((36, 52), LIMB, (12, 8)),
+    part((4, 20), LIMB, (4, 20)),
+    part((20, 52), LIMB, (8, 20)),
+];
+const FRONT_OVERLAY: [Part; 6] = [
+    part((40, 8), HEAD, (4, 0)),
+    part((20, 36), BODY, (4, 8)),
+    part((44, 36), LIMB, (0, 8)),
+    part((52, 52), LIMB, (12, 8)),
+    part((4, 36), LIMB, (4, 20)),
+    part((4, 52), LIMB, (8, 20)),
+];
+
+const BACK: [Part; 6] = [
+    part((24, 8), HEAD, (4, 0)),
+    part((32, 20), BODY, (4, 8)),
+    part((52, 20), LIMB, (12, 8)),
+    part((44, 52), LIMB, (0, 8)),
+    part((12, 20), LIMB, (8, 20)),
+    part((28, 52), LIMB, (4, 20)),
+];
+const BACK_OVERLAY: [Part; 6] = [
+    part((56, 8), HEAD, (4, 0)),
+    part((32, 36), BODY, (4, 8)),
+    part((52, 36), LIMB, (12, 8)),
+    part((60, 52), LIMB, (0, 8)),
+    part((12, 36), LIMB, (8, 20)),
+    part((12, 52), LIMB, (4, 20)),
+];
+
+fn paste(canvas: &mut RgbaImage, skin: &DynamicImage, from: &Part, to: &Part, mirror: bool) {
+    let mut crop = skin
+        .view(from.from.0, from.from.1, from.size.0, from.size.1)
+        .to_image();
+    if mirror {
+        crop = imageops::flip_horizontal(&crop);
+    }
+
+    imageops::overlay(canvas, &crop, to.to.0.into(), to.to.1.into());
+}
+
+fn compose(skin: &DynamicImage, parts: &[Part], overlay: &[Part]) -> RgbaImage {
+    // skins from before 1.8 are 64x32, without the left limbs and the overlays
+    let legacy = skin.height() < 64;
+
+    let mut canvas = RgbaImage::new(WIDTH, HEIGHT);
+
+    for (i, part) in parts.iter().enumerate() {
+        // there the left limbs mirror the right ones, which come right before them
+        if legacy && part.from.1 >= 32 {
+            paste(&mut canvas, skin, &parts[i - 1], part, true);
+        } else {
+            paste(&mut canvas, skin, part, part, false);
+        }
+    }
+
+    // the hat is the only overlay legacy skins have
+    let overlay = if legacy { &overlay[..1] } else { overlay };
+    for part in overlay {
+        paste(&mut canvas, skin, part, part, false);
+    }
+
+    imageops::resize(
+        &canvas,
+        WIDTH * SCALE,
+        HEIGHT * SCALE,
+        imageops::FilterType::Nearest,
+    )
+}
+
+fn to_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image.write_to(&mut io::Cursor::new(&mut bytes), ImageFormat::Png)?;
+
+    Ok(bytes)
+}
+
+fn render(account_id: String, skin: &[u8]) -> Result<SkinPreview> {
+    let skin = image::load_from_memory_with_format(skin, ImageFormat::Png)?;
+    if skin.width() != 64 || (skin.height() != 64 && skin.height() != 32) {
+        bail!("Unsupported skin size: {}x{}", skin.width(), skin.height());
+    }
+
+    Ok(SkinPreview {
+        account_id,
+        front: to_png(&compose(&skin, &FRONT, &FRONT_OVERLAY))?,
+        back: to_png(&compose(&skin, &BACK, &BACK_OVERLAY))?,
+    })
+}
+
+// same sources as the heads, but the whole texture
+pub async fn get_preview(account: Account) -> Result<SkinPreview> {
+    let url = match account.auth_server.as_deref() {
+        None => format!("https://crafatar.com/skins/{}", account.mc_id),
+        Some(ELY_BY_SERVER) => accounts::ely_by_skin_url(&account.mc_username),
+        Some(_) => bail!("This account's server doesn't share skins"),
+    };
+
+    let mut bytes = Vec::new();
+    io::copy(&mut AGENT.get(&url).call()?.into_reader(), &mut bytes)?;
+
+    render(account.mc_id, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn puts_the_face_on_the_front() {
+        let red = Rgba([255, 0, 0, 255]);
+
+        let mut skin = RgbaImage::new(64, 32);
+        skin.put_pixel(8, 8, red);
+        let skin = DynamicImage::ImageRgba8(skin);
+
+        let front = compose(&skin, &FRONT, &FRONT_OVERLAY);
+        assert_eq!(front.dimensions(), (WIDTH * SCALE, HEIGHT * SCALE));
+        assert_eq!(*front.get_pixel(4 * SCALE, 0), red);
+
+        let back = compose(&skin, &BACK, &BACK_OVERLAY);
+        assert_ne!(*back.get_pixel(4 * SCALE, 0), red);
+    }
+}
@@ -0,0 +1,293 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::authlib_injector;
+use crate::fabric::FabricMeta;
+use crate::instances::{copy_dir, Instance, InstanceKind, Loader};
+use crate::paths::{ASSETS_DIR, LIBRARIES_DIR, RUNTIMES_DIR};
+use crate::runtime_manager::{self, Arch};
+use crate::vanilla_installer::VersionMeta;
+
+// Shared between all instances
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageArea {
+    Assets,
+    Libraries,
+    Runtimes,
+}
+
+impl StorageArea {
+    pub const ALL: [StorageArea; 3] = [Self::Assets, Self::Libraries, Self::Runtimes];
+
+    pub fn get_dir(&self) -> PathBuf {
+        match self {
+            Self::Assets => ASSETS_DIR.to_path_buf(),
+            Self::Libraries => LIBRARIES_DIR.to_path_buf(),
+            Self::Runtimes => RUNTIMES_DIR.to_path_buf(),
+        }
+    }
+}
+
+impl Display for StorageArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assets => write!(f, "Assets"),
+            Self::Libraries => write!(f, "Libraries"),
+            Self::Runtimes => write!(f, "Java runtimes"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanReport {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub instances: Vec<(String, u64)>,
+    pub areas: Vec<(StorageArea, u64)>,
+}
+
+impl DiskUsage {
+    pub fn total(&self) -> u64 {
+        let instances = self.instances.iter().map(|(_, size)| size).sum::<u64>();
+        let areas = self.areas.iter().map(|(_, size)| size).sum::<u64>();
+
+        instances + areas
+    }
+}
+
+pub fn get_dir_size(dir: &Path) -> Result<u64> {
+    let mut size = 0;
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+
+        if entry.file_type()?.is_dir() {
+            size += get_dir_size(&entry.path())?;
+        } else {
+            size += entry.metadata()?.len();
+        }
+    }
+
+    Ok(size)
+}
+
+pub async fn get_disk_usage(instance_dirs: Vec<(String, PathBuf)>) -> Result<DiskUsage> {
+    let mut usage = DiskUsage::default();
+
+    for (name, dir) in instance_dirs {
+        usage.instances.push((name, get_dir_size(&dir)?));
+    }
+
+    for area in StorageArea::ALL {
+        usage.areas.push((area, get_dir_size(&area.get_dir())?));
+    }
+
+    // biggest first
+    usage.instances.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    Ok(usage)
+}
+
+// files the given instances still need, a meta that can't be read would make
+// its files look unused so the clean is aborted instead
+fn get_references(instances: &[Instance]) -> Result<HashSet<PathBuf>> {
+    let mut references = HashSet::new();
+
+    // any account can be from an authlib-injector server
+    references.extend(authlib_injector::get_agent_path());
+
+    for instance in instances {
+        let version_meta = VersionMeta::load(&instance.minecraft).with_context(|| {
+            format!(
+                "Can't read the meta of Minecraft {}, nothing was removed",
+                instance.minecraft
+            )
+        })?;
+
+        match instance.kind {
+            InstanceKind::Client => references.extend(version_meta.get_files()?),
+            InstanceKind::Server => {
+                references.insert(version_meta.get_server_path());
+            }
+        }
+
+        if let (Loader::Fabric, Some(loader_version)) = (instance.loader, &instance.loader_version)
+        {
+            let fabric_meta =
+                FabricMeta::load(&instance.minecraft, loader_version).with_context(|| {
+                    format!(
+                        "Can't read the meta of Fabric {}, nothing was removed",
+                        loader_version
+                    )
+                })?;
+
+            references.extend(fabric_meta.get_paths());
+        }
+    }
+
+    Ok(references)
+}
+
+fn clean_dir(dir: &Path, references: &HashSet<PathBuf>, report: &mut CleanReport) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if entry.file_type()?.is_dir() {
+            clean_dir(&path, references, report)?;
+
+            // only succeeds if the directory is empty now
+            let _ = fs::remove_dir(&path);
+        } else if path.extension().is_some_and(|ext| ext == "part") {
+            // a paused or running download
+            continue;
+        } else if !references.contains(&path) {
+            report.bytes += entry.metadata()?.len();
+            report.files += 1;
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+// runtimes are stored by java version and, e.g. for rosetta, architecture
+fn clean_runtimes(instances: &[Instance], report: &mut CleanReport) -> Result<()> {
+    let used = instances
+        .iter()
+        .map(|instance| {
+            let arch = match instance.kind {
+                InstanceKind::Client => VersionMeta::load(&instance.minecraft)
+                    .map(|meta| meta.get_arch())
+                    .unwrap_or(Arch::HOST),
+                InstanceKind::Server => Arch::HOST,
+            };
+
+            runtime_manager::get_runtime_id(instance.java.as_deref().unwrap_or("17"), arch)
+        })
+        .collect::<HashSet<_>>();
+
+    for entry in fs::read_dir(&*RUNTIMES_DIR)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+
+        if !used.contains(name.as_str()) {
+            report.bytes += get_dir_size(&path)?;
+            report.files += 1;
+            fs::remove_dir_all(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+// removes whatever no instance references anymore
+pub async fn clean_unused(
+    instances: Vec<Instance>,
+    areas: Vec<StorageArea>,
+) -> Result<CleanReport> {
+    let references = get_references(&instances)?;
+    let mut report = CleanReport::default();
+
+    for area in areas {
+        match area {
+            StorageArea::Assets => {
+                clean_dir(&ASSETS_DIR.join("objects"), &references, &mut report)?
+            }
+            StorageArea::Libraries => clean_dir(&LIBRARIES_DIR, &references, &mut report)?,
+            StorageArea::Runtimes => clean_runtimes(&instances, &mut report)?,
+        }
+    }
+
+    tracing::info!(
+        "removed {} unused files ({} bytes)",
+        report.files,
+        report.bytes
+    );
+
+    Ok(report)
+}
+
+// bytes available on the filesystem holding `dir`
+#[cfg(not(target_os = "windows"))]
+pub fn get_free_space(dir: &Path) -> Result<u64> {
+    let output = process::Command::new("df").arg("-Pk").arg(dir).output()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    // Filesystem 1024-blocks Used Available Capacity Mounted on
+    let Some(available) = output
+        .lines()
+        .nth(1)
+        .and_then(|line| line.split_whitespace().nth(3))
+        .and_then(|available| available.parse::<u64>().ok())
+    else {
+        bail!("Failed to get the free space of {}", dir.display());
+    };
+
+    Ok(available * 1024)
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_free_space(dir: &Path) -> Result<u64> {
+    let output = process::Command::new("powershell")
+        .args(["-NoProfile", "-Command"])
+        // quotes are doubled inside a single-quoted powershell string
+        .arg(format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            dir.display().to_string().replace('\'', "''")
+        ))
+        .output()?;
+
+    let Ok(available) = String::from_utf8_lossy(&output.stdout).trim().parse() else {
+        bail!("Failed to get the free space of {}", dir.display());
+    };
+
+    Ok(available)
+}
+
+// copies the instances to an empty folder, and only removes the old ones once everything is there
+pub async fn move_instances(from: PathBuf, to: PathBuf) -> Result<()> {
+    if to.starts_with(&from) {
+        bail!("The new folder can't be inside the current one");
+    }
+
+    fs::create_dir_all(&to)?;
+    if fs::read_dir(&to)?.next().is_some() {
+        bail!("The new folder must be empty");
+    }
+
+    let needed = get_dir_size(&from)?;
+    let available = get_free_space(&to)?;
+    if needed > available {
+        bail!(
+            "Not enough free space: {} bytes needed, {} bytes available",
+            needed,
+            available
+        );
+    }
+
+    if let Err(error) = copy_dir(&from, &to) {
+        fs::remove_dir_all(&to)?;
+        return Err(error);
+    }
+
+    fs::remove_dir_all(&from)?;
+
+    tracing::info!("moved {} bytes of instances to {}", needed, to.display());
+
+    Ok(())
+}
@@ -1,35 +1,322 @@
 // SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use anyhow::Result;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, process};
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::signature::{UnparsedPublicKey, ED25519};
 use serde::Deserialize;
 use version_compare::Version;
 
+use crate::paths::UPDATES_DIR;
 use crate::AGENT;
 
 const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/mq1/CrabLauncher/releases/latest";
-const RELEASES_BASE_URL: &str = "https://github.com/mq1/CrabLauncher/releases/tag/";
+
+// minisign public key of the release workflow, set when building releases
+const PUBLIC_KEY: Option<&str> = option_env!("CRAB_LAUNCHER_MINISIGN_KEY");
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
 
 #[derive(Deserialize)]
 struct Release {
     tag_name: String,
+    assets: Vec<Asset>,
 }
 
+// a release whose signature has been checked, the artifact is only downloaded
+// once the user asks for it
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub version: String,
+    name: String,
+    url: String,
+    signature: String,
+}
+
+#[cfg(target_os = "macos")]
+const ARTIFACT_SUFFIX: Option<&str> = Some("-MacOS-Universal2.dmg");
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+const ARTIFACT_SUFFIX: Option<&str> = Some("-Linux-x86_64.tar.gz");
+
+#[cfg(all(target_os = "windows", target_arch = "x86_64"))]
+const ARTIFACT_SUFFIX: Option<&str> = Some("-Windows-x86_64.zip");
+
+#[cfg(not(any(
+    target_os = "macos",
+    all(target_os = "linux", target_arch = "x86_64"),
+    all(target_os = "windows", target_arch = "x86_64")
+)))]
+const ARTIFACT_SUFFIX: Option<&str> = None;
+
 async fn get_latest_release() -> Result<Release> {
     let resp = AGENT.get(LATEST_RELEASE_URL).call()?.into_json()?;
 
     Ok(resp)
 }
 
-pub async fn check_for_updates() -> Result<Option<(String, String)>> {
+fn download(url: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    AGENT
+        .get(url)
+        .call()?
+        .into_reader()
+        .read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+fn decode_line(line: Option<&str>, len: usize) -> Result<Vec<u8>> {
+    let bytes = STANDARD.decode(line.ok_or_else(|| anyhow!("Truncated signature"))?.trim())?;
+
+    if bytes.len() != len {
+        bail!("Malformed signature");
+    }
+
+    Ok(bytes)
+}
+
+// checks the signed trusted comment of a minisign signature and returns it,
+// together with the signature of the data
+fn verify_trusted_comment(public_key: &str, signature: &str) -> Result<(Vec<u8>, String)> {
+    // algorithm, key id, ed25519 key
+    let public_key = decode_line(Some(public_key), 42)?;
+
+    let mut lines = signature.lines().skip(1);
+
+    // algorithm, key id, ed25519 signature
+    let signature = decode_line(lines.next(), 74)?;
+
+    if &signature[..2] != b"Ed" {
+        bail!("Unsupported signature algorithm");
+    }
+
+    if signature[2..10] != public_key[2..10] {
+        bail!("The update was signed with a different key");
+    }
+
+    // the trusted comment is signed too, together with the signature
+    let trusted_comment = lines
+        .next()
+        .and_then(|line| line.strip_prefix("trusted comment: "))
+        .ok_or_else(|| anyhow!("Missing trusted comment"))?;
+    let global_signature = decode_line(lines.next(), 64)?;
+
+    let mut message = signature[10..].to_vec();
+    message.extend_from_slice(trusted_comment.as_bytes());
+
+    UnparsedPublicKey::new(&ED25519, &public_key[10..])
+        .verify(&message, &global_signature)
+        .map_err(|_| anyhow!("Invalid trusted comment signature"))?;
+
+    Ok((signature, trusted_comment.to_string()))
+}
+
+// checks a minisign signature made with `minisign -S -l`, prehashed ones aren't supported;
+// returns the trusted comment
+pub fn verify(public_key: &str, data: &[u8], signature: &str) -> Result<String> {
+    let (signature, trusted_comment) = verify_trusted_comment(public_key, signature)?;
+
+    UnparsedPublicKey::new(&ED25519, &decode_line(Some(public_key), 42)?[10..])
+        .verify(data, &signature[10..])
+        .map_err(|_| anyhow!("Invalid signature"))?;
+
+    Ok(trusted_comment)
+}
+
+// minisign puts "file:<name>" in the trusted comment, an older release signed with
+// the same key would name an older file
+fn check_file_name(trusted_comment: &str, name: &str) -> Result<()> {
+    let expected = format!("file:{name}");
+
+    if !trusted_comment
+        .split_whitespace()
+        .any(|field| field == expected)
+    {
+        bail!("The signature doesn't belong to {}", name);
+    }
+
+    Ok(())
+}
+
+pub async fn check_for_updates() -> Result<Option<Update>> {
+    // development builds aren't signed, so they have nothing to compare against
+    let Some(public_key) = PUBLIC_KEY else {
+        tracing::info!("update check skipped, this build can't verify updates");
+        return Ok(None);
+    };
+
     let latest_release = get_latest_release().await?;
-    let latest_release = Version::from(&latest_release.tag_name).unwrap();
+    let latest_version = Version::from(&latest_release.tag_name).unwrap();
     let current_version = Version::from(env!("CARGO_PKG_VERSION")).unwrap();
 
-    if latest_release > current_version {
-        let url = format!("{}{}", RELEASES_BASE_URL, latest_release);
-        return Ok(Some((latest_release.to_string(), url)));
+    if latest_version <= current_version {
+        return Ok(None);
+    }
+
+    let Some(suffix) = ARTIFACT_SUFFIX else {
+        bail!("There are no release builds for this platform");
+    };
+
+    let name = format!("CrabLauncher-{}{}", latest_release.tag_name, suffix);
+    let find_asset = |name: &str| {
+        latest_release
+            .assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| anyhow!("{name} is missing from the release"))
+    };
+
+    let artifact = find_asset(&name)?;
+    let signature = find_asset(&format!("{name}.minisig"))?;
+
+    // only the signature for now, the artifact is downloaded when installing
+    let signature = String::from_utf8(download(&signature.browser_download_url)?)?;
+    let (_, trusted_comment) = verify_trusted_comment(public_key, &signature)?;
+    check_file_name(&trusted_comment, &name)?;
+
+    Ok(Some(Update {
+        version: latest_version.to_string(),
+        name,
+        url: artifact.browser_download_url.clone(),
+        signature,
+    }))
+}
+
+// downloads the artifact and checks it against the signature from the check
+pub async fn download_update(update: Update) -> Result<PathBuf> {
+    let Some(public_key) = PUBLIC_KEY else {
+        bail!("This build can't verify updates");
+    };
+
+    let data = download(&update.url)?;
+    let trusted_comment = verify(public_key, &data, &update.signature)?;
+    check_file_name(&trusted_comment, &update.name)?;
+
+    let path = UPDATES_DIR.join(&update.name);
+    fs::write(&path, data)?;
+
+    Ok(path)
+}
+
+// the launcher binary inside the release archive
+fn read_binary(artifact: &Path) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    if artifact.extension().is_some_and(|ext| ext == "zip") {
+        let mut zip = zip::ZipArchive::new(File::open(artifact)?)?;
+        zip.by_name("CrabLauncher.exe")?.read_to_end(&mut bytes)?;
+    } else if artifact.to_string_lossy().ends_with(".tar.gz") {
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(artifact)?));
+
+        let mut entry = tar
+            .entries()?
+            .filter_map(|entry| entry.ok())
+            .find(|entry| {
+                entry
+                    .path()
+                    .is_ok_and(|path| path.ends_with("CrabLauncher"))
+            })
+            .ok_or_else(|| anyhow!("The update doesn't contain the launcher"))?;
+
+        entry.read_to_end(&mut bytes)?;
+    } else {
+        bail!("{} can't be installed in place", artifact.display());
+    }
+
+    Ok(bytes)
+}
+
+// the running executable can't be overwritten on windows, but it can be renamed
+fn old_exe(exe: &Path) -> PathBuf {
+    exe.with_extension("old")
+}
+
+// replaces the running executable with the one from a verified update
+pub fn install(artifact: &Path) -> Result<()> {
+    let exe = env::current_exe()?;
+    let new_exe = exe.with_extension("new");
+
+    fs::write(&new_exe, read_binary(artifact)?)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new_exe, fs::Permissions::from_mode(0o755))?;
     }
 
-    Ok(None)
+    if cfg!(target_os = "windows") {
+        fs::rename(&exe, old_exe(&exe))?;
+    }
+
+    if let Err(error) = fs::rename(&new_exe, &exe) {
+        if cfg!(target_os = "windows") {
+            fs::rename(old_exe(&exe), &exe)?;
+        }
+
+        return Err(error.into());
+    }
+
+    fs::remove_file(artifact)?;
+
+    Ok(())
+}
+
+// starts the freshly installed launcher, the caller has to exit
+pub fn restart() -> Result<()> {
+    process::Command::new(env::current_exe()?).spawn()?;
+
+    Ok(())
+}
+
+// left behind by the previous update on windows
+pub fn remove_old_exe() {
+    if let Ok(exe) = env::current_exe() {
+        let _ = fs::remove_file(old_exe(&exe));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    #[test]
+    fn verifies_minisign_signatures() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let key_id = [1, 2, 3, 4, 5, 6, 7, 8];
+
+        let public_key = [&b"Ed"[..], &key_id, key_pair.public_key().as_ref()].concat();
+        let public_key = STANDARD.encode(public_key);
+
+        let data = b"CrabLauncher";
+        let signature = key_pair.sign(data);
+        let trusted_comment = "timestamp:0\tfile:CrabLauncher-1.0.0.zip";
+        let global_signature =
+            key_pair.sign(&[signature.as_ref(), trusted_comment.as_bytes()].concat());
+
+        let signature = format!(
+            "untrusted comment: test\n{}\ntrusted comment: {}\n{}\n",
+            STANDARD.encode([&b"Ed"[..], &key_id, signature.as_ref()].concat()),
+            trusted_comment,
+            STANDARD.encode(global_signature.as_ref()),
+        );
+
+        let trusted_comment = verify(&public_key, data, &signature).unwrap();
+        assert!(check_file_name(&trusted_comment, "CrabLauncher-1.0.0.zip").is_ok());
+        assert!(check_file_name(&trusted_comment, "CrabLauncher-1.1.0.zip").is_err());
+        assert!(verify(&public_key, b"CrabLauncher!", &signature).is_err());
+    }
 }
@@ -3,16 +3,23 @@
 
 use std::{
     collections::HashMap,
+    fmt,
     fs::{self, File},
-    io::BufReader,
-    path::PathBuf,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use serde::Deserialize;
+use serde_json::Value;
+use time::{macros::datetime, OffsetDateTime};
+use zip::ZipArchive;
 
-use crate::adoptium;
+use crate::cache;
+use crate::fabric;
+use crate::instances::{Instance, InstanceKind, Loader};
 use crate::paths::{ASSETS_DIR, LIBRARIES_DIR, META_DIR};
+use crate::runtime_manager::{self, Arch, JavaVendor};
 use crate::{DownloadItem, DownloadQueue, Hash, HashAlgorithm};
 
 #[cfg(target_os = "windows")]
@@ -24,6 +31,9 @@ const OS: &str = "linux";
 #[cfg(target_os = "macos")]
 const OS: &str = "osx";
 
+// forge profiles inherit from vanilla, some modpacks stack one more on top
+const MAX_INHERITANCE_DEPTH: usize = 5;
+
 #[cfg(target_os = "windows")]
 const SEPARATOR: char = ';';
 
@@ -35,34 +45,113 @@ struct VersionManifest {
     versions: Vec<Version>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseType {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+    // types added to the manifest later, never listed
+    #[serde(other)]
+    Other,
+}
+
+impl ReleaseType {
+    pub const ALL: [ReleaseType; 4] = [
+        ReleaseType::Release,
+        ReleaseType::Snapshot,
+        ReleaseType::OldBeta,
+        ReleaseType::OldAlpha,
+    ];
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseType::Release => write!(f, "Releases"),
+            ReleaseType::Snapshot => write!(f, "Snapshots"),
+            ReleaseType::OldBeta => write!(f, "Old betas"),
+            ReleaseType::OldAlpha => write!(f, "Old alphas"),
+            ReleaseType::Other => write!(f, "Other"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Version {
-    id: String,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub release_type: ReleaseType,
     url: String,
     sha1: String,
 }
 
-pub async fn get_versions() -> Result<Vec<String>> {
-    let resp = DownloadItem {
-        url: "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json".to_string(),
-        path: META_DIR.join("version_manifest_v2.json.new"),
-        hash: None,
-        extract: false,
-    }
-    .download_json::<VersionManifest>()?;
-
-    fs::rename(
-        META_DIR.join("version_manifest_v2.json.new"),
-        META_DIR.join("version_manifest_v2.json"),
+// newest first
+pub async fn get_versions() -> Result<Vec<Version>> {
+    let contents = cache::fetch(
+        "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
+        &META_DIR.join("version_manifest_v2.json"),
     )?;
+    let manifest = serde_json::from_str::<VersionManifest>(&contents)?;
 
-    let versions = resp
-        .versions
+    Ok(manifest.versions)
+}
+
+// instances on an older release can be updated to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatestRelease {
+    pub id: String,
+    older: Vec<String>,
+}
+
+impl LatestRelease {
+    pub fn is_newer_than(&self, id: &str) -> bool {
+        self.older.iter().any(|older| older == id)
+    }
+}
+
+pub async fn get_latest_release() -> Result<LatestRelease> {
+    let mut releases = get_versions()
+        .await?
         .into_iter()
-        .map(|v| v.id)
-        .collect::<Vec<String>>();
+        .filter(|version| version.release_type == ReleaseType::Release)
+        .map(|version| version.id);
+
+    let id = releases
+        .next()
+        .ok_or_else(|| anyhow!("There are no releases in the version manifest"))?;
+
+    Ok(LatestRelease {
+        id,
+        older: releases.collect(),
+    })
+}
 
-    Ok(versions)
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PatchNotes {
+    pub title: String,
+    pub version: String,
+    // rfc3339
+    pub date: String,
+    #[serde(rename = "shortText")]
+    pub short_text: String,
+}
+
+#[derive(Deserialize)]
+struct PatchNotesList {
+    entries: Vec<PatchNotes>,
+}
+
+// old versions have none
+pub async fn get_patch_notes(id: String) -> Result<Option<PatchNotes>> {
+    let contents = cache::fetch(
+        "https://launchercontent.mojang.com/v2/javaPatchNotes.json",
+        &META_DIR.join("java_patch_notes.json"),
+    )?;
+    let list = serde_json::from_str::<PatchNotesList>(&contents)?;
+
+    Ok(list.entries.into_iter().find(|notes| notes.version == id))
 }
 
 #[derive(Deserialize)]
@@ -77,45 +166,186 @@ struct Artifact {
     url: String,
     path: String,
     sha1: String,
+    size: u64,
 }
 
 #[derive(Deserialize)]
 struct LibraryDownloads {
-    artifact: Artifact,
+    // missing for natives-only libraries
+    artifact: Option<Artifact>,
+    // before 1.19 natives were classifiers of the library, see Library::natives
+    classifiers: Option<HashMap<String, Artifact>>,
+}
+
+#[derive(Deserialize)]
+struct Extract {
+    // prefixes of the entries to leave out, e.g. META-INF/
+    exclude: Vec<String>,
 }
 
 #[derive(Deserialize)]
 struct Os {
-    name: String,
+    name: Option<String>,
+    // only ever x86 in the argument rules
+    arch: Option<String>,
 }
 
 #[derive(Deserialize)]
 struct Rule {
     action: String,
-    os: Os,
+    // applies everywhere if missing
+    os: Option<Os>,
+    // e.g. has_custom_resolution, the launcher adds those arguments itself
+    features: Option<HashMap<String, bool>>,
+}
+
+impl Rule {
+    fn applies(&self, arch: Arch) -> bool {
+        self.features.is_none()
+            && self.os.as_ref().is_none_or(|os| {
+                os.name.as_deref().is_none_or(|name| name == OS)
+                    && os
+                        .arch
+                        .as_deref()
+                        .is_none_or(|os_arch| os_arch == "x86" && arch == Arch::X86)
+            })
+    }
+}
+
+// the last matching rule wins
+fn check_rules(rules: &[Rule], arch: Arch) -> bool {
+    rules
+        .iter()
+        .rev()
+        .find(|rule| rule.applies(arch))
+        .is_some_and(|rule| rule.action == "allow")
 }
 
 #[derive(Deserialize)]
 struct Library {
-    downloads: LibraryDownloads,
+    // maven coordinates, e.g. org.lwjgl:lwjgl:3.3.1
+    name: String,
+    // modded metas often leave it out, see get_download_item
+    downloads: Option<LibraryDownloads>,
+    // the maven repository for the ones without downloads
+    url: Option<String>,
     rules: Option<Vec<Rule>>,
+    // the classifier for each os, e.g. natives-windows-${arch}
+    natives: Option<HashMap<String, String>>,
+    extract: Option<Extract>,
+}
+
+// where libraries without a repository come from
+const DEFAULT_MAVEN: &str = "https://libraries.minecraft.net/";
+
+// group:artifact:version[:classifier][@extension], relative to the repository
+fn get_maven_path(name: &str) -> Option<String> {
+    let (name, extension) = name.split_once('@').unwrap_or((name, "jar"));
+
+    let (group, artifact, version, classifier) = match name.split(':').collect::<Vec<_>>()[..] {
+        [group, artifact, version] => (group, artifact, version, None),
+        [group, artifact, version, classifier] => (group, artifact, version, Some(classifier)),
+        _ => return None,
+    };
+
+    let file_name = match classifier {
+        Some(classifier) => format!("{artifact}-{version}-{classifier}.{extension}"),
+        None => format!("{artifact}-{version}.{extension}"),
+    };
+
+    Some(format!(
+        "{}/{}/{}/{}",
+        group.replace('.', "/"),
+        artifact,
+        version,
+        file_name
+    ))
+}
+
+// what a jar with native code is built for, from the end of its name
+fn get_natives_arch(path: &str) -> Option<Arch> {
+    let name = path.trim_end_matches(".jar");
+
+    if name.ends_with("x86_64") || name.ends_with("x64") {
+        Some(Arch::X64)
+    } else if name.ends_with("aarch_64") || name.ends_with("arm64") {
+        Some(Arch::Aarch64)
+    } else if name.ends_with("x86") {
+        Some(Arch::X86)
+    } else if name.ends_with("arm32") {
+        Some(Arch::Arm)
+    } else if name.contains("-natives-") {
+        // the ones without a suffix are for x86_64
+        Some(Arch::X64)
+    } else {
+        None
+    }
 }
 
 impl Library {
-    pub fn check(&self) -> bool {
+    fn get_artifact(&self) -> Option<&Artifact> {
+        self.downloads.as_ref()?.artifact.as_ref()
+    }
+
+    // relative to the libraries folder, none for natives-only libraries
+    fn get_path(&self) -> Option<String> {
+        match &self.downloads {
+            Some(_) => self.get_artifact().map(|artifact| artifact.path.clone()),
+            None => get_maven_path(&self.name),
+        }
+    }
+
+    // none for the ones an installer generates locally, they have no url
+    fn get_download_item(&self) -> Option<DownloadItem> {
+        if let Some(artifact) = self.get_artifact() {
+            if artifact.url.is_empty() {
+                return None;
+            }
+
+            return Some(DownloadItem {
+                url: artifact.url.clone(),
+                path: LIBRARIES_DIR.join(&artifact.path),
+                hash: Some(Hash {
+                    hash: artifact.sha1.clone(),
+                    function: HashAlgorithm::Sha1,
+                }),
+                size: Some(artifact.size),
+                extract: false,
+            });
+        }
+
+        // the checksum is unknown, the repository is trusted
+        let path = self.get_path()?;
+        let repository = self.url.as_deref().unwrap_or(DEFAULT_MAVEN);
+
+        Some(DownloadItem {
+            url: format!("{}/{}", repository.trim_end_matches('/'), path),
+            path: LIBRARIES_DIR.join(path),
+            hash: None,
+            size: None,
+            extract: false,
+        })
+    }
+
+    fn check_os(&self) -> bool {
         let mut yes = true;
 
         if let Some(rules) = &self.rules {
             yes = false;
 
+            // the last matching rule wins
             for rule in rules {
-                if rule.action == "allow" && rule.os.name == OS {
-                    yes = true;
+                if rule
+                    .os
+                    .as_ref()
+                    .is_none_or(|os| os.name.as_deref().is_none_or(|name| name == OS))
+                {
+                    yes = rule.action == "allow";
                 }
             }
         }
 
-        let path = &self.downloads.artifact.path;
+        let path = self.get_path().unwrap_or_default();
 
         if path.contains("linux") && cfg!(not(target_os = "linux")) {
             yes = false;
@@ -125,15 +355,43 @@ impl Library {
             yes = false;
         }
 
-        if path.contains("x86") && cfg!(not(target_arch = "x86_64")) {
-            yes = false;
-        } else if (path.contains("aarch_64") || path.contains("arm64"))
-            && cfg!(not(target_arch = "aarch64"))
-        {
-            yes = false;
+        yes
+    }
+
+    fn get_natives_arch(&self) -> Option<Arch> {
+        get_natives_arch(&self.get_path()?)
+    }
+
+    // the jar with this os' natives, in the pre-1.19 format
+    fn get_natives_artifact(&self, arch: Arch) -> Option<&Artifact> {
+        let classifier = self.natives.as_ref()?.get(OS)?;
+        let bits = match arch {
+            Arch::X64 | Arch::Aarch64 => "64",
+            Arch::X86 | Arch::Arm => "32",
+        };
+
+        self.downloads
+            .as_ref()?
+            .classifiers
+            .as_ref()?
+            .get(&classifier.replace("${arch}", bits))
+    }
+
+    // classifiers were only ever built for x86
+    fn get_classifier_archs(&self) -> Vec<Arch> {
+        match self.natives.as_ref().and_then(|natives| natives.get(OS)) {
+            Some(classifier) if classifier.contains("${arch}") => vec![Arch::X64, Arch::X86],
+            Some(_) => vec![Arch::X64],
+            None => Vec::new(),
         }
+    }
 
-        yes
+    // arch is what the game runs as, see VersionMeta::get_arch
+    pub fn check(&self, arch: Arch) -> bool {
+        self.check_os()
+            && self
+                .get_natives_arch()
+                .is_none_or(|natives| natives == arch)
     }
 }
 
@@ -141,11 +399,14 @@ impl Library {
 struct ClientArtifact {
     sha1: String,
     url: String,
+    size: u64,
 }
 
 #[derive(Deserialize)]
 struct VersionDownloads {
     client: ClientArtifact,
+    // missing in the oldest versions
+    server: Option<ClientArtifact>,
 }
 
 #[derive(Deserialize)]
@@ -158,35 +419,423 @@ pub struct VersionMeta {
     pub main_class: String,
     pub assets: String,
     downloads: VersionDownloads,
+    // missing from some hand-made or converted version files
+    #[serde(rename = "releaseTime", default, with = "time::serde::rfc3339::option")]
+    release_time: Option<OffsetDateTime>,
+    #[serde(rename = "type")]
+    release_type: Option<String>,
+    // before 1.13 the game arguments were a single string
+    #[serde(rename = "minecraftArguments")]
+    minecraft_arguments: Option<String>,
+    // since 1.13, also added to by the loader profiles
+    arguments: Option<Arguments>,
+    // the version whose client is used, set for the ones inheriting from it
+    jar: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgumentValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Argument {
+    Plain(String),
+    Conditional {
+        rules: Vec<Rule>,
+        value: ArgumentValue,
+    },
+}
+
+#[derive(Deserialize)]
+struct Arguments {
+    #[serde(default)]
+    game: Vec<Argument>,
+    #[serde(default)]
+    jvm: Vec<Argument>,
+}
+
+// for the metas that have no arguments at all
+const GAME_ARGUMENTS: &str = "--username ${auth_player_name} --version ${version_name} --gameDir ${game_directory} --assetsDir ${assets_root} --assetIndex ${assets_index_name} --uuid ${auth_uuid} --accessToken ${auth_access_token} --clientId ${clientid} --userType ${user_type} --versionType ${version_type}";
+
+fn fill_argument(arg: &str, values: &HashMap<&str, String>) -> String {
+    values.iter().fold(arg.to_string(), |arg, (key, value)| {
+        arg.replace(&format!("${{{}}}", key), value)
+    })
+}
+
+// the template is split first, so values with spaces stay a single argument
+fn fill_arguments(template: &str, values: &HashMap<&str, String>) -> Vec<String> {
+    template
+        .split_whitespace()
+        .map(|arg| fill_argument(arg, values))
+        .collect()
+}
+
+// keeps the arguments whose rules apply, e.g. -XstartOnFirstThread only on macos
+fn resolve_arguments(
+    arguments: &[Argument],
+    arch: Arch,
+    values: &HashMap<&str, String>,
+) -> Vec<String> {
+    arguments
+        .iter()
+        .flat_map(|argument| match argument {
+            Argument::Plain(arg) => vec![arg.as_str()],
+            Argument::Conditional { rules, value } if check_rules(rules, arch) => match value {
+                ArgumentValue::One(arg) => vec![arg.as_str()],
+                ArgumentValue::Many(args) => args.iter().map(String::as_str).collect(),
+            },
+            Argument::Conditional { .. } => Vec::new(),
+        })
+        .map(|arg| fill_argument(arg, values))
+        .collect()
+}
+
+// profiles made by other installers are only the differences from their parent
+fn read_meta(id: &str, depth: usize) -> Result<Value> {
+    if depth > MAX_INHERITANCE_DEPTH {
+        bail!("{} inherits from too many versions", id);
+    }
+
+    let path = META_DIR.join("versions").join(format!("{}.json", id));
+    let reader = BufReader::new(File::open(path)?);
+    let meta = serde_json::from_reader::<_, Value>(reader)?;
+
+    match meta.get("inheritsFrom").and_then(Value::as_str) {
+        Some(parent) => Ok(merge_meta(read_meta(parent, depth + 1)?, meta)),
+        None => Ok(meta),
+    }
+}
+
+// a library replaces the parent's one with the same group, artifact and classifier
+fn get_library_key(library: &Value) -> Option<String> {
+    let parts = library
+        .get("name")?
+        .as_str()?
+        .split(':')
+        .collect::<Vec<_>>();
+
+    Some(match parts.as_slice() {
+        [group, artifact, _, classifier, ..] => format!("{group}:{artifact}:{classifier}"),
+        [group, artifact, ..] => format!("{group}:{artifact}"),
+        _ => return None,
+    })
+}
+
+// the child wins, except for the libraries and arguments, which are combined
+fn merge_meta(parent: Value, child: Value) -> Value {
+    let (Value::Object(mut merged), Value::Object(child)) = (parent, child) else {
+        return Value::Null;
+    };
+
+    // the client jar stays the parent's one
+    if !merged.contains_key("jar") {
+        if let Some(id) = merged.get("id").cloned() {
+            merged.insert("jar".to_string(), id);
+        }
+    }
+
+    for (key, value) in child {
+        match (key.as_str(), merged.get_mut(&key), value) {
+            ("libraries", Some(Value::Array(libraries)), Value::Array(mut child_libraries)) => {
+                let keys = child_libraries
+                    .iter()
+                    .filter_map(get_library_key)
+                    .collect::<Vec<_>>();
+
+                child_libraries.extend(libraries.drain(..).filter(|library| {
+                    get_library_key(library).is_none_or(|key| !keys.contains(&key))
+                }));
+                *libraries = child_libraries;
+            }
+            ("arguments", Some(Value::Object(arguments)), Value::Object(child_arguments)) => {
+                for (kind, value) in child_arguments {
+                    match (arguments.get_mut(&kind), value) {
+                        (Some(Value::Array(list)), Value::Array(child_list)) => {
+                            list.extend(child_list)
+                        }
+                        (_, value) => {
+                            arguments.insert(kind, value);
+                        }
+                    }
+                }
+            }
+            // it's been resolved
+            ("inheritsFrom", _, _) => {}
+            (_, _, value) => {
+                merged.insert(key, value);
+            }
+        }
+    }
+
+    Value::Object(merged)
 }
 
 impl VersionMeta {
     pub fn load(id: &str) -> Result<Self> {
-        let path = META_DIR.join("versions").join(format!("{}.json", id));
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let version_meta = serde_json::from_reader(reader)?;
+        let version_meta = serde_json::from_value(read_meta(id, 0)?)?;
 
         Ok(version_meta)
     }
 
+    // before 1.19 there are no arm64 natives for macos, these versions run
+    // on an x86_64 runtime through rosetta
+    pub fn get_arch(&self) -> Arch {
+        let natives = self.get_natives_archs();
+
+        if cfg!(target_os = "macos")
+            && Arch::HOST == Arch::Aarch64
+            && !natives.is_empty()
+            && !natives.contains(&Arch::Aarch64)
+        {
+            Arch::X64
+        } else {
+            Arch::HOST
+        }
+    }
+
+    // the architectures this version has natives for, on this os
+    fn get_natives_archs(&self) -> Vec<Arch> {
+        self.libraries
+            .iter()
+            .filter(|library| library.check_os())
+            .flat_map(|library| {
+                let mut archs = library.get_classifier_archs();
+                archs.extend(library.get_natives_arch());
+                archs
+            })
+            .collect()
+    }
+
+    // unpacks the pre-1.19 natives for -Djava.library.path, false if there are none
+    pub fn extract_natives(&self, dir: &Path, arch: Arch) -> Result<bool> {
+        let libraries = self
+            .libraries
+            .iter()
+            .filter(|library| library.check_os())
+            .filter_map(|library| Some((library, library.get_natives_artifact(arch)?)))
+            .collect::<Vec<_>>();
+
+        if libraries.is_empty() {
+            return Ok(false);
+        }
+
+        // natives of the version played before would be loaded too
+        if dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        fs::create_dir_all(dir)?;
+
+        for (library, artifact) in libraries {
+            let exclude = library
+                .extract
+                .as_ref()
+                .map(|extract| extract.exclude.as_slice())
+                .unwrap_or_default();
+
+            let mut zip = ZipArchive::new(File::open(LIBRARIES_DIR.join(&artifact.path))?)?;
+
+            for i in 0..zip.len() {
+                let mut entry = zip.by_index(i)?;
+
+                let Some(name) = entry.enclosed_name().map(Path::to_path_buf) else {
+                    continue;
+                };
+
+                if entry.is_dir()
+                    || exclude
+                        .iter()
+                        .any(|prefix| entry.name().starts_with(prefix))
+                {
+                    continue;
+                }
+
+                let dest = dir.join(name);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+
+                io::copy(&mut entry, &mut File::create(dest)?)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    // rather than downloading natives the game can't load
+    pub fn check_natives(&self, arch: Arch) -> Result<()> {
+        let natives = self.get_natives_archs();
+
+        if !natives.is_empty() && !natives.contains(&arch) {
+            bail!("Minecraft {} doesn't run on {} {}", self.id, OS, arch);
+        }
+
+        Ok(())
+    }
+
+    // e.g. old_alpha, shown in the title screen of some versions
+    pub fn get_release_type(&self) -> &str {
+        self.release_type.as_deref().unwrap_or("release")
+    }
+
+    // loader profiles for old versions can add to the legacy string, e.g. --tweakClass
+    pub fn get_game_args(&self, values: &HashMap<&str, String>) -> Vec<String> {
+        let arguments = self
+            .arguments
+            .as_ref()
+            .map(|a| a.game.as_slice())
+            .unwrap_or_default();
+
+        let mut game_args = match &self.minecraft_arguments {
+            Some(template) => fill_arguments(template, values),
+            None if arguments.is_empty() => fill_arguments(GAME_ARGUMENTS, values),
+            None => Vec::new(),
+        };
+        game_args.extend(resolve_arguments(arguments, self.get_arch(), values));
+
+        game_args
+    }
+
+    // None before 1.13, the launcher passes its own
+    pub fn get_jvm_args(&self, values: &HashMap<&str, String>) -> Option<Vec<String>> {
+        let arguments = self.arguments.as_ref().filter(|a| !a.jvm.is_empty())?;
+
+        let mut values = values.clone();
+        values.insert(
+            "library_directory",
+            LIBRARIES_DIR.to_string_lossy().to_string(),
+        );
+        values.insert("classpath_separator", SEPARATOR.to_string());
+
+        Some(resolve_arguments(&arguments.jvm, self.get_arch(), &values))
+    }
+
+    // --quickPlaySingleplayer was added in 23w14a, a 1.20 snapshot; without a
+    // release time it's passed anyway, older versions ignore unknown arguments
+    pub fn supports_quick_play(&self) -> bool {
+        self.release_time
+            .is_none_or(|release_time| release_time >= datetime!(2023-04-05 00:00 UTC))
+    }
+
     fn get_client_path(&self) -> PathBuf {
+        let id = self.jar.as_deref().unwrap_or(&self.id);
+
+        LIBRARIES_DIR
+            .join("com")
+            .join("mojang")
+            .join("minecraft")
+            .join(id)
+            .join(format!("minecraft-{}-client.jar", id))
+    }
+
+    pub fn get_server_path(&self) -> PathBuf {
         LIBRARIES_DIR
             .join("com")
             .join("mojang")
             .join("minecraft")
             .join(self.id.as_str())
-            .join(format!("minecraft-{}-client.jar", self.id))
+            .join(format!("minecraft-{}-server.jar", self.id))
     }
 
-    pub fn get_classpath(&self) -> Result<String> {
-        let mut paths = vec![self.get_client_path()];
+    // everything in the shared store this version uses
+    pub fn get_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.get_client_path()];
 
         for library in &self.libraries {
-            if library.check() {
-                let path = LIBRARIES_DIR.join(&library.downloads.artifact.path);
+            if let Some(path) = library.get_path() {
+                files.push(LIBRARIES_DIR.join(path));
+            }
+
+            for artifact in library
+                .downloads
+                .iter()
+                .filter_map(|downloads| downloads.classifiers.as_ref())
+                .flat_map(HashMap::values)
+            {
+                files.push(LIBRARIES_DIR.join(&artifact.path));
+            }
+        }
+
+        let path = ASSETS_DIR
+            .join("indexes")
+            .join(format!("{}.json", self.asset_index.id));
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(path)?);
+            let asset_index = serde_json::from_reader::<_, AssetIndex>(reader)?;
+
+            for object in asset_index.objects.into_values() {
+                let hash = Hash {
+                    hash: object.hash,
+                    function: HashAlgorithm::Sha1,
+                };
+
+                files.push(ASSETS_DIR.join("objects").join(hash.get_path()));
+            }
+        }
+
+        Ok(files)
+    }
 
-                paths.push(path);
+    // the directory to pass as --assetsDir
+    pub fn get_assets_dir(&self, instance_dir: &Path) -> Result<PathBuf> {
+        let path = ASSETS_DIR
+            .join("indexes")
+            .join(format!("{}.json", self.asset_index.id));
+        let reader = BufReader::new(File::open(path)?);
+        let asset_index = serde_json::from_reader::<_, AssetIndex>(reader)?;
+
+        if asset_index.map_to_resources {
+            let dir = instance_dir.join("resources");
+            asset_index.reconstruct(&dir)?;
+
+            Ok(dir)
+        } else if asset_index.is_virtual {
+            let dir = ASSETS_DIR.join("virtual").join(&self.asset_index.id);
+            asset_index.reconstruct(&dir)?;
+
+            Ok(dir)
+        } else {
+            Ok(ASSETS_DIR.to_path_buf())
+        }
+    }
+
+    // the libraries on this platform's classpath, by maven coordinates
+    pub fn get_libraries(&self) -> Vec<(String, PathBuf)> {
+        let arch = self.get_arch();
+
+        self.libraries
+            .iter()
+            .filter(|library| library.check(arch))
+            .filter_map(|library| {
+                Some((
+                    library.name.clone(),
+                    LIBRARIES_DIR.join(library.get_path()?),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn get_client(&self) -> (String, PathBuf) {
+        (
+            format!("com.mojang:minecraft:{}:client", self.id),
+            self.get_client_path(),
+        )
+    }
+
+    // extra paths, e.g. from a mod loader, come first
+    pub fn get_classpath(&self, extra: &[PathBuf]) -> Result<String> {
+        let mut paths = extra.to_vec();
+        paths.push(self.get_client_path());
+
+        let arch = self.get_arch();
+        for library in &self.libraries {
+            if let Some(path) = library.get_path().filter(|_| library.check(arch)) {
+                paths.push(LIBRARIES_DIR.join(path));
             }
         }
 
@@ -203,14 +852,64 @@ impl VersionMeta {
 #[derive(Deserialize)]
 struct Object {
     hash: String,
+    size: u64,
 }
 
 #[derive(Deserialize)]
 struct AssetIndex {
     objects: HashMap<String, Object>,
+    // legacy layout, assets are looked up by name
+    #[serde(default, rename = "virtual")]
+    is_virtual: bool,
+    // pre-1.6 layout, assets are read from the instance's resources folder
+    #[serde(default)]
+    map_to_resources: bool,
 }
 
-pub fn download_version(id: &str) -> Result<DownloadQueue> {
+impl AssetIndex {
+    // copy the objects to where old versions expect them, by name
+    fn reconstruct(&self, dir: &Path) -> Result<()> {
+        for (name, object) in &self.objects {
+            let dest = dir.join(name);
+            if dest.exists() {
+                continue;
+            }
+
+            let hash = Hash {
+                hash: object.hash.clone(),
+                function: HashAlgorithm::Sha1,
+            };
+            let src = ASSETS_DIR.join("objects").join(hash.get_path());
+            if !src.exists() {
+                bail!("Missing asset: {}", name);
+            }
+
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            // hard links save space, but don't work across filesystems
+            if fs::hard_link(&src, &dest).is_err() {
+                fs::copy(&src, &dest)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn download_meta(id: &str) -> Result<VersionMeta> {
+    // profiles from other installers aren't in the manifest, but their parent is
+    let path = META_DIR.join("versions").join(format!("{}.json", id));
+    if let Ok(contents) = fs::read_to_string(path) {
+        let meta = serde_json::from_str::<Value>(&contents)?;
+
+        if let Some(parent) = meta.get("inheritsFrom").and_then(Value::as_str) {
+            download_meta(parent)?;
+            return VersionMeta::load(id);
+        }
+    }
+
     let version_manifest = {
         let path = META_DIR.join("version_manifest_v2.json");
         let contents = fs::read_to_string(path)?;
@@ -221,7 +920,7 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
         .versions
         .into_iter()
         .find(|v| v.id == id)
-        .unwrap();
+        .ok_or_else(|| anyhow!("Version {} not found", id))?;
 
     // download version meta
     let version_meta = DownloadItem {
@@ -231,10 +930,45 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
             hash: version.sha1,
             function: HashAlgorithm::Sha1,
         }),
+        size: None,
         extract: false,
     }
     .download_json::<VersionMeta>()?;
 
+    Ok(version_meta)
+}
+
+// servers only need their jar and a runtime
+pub fn download_server(id: &str, vendor: JavaVendor) -> Result<DownloadQueue> {
+    let version_meta = download_meta(id)?;
+    let path = version_meta.get_server_path();
+
+    let server = version_meta
+        .downloads
+        .server
+        .ok_or_else(|| anyhow!("There is no server for {}", id))?;
+
+    let mut download_items = vec![DownloadItem {
+        url: server.url,
+        path,
+        hash: Some(Hash {
+            hash: server.sha1,
+            function: HashAlgorithm::Sha1,
+        }),
+        size: Some(server.size),
+        extract: false,
+    }];
+
+    download_items.extend_from_slice(&runtime_manager::install("17", vendor, Arch::HOST)?);
+
+    Ok(DownloadQueue::new(download_items))
+}
+
+pub fn download_version(id: &str, vendor: JavaVendor) -> Result<DownloadQueue> {
+    let version_meta = download_meta(id)?;
+    let arch = version_meta.get_arch();
+    version_meta.check_natives(arch)?;
+
     let mut download_items = vec![];
 
     // download client
@@ -245,10 +979,11 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
             hash: version_meta.downloads.client.sha1,
             function: HashAlgorithm::Sha1,
         }),
+        size: Some(version_meta.downloads.client.size),
         extract: false,
     });
 
-    download_items.extend_from_slice(&adoptium::install("17")?);
+    download_items.extend_from_slice(&runtime_manager::install("17", vendor, arch)?);
 
     let asset_index = DownloadItem {
         url: version_meta.asset_index.url,
@@ -259,6 +994,7 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
             hash: version_meta.asset_index.sha1,
             function: HashAlgorithm::Sha1,
         }),
+        size: None,
         extract: false,
     }
     .download_json::<AssetIndex>()?;
@@ -278,23 +1014,29 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
             ),
             path,
             hash: Some(hash),
+            size: Some(value.size),
             extract: false,
         });
     }
 
     for library in version_meta.libraries {
-        if library.check() {
-            let hash = Hash {
-                hash: library.downloads.artifact.sha1,
-                function: HashAlgorithm::Sha1,
-            };
+        if let Some(item) = library.get_download_item().filter(|_| library.check(arch)) {
+            download_items.push(item);
+        }
 
-            let path = LIBRARIES_DIR.join(library.downloads.artifact.path);
+        let natives = library
+            .get_natives_artifact(arch)
+            .filter(|_| library.check_os());
 
+        if let Some(artifact) = natives {
             download_items.push(DownloadItem {
-                url: library.downloads.artifact.url,
-                path,
-                hash: Some(hash),
+                url: artifact.url.clone(),
+                path: LIBRARIES_DIR.join(&artifact.path),
+                hash: Some(Hash {
+                    hash: artifact.sha1.clone(),
+                    function: HashAlgorithm::Sha1,
+                }),
+                size: Some(artifact.size),
                 extract: false,
             });
         }
@@ -302,3 +1044,279 @@ pub fn download_version(id: &str) -> Result<DownloadQueue> {
 
     Ok(DownloadQueue::new(download_items))
 }
+
+pub async fn verify_version(id: String, vendor: JavaVendor) -> Result<DownloadQueue> {
+    download_version(&id, vendor)?.verify()
+}
+
+pub async fn verify_server(id: String, vendor: JavaVendor) -> Result<DownloadQueue> {
+    download_server(&id, vendor)?.verify()
+}
+
+pub async fn predownload_versions(ids: Vec<String>, vendor: JavaVendor) -> Result<DownloadQueue> {
+    // make sure the manifest is fresh
+    get_versions().await?;
+
+    let mut queue = DownloadQueue::new(Vec::new());
+
+    for id in ids {
+        queue.append(download_version(&id, vendor)?);
+    }
+
+    Ok(queue)
+}
+
+// everything an instance needs to start, its loader included
+pub async fn download_instance(info: Instance, vendor: JavaVendor) -> Result<DownloadQueue> {
+    if info.kind == InstanceKind::Server {
+        return download_server(&info.minecraft, vendor);
+    }
+
+    let mut queue = download_version(&info.minecraft, vendor)?;
+
+    if let (Loader::Fabric, Some(loader_version)) = (info.loader, &info.loader_version) {
+        queue.append(DownloadQueue::new(fabric::install(
+            &info.minecraft,
+            loader_version,
+        )?));
+    }
+
+    Ok(queue)
+}
+
+// the missing or corrupted files of download_instance
+pub async fn verify_instance(info: Instance, vendor: JavaVendor) -> Result<DownloadQueue> {
+    download_instance(info, vendor).await?.verify()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // natives are only allowed on their os
+    fn library(path: &str, natives: bool) -> Library {
+        let mut library = serde_json::json!({
+            "name": "org.lwjgl:lwjgl:3.3.1",
+            "downloads": {
+                "artifact": { "url": "", "path": path, "sha1": "", "size": 0 }
+            },
+        });
+
+        if natives {
+            library["rules"] = serde_json::json!([{ "action": "allow", "os": { "name": "osx" } }]);
+        }
+
+        serde_json::from_value(library).unwrap()
+    }
+
+    #[test]
+    fn picks_natives_for_the_arch() {
+        let x64 = library("org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives-macos.jar", true);
+        let arm64 = library(
+            "org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives-macos-arm64.jar",
+            true,
+        );
+        let any = library("org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1.jar", false);
+
+        assert_eq!(cfg!(target_os = "macos"), x64.check(Arch::X64));
+        assert!(!x64.check(Arch::Aarch64));
+        assert!(!arm64.check(Arch::X64));
+        assert_eq!(cfg!(target_os = "macos"), arm64.check(Arch::Aarch64));
+        assert!(any.check(Arch::X64));
+        assert!(any.check(Arch::Aarch64));
+    }
+
+    #[test]
+    fn fills_legacy_arguments() {
+        let values = HashMap::from([
+            ("auth_player_name", "Steve".to_string()),
+            ("auth_session", "token:abc:123".to_string()),
+            ("game_directory", "my game".to_string()),
+        ]);
+
+        assert_eq!(
+            fill_arguments(
+                "${auth_player_name} ${auth_session} --gameDir ${game_directory} --unknown ${other}",
+                &values
+            ),
+            [
+                "Steve",
+                "token:abc:123",
+                "--gameDir",
+                "my game",
+                "--unknown",
+                "${other}"
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_inherited_metas() {
+        let parent = serde_json::json!({
+            "id": "1.12.2",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [
+                { "name": "com.google.guava:guava:17.0" },
+                { "name": "org.lwjgl.lwjgl:lwjgl:2.9.4" },
+            ],
+            "arguments": { "game": ["--username"], "jvm": ["-cp"] },
+        });
+        let child = serde_json::json!({
+            "id": "1.12.2-forge",
+            "inheritsFrom": "1.12.2",
+            "mainClass": "net.minecraft.launchwrapper.Launch",
+            "libraries": [
+                { "name": "net.minecraftforge:forge:14.23.5" },
+                { "name": "com.google.guava:guava:21.0" },
+            ],
+            "arguments": { "game": ["--tweakClass"] },
+        });
+
+        let merged = merge_meta(parent, child);
+
+        assert_eq!(merged["id"], "1.12.2-forge");
+        assert_eq!(merged["jar"], "1.12.2");
+        assert_eq!(merged["mainClass"], "net.minecraft.launchwrapper.Launch");
+        assert_eq!(merged.get("inheritsFrom"), None);
+        assert_eq!(
+            merged["libraries"],
+            serde_json::json!([
+                { "name": "net.minecraftforge:forge:14.23.5" },
+                { "name": "com.google.guava:guava:21.0" },
+                { "name": "org.lwjgl.lwjgl:lwjgl:2.9.4" },
+            ])
+        );
+        assert_eq!(
+            merged["arguments"],
+            serde_json::json!({ "game": ["--username", "--tweakClass"], "jvm": ["-cp"] })
+        );
+    }
+
+    #[test]
+    fn resolves_modern_arguments() {
+        let meta = serde_json::from_value::<VersionMeta>(serde_json::json!({
+            "id": "1.20.1-forge",
+            "assetIndex": { "id": "5", "sha1": "", "url": "" },
+            "libraries": [],
+            "mainClass": "cpw.mods.bootstraplauncher.BootstrapLauncher",
+            "assets": "5",
+            "downloads": { "client": { "sha1": "", "url": "", "size": 0 } },
+            "arguments": {
+                "game": [
+                    "--username",
+                    "${auth_player_name}",
+                    {
+                        "rules": [{ "action": "allow", "features": { "is_demo_user": true } }],
+                        "value": "--demo",
+                    },
+                    "--launchTarget",
+                    "forgeclient",
+                ],
+                "jvm": [
+                    {
+                        "rules": [{ "action": "allow", "os": { "name": OS } }],
+                        "value": ["-Dos.name=Some OS", "-Dos.version=1"],
+                    },
+                    {
+                        "rules": [{ "action": "allow", "os": { "name": "elsewhere" } }],
+                        "value": "-XstartOnFirstThread",
+                    },
+                    "-cp",
+                    "${classpath}",
+                ],
+            },
+        }))
+        .unwrap();
+
+        let values = HashMap::from([
+            ("auth_player_name", "Steve".to_string()),
+            ("classpath", "a.jar".to_string()),
+        ]);
+
+        assert_eq!(
+            meta.get_game_args(&values),
+            ["--username", "Steve", "--launchTarget", "forgeclient"]
+        );
+        assert_eq!(
+            meta.get_jvm_args(&values).unwrap(),
+            ["-Dos.name=Some OS", "-Dos.version=1", "-cp", "a.jar"]
+        );
+    }
+
+    #[test]
+    fn picks_the_natives_classifier() {
+        let artifact =
+            |name: &str| serde_json::json!({ "url": "", "path": name, "sha1": "", "size": 0 });
+
+        let library = serde_json::from_value::<Library>(serde_json::json!({
+            "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+            "downloads": {
+                "classifiers": {
+                    "natives-32": artifact("32.jar"),
+                    "natives-64": artifact("64.jar"),
+                },
+            },
+            "natives": { OS: "natives-${arch}" },
+            "rules": [
+                { "action": "allow" },
+                { "action": "disallow", "os": { "name": "elsewhere" } },
+            ],
+        }))
+        .unwrap();
+
+        assert!(library.check_os());
+        assert_eq!(library.get_path(), None);
+        assert_eq!(
+            library.get_natives_artifact(Arch::X64).unwrap().path,
+            "64.jar"
+        );
+        assert_eq!(
+            library.get_natives_artifact(Arch::X86).unwrap().path,
+            "32.jar"
+        );
+    }
+
+    #[test]
+    fn derives_maven_paths() {
+        assert_eq!(
+            get_maven_path("net.minecraftforge:forge:1.12.2-14.23.5.2860:universal").as_deref(),
+            Some("net/minecraftforge/forge/1.12.2-14.23.5.2860/forge-1.12.2-14.23.5.2860-universal.jar")
+        );
+        assert_eq!(
+            get_maven_path("de.oceanlabs.mcp:mcp_config:1.16.5@zip").as_deref(),
+            Some("de/oceanlabs/mcp/mcp_config/1.16.5/mcp_config-1.16.5.zip")
+        );
+        assert_eq!(get_maven_path("invalid"), None);
+    }
+
+    #[test]
+    fn tells_the_natives_arch() {
+        let arch = |path| get_natives_arch(path);
+
+        assert_eq!(arch("lwjgl-3.3.1-natives-linux.jar"), Some(Arch::X64));
+        assert_eq!(
+            arch("lwjgl-3.3.1-natives-linux-arm64.jar"),
+            Some(Arch::Aarch64)
+        );
+        assert_eq!(arch("lwjgl-3.3.1-natives-linux-arm32.jar"), Some(Arch::Arm));
+        assert_eq!(arch("lwjgl-3.3.1-natives-windows-x86.jar"), Some(Arch::X86));
+        assert_eq!(
+            arch("netty-transport-native-epoll-4.1.82.Final-linux-x86_64.jar"),
+            Some(Arch::X64)
+        );
+        assert_eq!(arch("lwjgl-3.3.1.jar"), None);
+    }
+
+    #[test]
+    fn reads_unknown_release_types() {
+        let version = serde_json::from_value::<Version>(serde_json::json!({
+            "id": "1.21-experimental",
+            "type": "experiment",
+            "url": "",
+            "sha1": "",
+        }))
+        .unwrap();
+
+        assert_eq!(version.release_type, ReleaseType::Other);
+    }
+}
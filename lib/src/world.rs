@@ -0,0 +1,270 @@
+// SPDX-FileCopyrightText: 2023 Manuel Quarneti <manuelquarneti@protonmail.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::nbt::{self, Tag};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Survival,
+        GameMode::Creative,
+        GameMode::Adventure,
+        GameMode::Spectator,
+    ];
+
+    fn from_id(id: i32) -> Self {
+        match id {
+            1 => GameMode::Creative,
+            2 => GameMode::Adventure,
+            3 => GameMode::Spectator,
+            _ => GameMode::Survival,
+        }
+    }
+
+    fn id(&self) -> i32 {
+        *self as i32
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Survival => write!(f, "Survival"),
+            GameMode::Creative => write!(f, "Creative"),
+            GameMode::Adventure => write!(f, "Adventure"),
+            GameMode::Spectator => write!(f, "Spectator"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Peaceful,
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+    ];
+
+    fn from_id(id: i8) -> Self {
+        match id {
+            0 => Difficulty::Peaceful,
+            1 => Difficulty::Easy,
+            3 => Difficulty::Hard,
+            _ => Difficulty::Normal,
+        }
+    }
+
+    fn id(&self) -> i8 {
+        *self as i8
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Peaceful => write!(f, "Peaceful"),
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Normal => write!(f, "Normal"),
+            Difficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+// the fields that are safe to change; everything else is written back untouched
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelDat {
+    pub name: String,
+    pub game_mode: GameMode,
+    pub difficulty: Difficulty,
+    pub cheats: bool,
+    // read only
+    pub hardcore: bool,
+    pub version: Option<String>,
+    pub seed: Option<i64>,
+    root_name: String,
+    root: Tag,
+}
+
+fn byte(data: &Tag, key: &str) -> Option<i8> {
+    match data.get(key)? {
+        Tag::Byte(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn int(data: &Tag, key: &str) -> Option<i32> {
+    match data.get(key)? {
+        Tag::Int(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn long(data: &Tag, key: &str) -> Option<i64> {
+    match data.get(key)? {
+        Tag::Long(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn string(data: &Tag, key: &str) -> Option<String> {
+    match data.get(key)? {
+        Tag::String(value) => Some(value.clone()),
+        _ => None,
+    }
+}
+
+impl LevelDat {
+    fn from_nbt(root_name: String, root: Tag) -> Result<Self> {
+        let data = root
+            .get("Data")
+            .ok_or_else(|| anyhow!("level.dat has no Data"))?;
+
+        // the seed moved into the world generation settings in 1.16
+        let seed = data
+            .get("WorldGenSettings")
+            .and_then(|settings| long(settings, "seed"))
+            .or_else(|| long(data, "RandomSeed"));
+
+        Ok(Self {
+            name: string(data, "LevelName").unwrap_or_default(),
+            game_mode: GameMode::from_id(int(data, "GameType").unwrap_or_default()),
+            difficulty: Difficulty::from_id(byte(data, "Difficulty").unwrap_or(2)),
+            cheats: byte(data, "allowCommands").unwrap_or_default() != 0,
+            hardcore: byte(data, "hardcore").unwrap_or_default() != 0,
+            version: data
+                .get("Version")
+                .and_then(|version| string(version, "Name")),
+            seed,
+            root_name,
+            root,
+        })
+    }
+
+    fn to_nbt(&self) -> Result<Tag> {
+        let mut root = self.root.clone();
+        let data = root
+            .get_mut("Data")
+            .ok_or_else(|| anyhow!("level.dat has no Data"))?;
+
+        data.insert("LevelName", Tag::String(self.name.clone()));
+        data.insert("GameType", Tag::Int(self.game_mode.id()));
+        data.insert("Difficulty", Tag::Byte(self.difficulty.id()));
+        data.insert("allowCommands", Tag::Byte(self.cheats.into()));
+
+        // singleplayer worlds keep the player's own game mode, which wins
+        if let Some(player) = data.get_mut("Player") {
+            if player.get("playerGameType").is_some() {
+                player.insert("playerGameType", Tag::Int(self.game_mode.id()));
+            }
+        }
+
+        Ok(root)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("The world needs a name");
+        }
+
+        Ok(())
+    }
+}
+
+pub fn load(world_dir: &Path) -> Result<LevelDat> {
+    let mut bytes = Vec::new();
+    GzDecoder::new(fs::File::open(world_dir.join("level.dat"))?).read_to_end(&mut bytes)?;
+
+    let (root_name, root) = nbt::read(&bytes)?;
+
+    LevelDat::from_nbt(root_name, root)
+}
+
+// the previous level.dat stays next to it as level.dat.bak
+pub fn save(world_dir: &Path, level: &LevelDat) -> Result<()> {
+    level.validate()?;
+
+    let bytes = nbt::write(&level.root_name, &level.to_nbt()?)?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&bytes)?;
+    let compressed = encoder.finish()?;
+
+    let path = world_dir.join("level.dat");
+    let temp_path = world_dir.join("level.dat.tmp");
+    fs::write(&temp_path, compressed)?;
+    fs::copy(&path, world_dir.join("level.dat.bak"))?;
+    fs::rename(temp_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_level_dat_and_keeps_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let root = Tag::Compound(vec![(
+            "Data".to_string(),
+            Tag::Compound(vec![
+                ("LevelName".to_string(), Tag::String("Old".to_string())),
+                ("GameType".to_string(), Tag::Int(0)),
+                ("RandomSeed".to_string(), Tag::Long(7)),
+                (
+                    "Player".to_string(),
+                    Tag::Compound(vec![("playerGameType".to_string(), Tag::Int(0))]),
+                ),
+            ]),
+        )]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&nbt::write("", &root).unwrap()).unwrap();
+        fs::write(dir.path().join("level.dat"), encoder.finish().unwrap()).unwrap();
+
+        let mut level = load(dir.path()).unwrap();
+        assert_eq!(level.name, "Old");
+        assert_eq!(level.seed, Some(7));
+        assert_eq!(level.difficulty, Difficulty::Normal);
+
+        level.name = "New".to_string();
+        level.game_mode = GameMode::Creative;
+        level.cheats = true;
+        save(dir.path(), &level).unwrap();
+
+        let mut level = load(dir.path()).unwrap();
+        assert_eq!(level.name, "New");
+        assert_eq!(level.game_mode, GameMode::Creative);
+        assert!(level.cheats);
+        let data = level.root.get("Data").unwrap();
+        assert_eq!(int(data.get("Player").unwrap(), "playerGameType"), Some(1));
+        assert!(dir.path().join("level.dat.bak").exists());
+
+        level.name.clear();
+        assert!(save(dir.path(), &level).is_err());
+    }
+}